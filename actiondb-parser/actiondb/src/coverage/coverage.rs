@@ -0,0 +1,152 @@
+use uuid::Uuid;
+
+use matcher::Matcher;
+use suggest::{Cluster, Suggester};
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+// Collects how the messages of a corpus are classified by a matcher.
+pub struct Coverage {
+    // in the order of their registration
+    patterns: Vec<PatternCoverage>,
+    indices: HashMap<Uuid, usize>,
+    messages: usize,
+    matched: usize,
+    unmatched: Suggester,
+}
+
+#[derive(Clone, Debug)]
+pub struct PatternCoverage {
+    uuid: Uuid,
+    name: Option<String>,
+    hits: usize,
+    match_time_ns: u64,
+}
+
+impl PatternCoverage {
+    fn new(uuid: Uuid, name: Option<String>) -> PatternCoverage {
+        PatternCoverage {
+            uuid: uuid,
+            name: name,
+            hits: 0,
+            match_time_ns: 0,
+        }
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    // The number of messages which were matched by the pattern.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    // The average time of parsing the messages which were matched by the
+    // pattern in nanoseconds.
+    pub fn average_match_time_ns(&self) -> u64 {
+        if self.hits == 0 {
+            0
+        } else {
+            self.match_time_ns / self.hits as u64
+        }
+    }
+}
+
+impl Coverage {
+    pub fn new() -> Coverage {
+        let mut unmatched = Suggester::new();
+        unmatched.set_max_samples(1);
+        Coverage {
+            patterns: Vec::new(),
+            indices: HashMap::new(),
+            messages: 0,
+            matched: 0,
+            unmatched: unmatched,
+        }
+    }
+
+    // Registers a pattern of the matcher, so it's reported even if it
+    // doesn't match any message.
+    pub fn add_pattern(&mut self, uuid: Uuid, name: Option<String>) {
+        if !self.indices.contains_key(&uuid) {
+            self.indices.insert(uuid.clone(), self.patterns.len());
+            self.patterns.push(PatternCoverage::new(uuid, name));
+        }
+    }
+
+    // Parses the message with the matcher and records the result and the
+    // time spent with it.
+    pub fn parse<M: Matcher + ?Sized>(&mut self, matcher: &M, message: &str) {
+        let start = Instant::now();
+        let result = matcher.parse(message);
+        let elapsed = start.elapsed();
+        let elapsed_ns = elapsed.as_secs() * 1_000_000_000 + elapsed.subsec_nanos() as u64;
+
+        self.messages += 1;
+        match result {
+            Some(result) => {
+                let pattern = result.pattern();
+                self.add_pattern(pattern.uuid().clone(),
+                                 pattern.name().map(|name| name.to_owned()));
+                let index = self.indices[pattern.uuid()];
+                let coverage = &mut self.patterns[index];
+                coverage.hits += 1;
+                coverage.match_time_ns += elapsed_ns;
+                self.matched += 1;
+            }
+            None => self.unmatched.add_message(message),
+        }
+    }
+
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    pub fn matched(&self) -> usize {
+        self.matched
+    }
+
+    pub fn unmatched(&self) -> usize {
+        self.messages - self.matched
+    }
+
+    // The ratio of the matched messages, it's 0 if there were no messages.
+    pub fn match_ratio(&self) -> f64 {
+        if self.messages == 0 {
+            0.0
+        } else {
+            self.matched as f64 / self.messages as f64
+        }
+    }
+
+    // The patterns ordered by their hits, the most frequent first.
+    pub fn patterns(&self) -> Vec<&PatternCoverage> {
+        let mut patterns: Vec<&PatternCoverage> = self.patterns.iter().collect();
+        // the sort is stable, so the patterns with the same hits remain in
+        // the order of their registration
+        patterns.sort_by(|a, b| b.hits.cmp(&a.hits));
+        patterns
+    }
+
+    pub fn patterns_without_hits(&self) -> Vec<&PatternCoverage> {
+        self.patterns.iter().filter(|pattern| pattern.hits == 0).collect()
+    }
+
+    // The token structures of the unmatched messages, the most frequent
+    // first.
+    pub fn unmatched_shapes(&self) -> Vec<&Cluster> {
+        self.unmatched.clusters()
+    }
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Coverage::new()
+    }
+}
@@ -0,0 +1,6 @@
+pub use self::coverage::{Coverage, PatternCoverage};
+
+#[cfg(test)]
+mod test;
+mod coverage;
+mod report;
@@ -0,0 +1,122 @@
+use serde_json;
+use serde_json::builder::{ArrayBuilder, ObjectBuilder};
+
+use super::{Coverage, PatternCoverage};
+
+use std::fmt::Write;
+
+const NS_PER_US: f64 = 1000.0;
+
+impl Coverage {
+    // Renders the coverage as human readable tables. At most `shapes`
+    // unmatched message shapes are listed.
+    pub fn to_table(&self, shapes: usize) -> String {
+        let mut buffer = String::new();
+        let _ = writeln!(buffer, "Messages: {}", self.messages());
+        let _ = writeln!(buffer,
+                         "Matched: {} ({:.2}%)",
+                         self.matched(),
+                         self.match_ratio() * 100.0);
+        let _ = writeln!(buffer, "Unmatched: {}", self.unmatched());
+
+        let patterns = self.patterns();
+        let name_width = patterns.iter()
+                                 .map(|pattern| pattern.name().unwrap_or("").len())
+                                 .fold("NAME".len(), |max, len| if len > max { len } else { max });
+        let _ = writeln!(buffer, "\nHits per pattern:");
+        let _ = writeln!(buffer,
+                         "{:<36} {:<width$} {:>10} {:>8} {:>14}",
+                         "UUID",
+                         "NAME",
+                         "HITS",
+                         "RATIO",
+                         "AVG TIME (us)",
+                         width = name_width);
+        for pattern in patterns {
+            let _ = writeln!(buffer,
+                             "{:<36} {:<width$} {:>10} {:>7.2}% {:>14.3}",
+                             pattern.uuid().hyphenated().to_string(),
+                             pattern.name().unwrap_or(""),
+                             pattern.hits(),
+                             self.hit_ratio(pattern) * 100.0,
+                             pattern.average_match_time_ns() as f64 / NS_PER_US,
+                             width = name_width);
+        }
+
+        let without_hits = self.patterns_without_hits();
+        if !without_hits.is_empty() {
+            let _ = writeln!(buffer, "\nPatterns without hits:");
+            for pattern in without_hits {
+                let _ = writeln!(buffer,
+                                 "{} {}",
+                                 pattern.uuid().hyphenated(),
+                                 pattern.name().unwrap_or(""));
+            }
+        }
+
+        let unmatched_shapes = self.unmatched_shapes();
+        if !unmatched_shapes.is_empty() {
+            let _ = writeln!(buffer, "\nMost frequent unmatched message shapes:");
+            let _ = writeln!(buffer, "{:>10} {}", "COUNT", "SHAPE");
+            for shape in unmatched_shapes.into_iter().take(shapes) {
+                let _ = writeln!(buffer, "{:>10} {}", shape.count(), shape.template());
+            }
+        }
+        buffer
+    }
+
+    // Renders the coverage as a JSON document. At most `shapes` unmatched
+    // message shapes are listed.
+    pub fn to_json(&self, shapes: usize) -> Result<String, serde_json::Error> {
+        let patterns = self.patterns()
+                           .into_iter()
+                           .fold(ArrayBuilder::new(), |builder, pattern| {
+                               builder.push(ObjectBuilder::new()
+                                                .insert("uuid",
+                                                        pattern.uuid().hyphenated().to_string())
+                                                .insert("name", pattern.name())
+                                                .insert("hits", pattern.hits())
+                                                .insert("ratio", self.hit_ratio(pattern))
+                                                .insert("average_match_time_ns",
+                                                        pattern.average_match_time_ns())
+                                                .build())
+                           })
+                           .build();
+        let without_hits = self.patterns_without_hits()
+                               .into_iter()
+                               .fold(ArrayBuilder::new(), |builder, pattern| {
+                                   builder.push(pattern.uuid().hyphenated().to_string())
+                               })
+                               .build();
+        let unmatched_shapes = self.unmatched_shapes()
+                                   .into_iter()
+                                   .take(shapes)
+                                   .fold(ArrayBuilder::new(), |builder, shape| {
+                                       builder.push(ObjectBuilder::new()
+                                                        .insert("shape", shape.template())
+                                                        .insert("count", shape.count())
+                                                        .insert("sample", &shape.samples()[0])
+                                                        .build())
+                                   })
+                                   .build();
+        let report = ObjectBuilder::new()
+                         .insert("messages", self.messages())
+                         .insert("matched", self.matched())
+                         .insert("unmatched", self.unmatched())
+                         .insert("match_ratio", self.match_ratio())
+                         .insert("patterns", patterns)
+                         .insert("patterns_without_hits", without_hits)
+                         .insert("unmatched_shapes", unmatched_shapes)
+                         .build();
+        serde_json::to_string_pretty(&report)
+    }
+
+    // The ratio of the messages which were matched by the pattern.
+    fn hit_ratio(&self, pattern: &PatternCoverage) -> f64 {
+        if self.messages() == 0 {
+            0.0
+        } else {
+            pattern.hits() as f64 / self.messages() as f64
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use uuid::Uuid;
+
+use matcher::Matcher;
+use matcher::pattern::Pattern;
+use matcher::trie::factory::TrieMatcherFactory;
+use matcher::MatcherFactory;
+use super::Coverage;
+
+const UUID_LOGIN: &'static str = "6d2cba0c-e241-464a-89c3-8035cac8f73e";
+const UUID_LOGOUT: &'static str = "9a49c47d-29e9-4072-be84-3b76c6814743";
+
+fn coverage_of(messages: &[&str]) -> Coverage {
+    let login = Pattern::from_json(&format!(r#"{{"uuid": "{}", "name": "LOGIN",
+                                               "pattern": "user %{{GREEDY:user}} logged in"}}"#,
+                                            UUID_LOGIN))
+                    .unwrap();
+    let logout = Pattern::from_json(&format!(r#"{{"uuid": "{}", "name": "LOGOUT",
+                                                "pattern": "user %{{GREEDY:user}} logged out"}}"#,
+                                             UUID_LOGOUT))
+                     .unwrap();
+    let mut coverage = Coverage::new();
+    coverage.add_pattern(login.uuid().clone(), Some("LOGIN".to_owned()));
+    coverage.add_pattern(logout.uuid().clone(), Some("LOGOUT".to_owned()));
+
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(login);
+    matcher.add_pattern(logout);
+    for message in messages {
+        coverage.parse(&matcher, message);
+    }
+    coverage
+}
+
+#[test]
+fn test_given_coverage_when_messages_are_parsed_then_the_hits_are_counted_per_pattern() {
+    let coverage = coverage_of(&["user bob logged in",
+                                 "user alice logged in",
+                                 "disk sda is full",
+                                 "disk sdb is full"]);
+
+    assert_eq!(coverage.messages(), 4);
+    assert_eq!(coverage.matched(), 2);
+    assert_eq!(coverage.unmatched(), 2);
+    assert_eq!(coverage.match_ratio(), 0.5);
+
+    let patterns = coverage.patterns();
+    assert_eq!(patterns[0].uuid(), &Uuid::parse_str(UUID_LOGIN).unwrap());
+    assert_eq!(patterns[0].hits(), 2);
+    assert_eq!(patterns[1].hits(), 0);
+
+    let without_hits = coverage.patterns_without_hits();
+    assert_eq!(without_hits.len(), 1);
+    assert_eq!(without_hits[0].name(), Some("LOGOUT"));
+}
+
+#[test]
+fn test_given_coverage_when_messages_are_unmatched_then_their_shapes_are_counted() {
+    let coverage = coverage_of(&["disk sda is full", "disk sdb is full", "cpu is hot"]);
+
+    let shapes = coverage.unmatched_shapes();
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes[0].template(), "disk <*> is full");
+    assert_eq!(shapes[0].count(), 2);
+}
+
+#[test]
+fn test_given_coverage_when_it_is_rendered_then_both_the_table_and_the_json_contain_the_hits() {
+    let coverage = coverage_of(&["user bob logged in", "disk sda is full", "disk sdb is full"]);
+
+    let table = coverage.to_table(10);
+    assert!(table.contains("Matched: 1 (33.33%)"));
+    assert!(table.contains("Patterns without hits:"));
+    assert!(table.contains("         2 disk <*> is full"));
+
+    let json = coverage.to_json(10).unwrap();
+    assert!(json.contains("\"matched\": 1"));
+    assert!(json.contains(&format!("\"patterns_without_hits\": [\n    \"{}\"", UUID_LOGOUT)));
+    assert!(json.contains("\"shape\": \"disk <*> is full\""));
+}
@@ -0,0 +1,197 @@
+use uuid::Uuid;
+
+use matcher::Matcher;
+use matcher::result::MatchResult;
+
+use std::collections::{BTreeMap, HashMap};
+
+const DEFAULT_MAX_EXAMPLES: usize = 3;
+
+// Compares the classification of messages by two matchers, usually built
+// from two versions of the same pattern files.
+pub struct Diff {
+    max_examples: usize,
+    messages: usize,
+    changed: usize,
+    // in the order of their first appearance
+    groups: Vec<ChangeGroup>,
+    indices: HashMap<(Option<PatternRef>, Option<PatternRef>), usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PatternRef {
+    uuid: Uuid,
+    name: Option<String>,
+}
+
+impl PatternRef {
+    fn from_result(result: &MatchResult) -> PatternRef {
+        PatternRef {
+            uuid: result.pattern().uuid().clone(),
+            name: result.pattern().name().map(|name| name.to_owned()),
+        }
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+}
+
+// The messages whose classification changed the same way: they were
+// matched by the same old pattern and they are matched by the same new one.
+#[derive(Clone, Debug)]
+pub struct ChangeGroup {
+    old: Option<PatternRef>,
+    new: Option<PatternRef>,
+    count: usize,
+    examples: Vec<Example>,
+}
+
+impl ChangeGroup {
+    // The old pattern, `None` if the messages weren't matched.
+    pub fn old_pattern(&self) -> Option<&PatternRef> {
+        self.old.as_ref()
+    }
+
+    // The new pattern, `None` if the messages aren't matched.
+    pub fn new_pattern(&self) -> Option<&PatternRef> {
+        self.new.as_ref()
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn examples(&self) -> &[Example] {
+        &self.examples
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Example {
+    message: String,
+    old_values: BTreeMap<String, String>,
+    new_values: BTreeMap<String, String>,
+}
+
+impl Example {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn old_values(&self) -> &BTreeMap<String, String> {
+        &self.old_values
+    }
+
+    pub fn new_values(&self) -> &BTreeMap<String, String> {
+        &self.new_values
+    }
+}
+
+impl Diff {
+    pub fn new() -> Diff {
+        Diff::default()
+    }
+
+    // The maximum number of example messages of a change group.
+    pub fn set_max_examples(&mut self, max_examples: usize) {
+        self.max_examples = max_examples;
+    }
+
+    // Parses the message with both matchers and records the change if the
+    // pattern, its name or the extracted values differ.
+    pub fn compare<O, N>(&mut self, old: &O, new: &N, message: &str)
+        where O: Matcher + ?Sized,
+              N: Matcher + ?Sized
+    {
+        let old_result = old.parse(message);
+        let new_result = new.parse(message);
+        let old_pattern = old_result.as_ref().map(PatternRef::from_result);
+        let new_pattern = new_result.as_ref().map(PatternRef::from_result);
+        let old_values = old_result.as_ref().map(values).unwrap_or_default();
+        let new_values = new_result.as_ref().map(values).unwrap_or_default();
+
+        self.messages += 1;
+        if old_pattern == new_pattern && old_values == new_values {
+            return;
+        }
+        self.changed += 1;
+
+        let key = (old_pattern, new_pattern);
+        let index = match self.indices.get(&key) {
+            Some(index) => *index,
+            None => {
+                self.groups.push(ChangeGroup {
+                    old: key.0.clone(),
+                    new: key.1.clone(),
+                    count: 0,
+                    examples: Vec::new(),
+                });
+                self.groups.len() - 1
+            }
+        };
+        self.indices.insert(key, index);
+
+        let group = &mut self.groups[index];
+        group.count += 1;
+        if group.examples.len() < self.max_examples {
+            group.examples.push(Example {
+                message: message.to_owned(),
+                old_values: old_values,
+                new_values: new_values,
+            });
+        }
+    }
+
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    // The number of messages whose classification changed.
+    pub fn changed(&self) -> usize {
+        self.changed
+    }
+
+    // The change groups, the biggest first.
+    pub fn groups(&self) -> Vec<&ChangeGroup> {
+        let mut groups: Vec<&ChangeGroup> = self.groups.iter().collect();
+        // the sort is stable, so the groups with the same size remain in the
+        // order of their first appearance
+        groups.sort_by(|a, b| b.count.cmp(&a.count));
+        groups
+    }
+}
+
+impl Default for Diff {
+    fn default() -> Self {
+        Diff {
+            max_examples: DEFAULT_MAX_EXAMPLES,
+            messages: 0,
+            changed: 0,
+            groups: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+// The parsed values take precedence over the additional values of the
+// pattern.
+fn values(result: &MatchResult) -> BTreeMap<String, String> {
+    let mut values: BTreeMap<String, String> = result.additional_values()
+                                                     .into_iter()
+                                                     .map(|(key, value)| {
+                                                         (key.to_owned(), value.into_owned())
+                                                     })
+                                                     .collect();
+    for (key, value) in result.values() {
+        values.insert((*key).to_owned(), (*value).to_owned());
+    }
+    for (key, value) in result.indexed_values() {
+        values.insert(key, value.into_owned());
+    }
+    values
+}
@@ -0,0 +1,6 @@
+pub use self::diff::{ChangeGroup, Diff, Example, PatternRef};
+
+#[cfg(test)]
+mod test;
+mod diff;
+mod report;
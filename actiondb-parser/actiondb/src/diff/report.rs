@@ -0,0 +1,105 @@
+use serde_json::{self, Value};
+use serde_json::builder::{ArrayBuilder, ObjectBuilder};
+
+use super::{ChangeGroup, Diff, Example, PatternRef};
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const NO_MATCH: &'static str = "<no match>";
+
+impl Diff {
+    // Renders the changes as human readable text, grouped by the old and the
+    // new patterns.
+    pub fn to_text(&self) -> String {
+        let mut buffer = String::new();
+        let _ = writeln!(buffer, "Messages: {}", self.messages());
+        let _ = writeln!(buffer, "Changed: {}", self.changed());
+
+        for group in self.groups() {
+            let _ = writeln!(buffer,
+                             "\n{} message(s): {} -> {}",
+                             group.count(),
+                             describe(group.old_pattern()),
+                             describe(group.new_pattern()));
+            for example in group.examples() {
+                let _ = writeln!(buffer, "  message: {}", example.message());
+                if example.old_values() != example.new_values() {
+                    let _ = writeln!(buffer,
+                                     "    old values: {}",
+                                     format_values(example.old_values()));
+                    let _ = writeln!(buffer,
+                                     "    new values: {}",
+                                     format_values(example.new_values()));
+                }
+            }
+        }
+        buffer
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let groups = self.groups()
+                         .into_iter()
+                         .fold(ArrayBuilder::new(),
+                               |builder, group| builder.push(group_to_json(group)))
+                         .build();
+        let diff = ObjectBuilder::new()
+                       .insert("messages", self.messages())
+                       .insert("changed", self.changed())
+                       .insert("groups", groups)
+                       .build();
+        serde_json::to_string_pretty(&diff)
+    }
+}
+
+fn describe(pattern: Option<&PatternRef>) -> String {
+    match pattern {
+        Some(pattern) => {
+            format!("{} ({})",
+                    pattern.name().unwrap_or(""),
+                    pattern.uuid().hyphenated())
+        }
+        None => NO_MATCH.to_owned(),
+    }
+}
+
+fn format_values(values: &BTreeMap<String, String>) -> String {
+    let values: Vec<String> = values.iter()
+                                    .map(|(key, value)| format!("{}={}", key, value))
+                                    .collect();
+    values.join(" ")
+}
+
+fn pattern_to_json(pattern: Option<&PatternRef>) -> Value {
+    match pattern {
+        Some(pattern) => {
+            ObjectBuilder::new()
+                .insert("uuid", pattern.uuid().hyphenated().to_string())
+                .insert("name", pattern.name())
+                .build()
+        }
+        None => Value::Null,
+    }
+}
+
+fn group_to_json(group: &ChangeGroup) -> Value {
+    let examples = group.examples()
+                        .iter()
+                        .fold(ArrayBuilder::new(),
+                              |builder, example| builder.push(example_to_json(example)))
+                        .build();
+    ObjectBuilder::new()
+        .insert("old", pattern_to_json(group.old_pattern()))
+        .insert("new", pattern_to_json(group.new_pattern()))
+        .insert("count", group.count())
+        .insert("examples", examples)
+        .build()
+}
+
+fn example_to_json(example: &Example) -> Value {
+    ObjectBuilder::new()
+        .insert("message", example.message())
+        .insert("old_values", example.old_values())
+        .insert("new_values", example.new_values())
+        .build()
+}
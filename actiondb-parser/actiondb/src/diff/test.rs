@@ -0,0 +1,79 @@
+use matcher::{Matcher, MatcherFactory};
+use matcher::pattern::Pattern;
+use matcher::trie::factory::TrieMatcherFactory;
+use super::Diff;
+
+const UUID_LOGIN: &'static str = "6d2cba0c-e241-464a-89c3-8035cac8f73e";
+const UUID_LOGOUT: &'static str = "9a49c47d-29e9-4072-be84-3b76c6814743";
+
+fn matcher(patterns: &[(&str, &str, &str)]) -> <TrieMatcherFactory as MatcherFactory>::Matcher {
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    for &(uuid, name, pattern) in patterns {
+        let json = format!(r#"{{"uuid": "{}", "name": "{}", "pattern": "{}"}}"#,
+                           uuid,
+                           name,
+                           pattern);
+        matcher.add_pattern(Pattern::from_json(&json).expect("Failed to deserialize a pattern"));
+    }
+    matcher
+}
+
+#[test]
+fn test_given_diff_when_the_classification_does_not_change_then_no_change_is_recorded() {
+    let old = matcher(&[(UUID_LOGIN, "LOGIN", "user %{GREEDY:user} logged in")]);
+    let new = matcher(&[(UUID_LOGIN, "LOGIN", "user %{GREEDY:user} logged in")]);
+    let mut diff = Diff::new();
+
+    diff.compare(&old, &new, "user bob logged in");
+    diff.compare(&old, &new, "disk is full");
+
+    assert_eq!(diff.messages(), 2);
+    assert_eq!(diff.changed(), 0);
+    assert!(diff.groups().is_empty());
+}
+
+#[test]
+fn test_given_diff_when_the_classification_changes_then_the_changes_are_grouped_by_the_patterns() {
+    let old = matcher(&[(UUID_LOGIN, "LOGIN", "user %{GREEDY:user} logged in")]);
+    let new = matcher(&[(UUID_LOGIN, "LOGIN", "user %{GREEDY:username} logged in"),
+                        (UUID_LOGOUT, "LOGOUT", "user %{GREEDY:user} logged out")]);
+    let mut diff = Diff::new();
+    diff.set_max_examples(1);
+
+    diff.compare(&old, &new, "user bob logged out");
+    diff.compare(&old, &new, "user bob logged in");
+    diff.compare(&old, &new, "user alice logged in");
+
+    assert_eq!(diff.changed(), 3);
+    let groups = diff.groups();
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].count(), 2);
+    assert_eq!(groups[0].old_pattern().and_then(|pattern| pattern.name()), Some("LOGIN"));
+    assert_eq!(groups[0].new_pattern().and_then(|pattern| pattern.name()), Some("LOGIN"));
+    assert_eq!(groups[0].examples().len(), 1);
+    let example = &groups[0].examples()[0];
+    assert_eq!(example.old_values().get("user").map(|user| user.as_str()), Some("bob"));
+    assert_eq!(example.new_values().get("username").map(|user| user.as_str()), Some("bob"));
+
+    assert!(groups[1].old_pattern().is_none());
+    assert_eq!(groups[1].new_pattern().and_then(|pattern| pattern.name()), Some("LOGOUT"));
+}
+
+#[test]
+fn test_given_diff_when_it_is_rendered_then_the_groups_are_described() {
+    let old = matcher(&[(UUID_LOGIN, "LOGIN", "user %{GREEDY:user} logged in")]);
+    let new = matcher(&[(UUID_LOGOUT, "LOGOUT", "user %{GREEDY:user} logged out")]);
+    let mut diff = Diff::new();
+
+    diff.compare(&old, &new, "user bob logged in");
+
+    let text = diff.to_text();
+    assert!(text.contains(&format!("1 message(s): LOGIN ({}) -> <no match>", UUID_LOGIN)));
+    assert!(text.contains("  message: user bob logged in\n    old values: user=bob\n    new \
+                           values: \n"));
+
+    let json = diff.to_json().unwrap();
+    assert!(json.contains("\"new\": null"));
+    assert!(json.contains(&format!("\"uuid\": \"{}\"", UUID_LOGIN)));
+}
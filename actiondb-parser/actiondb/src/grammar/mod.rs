@@ -2,6 +2,107 @@
 mod test;
 pub mod parser;
 
+use matcher::compiled_pattern::{CompiledPattern, TokenType};
+use parsers::ParserType;
+
+// The line ending which is matched by `%{NEWLINE}`.
+pub const NEWLINE: &'static str = "\n";
+const NEWLINE_TOKEN: &'static str = "%{NEWLINE}";
+
 pub fn unescape_literal(literal: &str) -> String {
     literal.replace(r#"\%\{"#, "%{")
 }
+
+pub fn escape_literal(literal: &str) -> String {
+    literal.replace("%{", r#"\%\{"#)
+}
+
+// Escapes the backslashes and quotation marks of a string parameter, like the
+// separator of REPEAT.
+pub fn escape_string(string: &str) -> String {
+    string.replace('\\', r#"\\"#).replace('"', r#"\""#)
+}
+
+// `%{NEWLINE}` is parsed as a separate literal, so the neighbouring literals
+// are merged into one.
+pub fn merge_literals(tokens: Vec<TokenType>) -> CompiledPattern {
+    let mut merged: CompiledPattern = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if let TokenType::Literal(ref literal) = token {
+            if let Some(&mut TokenType::Literal(ref mut previous)) = merged.last_mut() {
+                previous.push_str(literal);
+                continue;
+            }
+        }
+        merged.push(token);
+    }
+    merged
+}
+
+// Renders a compiled pattern in the grammar, so parsing the result gives back
+// an equivalent pattern. The end string of a GREEDY parser is not rendered,
+// because it's the literal token which follows the parser. Line feeds are
+// rendered as `%{NEWLINE}`.
+pub fn format_pattern(pattern: &[TokenType]) -> String {
+    let mut formatted = String::new();
+    for token in pattern {
+        match *token {
+            TokenType::Literal(ref literal) => {
+                formatted.push_str(&escape_literal(literal).replace(NEWLINE, NEWLINE_TOKEN))
+            }
+            TokenType::Parser(ref parser) => {
+                formatted.push_str("%{");
+                formatted.push_str(&format_parser_type(parser.parser_type()));
+                if let Some(name) = parser.name() {
+                    formatted.push(':');
+                    formatted.push_str(name);
+                }
+                formatted.push('}');
+            }
+        }
+    }
+    formatted
+}
+
+// Renders a parser without its name, like `SET("ab",min_len=1)`.
+fn format_parser_type(parser_type: ParserType) -> String {
+    let (parser_name, params) = match parser_type {
+        ParserType::Set { characters, min_length, max_length, .. } => {
+            let mut params = vec![format!("\"{}\"", characters)];
+            params.extend(format_length_params(min_length, max_length));
+            ("SET", params)
+        }
+        ParserType::Int { min_length, max_length } => {
+            ("INT", format_length_params(min_length, max_length))
+        }
+        ParserType::Greedy { .. } => ("GREEDY", Vec::new()),
+        ParserType::Lines { count } => ("LINES", vec![count.to_string()]),
+        ParserType::Repeat { item, separator, min, max } => {
+            let mut params = vec![format_parser_type(*item),
+                                  format!("separator=\"{}\"", escape_string(separator))];
+            if min != 1 {
+                params.push(format!("min={}", min));
+            }
+            if let Some(max) = max {
+                params.push(format!("max={}", max));
+            }
+            ("REPEAT", params)
+        }
+    };
+    if params.is_empty() {
+        parser_name.to_owned()
+    } else {
+        format!("{}({})", parser_name, params.join(","))
+    }
+}
+
+fn format_length_params(min_length: Option<usize>, max_length: Option<usize>) -> Vec<String> {
+    let mut params = Vec::new();
+    if let Some(min_length) = min_length {
+        params.push(format!("min_len={}", min_length));
+    }
+    if let Some(max_length) = max_length {
+        params.push(format!("max_len={}", max_length));
+    }
+    params
+}
@@ -1,7 +1,8 @@
 // Generated by rust-peg. Do not edit.
 #![allow(non_snake_case, unused)]
 use matcher::compiled_pattern::{CompiledPattern, TokenType};
-use parsers::{SetParser, IntParser, Parser, OptionalParameter, GreedyParser};
+use parsers::{SetParser, IntParser, Parser, OptionalParameter, GreedyParser, LinesParser,
+              RepeatParser};
 use grammar;
 use utils;
 use parsers::ParserFactory;
@@ -162,7 +163,7 @@ fn parse_pattern<'input, F: ParserFactory>(input: &'input str,
                         let match_str = &input[start_pos..pos];
                         Matched(pos, {
                             let mut pieces = pieces;
-                            utils::flatten_vec(pieces)
+                            grammar::merge_literals(utils::flatten_vec(pieces))
                         })
                     }
                 }
@@ -180,11 +181,39 @@ fn parse_pattern_piece<'input, F: ParserFactory>(input: &'input str,
         match choice_res {
             Matched(pos, value) => Matched(pos, value),
             Failed => {
-                let choice_res = parse_piece_literal::<F>(input, state, pos);
+                let choice_res = parse_piece_NEWLINE::<F>(input, state, pos);
                 match choice_res {
                     Matched(pos, value) => Matched(pos, value),
-                    Failed => parse_piece_parser::<F>(input, state, pos),
+                    Failed => {
+                        let choice_res = parse_piece_literal::<F>(input, state, pos);
+                        match choice_res {
+                            Matched(pos, value) => Matched(pos, value),
+                            Failed => parse_piece_parser::<F>(input, state, pos),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+fn parse_piece_NEWLINE<'input, F: ParserFactory>(input: &'input str,
+                                                 state: &mut ParseState<'input>,
+                                                 pos: usize)
+                                                 -> RuleResult<Vec<TokenType>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_newline::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, newline) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            vec![TokenType::Literal(newline.to_owned())]
+                        })
+                    }
                 }
+                Failed => Failed,
             }
         }
     }
@@ -258,7 +287,19 @@ fn parse_parser<'input, F: ParserFactory>(input: &'input str,
         let choice_res = parse_parser_SET::<F>(input, state, pos);
         match choice_res {
             Matched(pos, value) => Matched(pos, value),
-            Failed => parse_parser_INT::<F>(input, state, pos),
+            Failed => {
+                let choice_res = parse_parser_INT::<F>(input, state, pos);
+                match choice_res {
+                    Matched(pos, value) => Matched(pos, value),
+                    Failed => {
+                        let choice_res = parse_parser_LINES::<F>(input, state, pos);
+                        match choice_res {
+                            Matched(pos, value) => Matched(pos, value),
+                            Failed => parse_parser_REPEAT::<F>(input, state, pos),
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -546,42 +587,36 @@ fn parse_parser_INT_optional_params<'input, F: ParserFactory>
         }
     }
 }
-fn parse_parser_GREEDY<'input, F: ParserFactory>(input: &'input str,
-                                                 state: &mut ParseState<'input>,
-                                                 pos: usize)
-                                                 -> RuleResult<Vec<TokenType>> {
+fn parse_parser_LINES<'input, F: ParserFactory>(input: &'input str,
+                                                state: &mut ParseState<'input>,
+                                                pos: usize)
+                                                -> RuleResult<Box<Parser>> {
     {
         let start_pos = pos;
         {
-            let seq_res = parse_PARSER_BEGIN::<F>(input, state, pos);
+            let seq_res = parse_LINES::<F>(input, state, pos);
             match seq_res {
                 Matched(pos, _) => {
                     {
-                        let seq_res = parse_GREEDY::<F>(input, state, pos);
+                        let seq_res = parse_PARSER_PARAMS_BEGIN::<F>(input, state, pos);
                         match seq_res {
                             Matched(pos, _) => {
                                 {
-                                    let seq_res = match parse_parser_name::<F>(input, state, pos) {
-                                        Matched(newpos, value) => {
-                                            Matched(newpos, Some(value))
-                                        }
-                                        Failed => {
-                                            Matched(pos, None)
-                                        }
-                                    };
+                                    let seq_res = parse_positive_int::<F>(input, state, pos);
                                     match seq_res {
-                                        Matched(pos, name) => {
+                                        Matched(pos, count) => {
                                             {
-                                                let seq_res = parse_PARSER_END::<F>(input,
-                                                                                    state,
-                                                                                    pos);
+                                                let seq_res =
+                                                    parse_PARSER_PARAMS_END::<F>(input,
+                                                                                 state,
+                                                                                 pos);
                                                 match seq_res {
                                                     Matched(pos, _) => {
                                                         {
                                                             let seq_res =
-                                                                match parse_literal::<F>(input,
-                                                                                         state,
-                                                                                         pos) {
+                                                                match parse_parser_name::<F>(input,
+                                                                                             state,
+                                                                                             pos) {
                                                                     Matched(newpos, value) => {
                                                                         Matched(newpos, Some(value))
                                                                     }
@@ -590,29 +625,14 @@ fn parse_parser_GREEDY<'input, F: ParserFactory>(input: &'input str,
                                                                     }
                                                                 };
                                                             match seq_res {
-                                                                Matched(pos, end_string) => {
+                                                                Matched(pos, name) => {
                                                                     {
                                                                         let match_str =
                                                                             &input[start_pos..pos];
-                                                                        Matched(pos,
-                                                                                {
-                                                                                    let mut tokens =
-                                                                                        Vec::new();
-                                                                                    let parser =
-                                                                                        F::new_greedy(name,
-                                                                                                      end_string);
-                                                                                    tokens.push(TokenType::Parser(parser));
-                                                                                    let end_string =
-                                                                                        end_string.map(|string|
-                                                                                                           string.to_owned());
-                                                                                    if let Some(end_string)
-                                                                                           =
-                                                                                           end_string
-                                                                                           {
-                                                                                        tokens.push(TokenType::Literal(end_string));
-                                                                                    }
-                                                                                    tokens
-                                                                                })
+                                                                        Matched(pos, {
+                                                                            F::new_lines(name,
+                                                                                         count)
+                                                                        })
                                                                     }
                                                                 }
                                                                 Failed => Failed,
@@ -636,91 +656,855 @@ fn parse_parser_GREEDY<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
-fn parse_parser_BASE_optional_param<'input, F: ParserFactory>
-                                                              (input: &'input str,
-                                                               state: &mut ParseState<'input>,
-                                                               pos: usize)
-                                                               -> RuleResult<OptionalParameter<'input>> {
+fn parse_parser_REPEAT<'input, F: ParserFactory>(input: &'input str,
+                                                 state: &mut ParseState<'input>,
+                                                 pos: usize)
+                                                 -> RuleResult<Box<Parser>> {
     {
-        let choice_res = {
-            let start_pos = pos;
-            {
-                let seq_res = parse_MIN_LEN::<F>(input, state, pos);
-                match seq_res {
-                    Matched(pos, name) => {
-                        {
-                            let seq_res = slice_eq(input, state, pos, "=");
-                            match seq_res {
-                                Matched(pos, _) => {
-                                    {
-                                        let seq_res = parse_int::<F>(input, state, pos);
-                                        match seq_res {
-                                            Matched(pos, value) => {
-                                                {
-                                                    let match_str = &input[start_pos..pos];
-                                                    Matched(pos, {
-                                                        OptionalParameter::Int(name, value)
-                                                    })
+        let start_pos = pos;
+        {
+            let seq_res = parse_REPEAT::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = parse_PARSER_PARAMS_BEGIN::<F>(input, state, pos);
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = parse_parser_REPEAT_item::<F>(input, state, pos);
+                                    match seq_res {
+                                        Matched(pos, item) => {
+                                            {
+                                                let seq_res = parse_comma::<F>(input, state, pos);
+                                                match seq_res {
+                                                    Matched(pos, _) => {
+                                                        {
+                                                            let seq_res =
+                                                                parse_parser_REPEAT_separator::<F>(input,
+                                                                                                   state,
+                                                                                                   pos);
+                                                            match seq_res {
+                                                                Matched(pos, separator) => {
+                                                                    {
+                                                                        let seq_res =
+                                                                            match parse_parser_REPEAT_optional_params::<F>(input,
+                                                                                                                      state,
+                                                                                                                      pos)
+                                                                                {
+                                                                                Matched(newpos,
+                                                                                        value)
+                                                                                =>
+                                                                                {
+                                                                                    Matched(newpos,
+                                                                                            Some(value))
+                                                                                }
+                                                                                Failed
+                                                                                =>
+                                                                                {
+                                                                                    Matched(pos,
+                                                                                            None)
+                                                                                }
+                                                                            };
+                                                                        match seq_res {
+                                                                            Matched(pos, opt_params) => {
+                                                                                {
+                                                                                    let seq_res =
+                                                                                        parse_PARSER_PARAMS_END::<F>(input,
+                                                                                                                     state,
+                                                                                                                     pos);
+                                                                                    match seq_res {
+                                                                                        Matched(pos, _) => {
+                                                                                            {
+                                                                                                let seq_res =
+                                                                                                    match parse_parser_name::<F>(input,
+                                                                                                                                 state,
+                                                                                                                                 pos) {
+                                                                                                        Matched(newpos, value) => {
+                                                                                                            Matched(newpos, Some(value))
+                                                                                                        }
+                                                                                                        Failed => {
+                                                                                                            Matched(pos, None)
+                                                                                                        }
+                                                                                                    };
+                                                                                                match seq_res {
+                                                                                                    Matched(pos, name) => {
+                                                                                                        {
+                                                                                                            let match_str =
+                                                                                                                &input[start_pos..pos];
+                                                                                                            Matched(pos, {
+                                                                                                                F::new_repeat(name,
+                                                                                                                              item,
+                                                                                                                              &separator,
+                                                                                                                              opt_params)
+                                                                                                            })
+                                                                                                        }
+                                                                                                    }
+                                                                                                    Failed => Failed,
+                                                                                                }
+                                                                                            }
+                                                                                        }
+                                                                                        Failed => Failed,
+                                                                                    }
+                                                                                }
+                                                                            }
+                                                                            Failed => Failed,
+                                                                        }
+                                                                    }
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                    }
+                                                    Failed => Failed,
                                                 }
                                             }
-                                            Failed => Failed,
                                         }
+                                        Failed => Failed,
                                     }
                                 }
-                                Failed => Failed,
                             }
+                            Failed => Failed,
                         }
                     }
-                    Failed => Failed,
                 }
+                Failed => Failed,
             }
-        };
+        }
+    }
+}
+fn parse_parser_REPEAT_item<'input, F: ParserFactory>(input: &'input str,
+                                                      state: &mut ParseState<'input>,
+                                                      pos: usize)
+                                                      -> RuleResult<Box<Parser>> {
+    {
+        let choice_res = parse_parser_REPEAT_item_SET::<F>(input, state, pos);
         match choice_res {
             Matched(pos, value) => Matched(pos, value),
             Failed => {
-                let start_pos = pos;
-                {
-                    let seq_res = parse_MAX_LEN::<F>(input, state, pos);
-                    match seq_res {
-                        Matched(pos, name) => {
-                            {
-                                let seq_res = slice_eq(input, state, pos, "=");
-                                match seq_res {
-                                    Matched(pos, _) => {
-                                        {
-                                            let seq_res = parse_int::<F>(input, state, pos);
-                                            match seq_res {
-                                                Matched(pos, value) => {
-                                                    {
-                                                        let match_str = &input[start_pos..pos];
-                                                        Matched(pos, {
-                                                            OptionalParameter::Int(name, value)
-                                                        })
+                let choice_res = parse_parser_REPEAT_item_INT::<F>(input, state, pos);
+                match choice_res {
+                    Matched(pos, value) => Matched(pos, value),
+                    Failed => parse_parser_REPEAT_item_LINES::<F>(input, state, pos),
+                }
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_item_SET<'input, F: ParserFactory>(input: &'input str,
+                                                          state: &mut ParseState<'input>,
+                                                          pos: usize)
+                                                          -> RuleResult<Box<Parser>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_SET::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = parse_PARSER_PARAMS_BEGIN::<F>(input, state, pos);
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = parse_string::<F>(input, state, pos);
+                                    match seq_res {
+                                        Matched(pos, set) => {
+                                            {
+                                                let seq_res =
+                                                    match parse_parser_SET_optional_params::<F>(input,
+                                                                                           state,
+                                                                                           pos)
+                                                        {
+                                                        Matched(newpos, value)
+                                                        => {
+                                                            Matched(newpos,
+                                                                    Some(value))
+                                                        }
+                                                        Failed => {
+                                                            Matched(pos, None)
+                                                        }
+                                                    };
+                                                match seq_res {
+                                                    Matched(pos, opt_params) => {
+                                                        {
+                                                            let seq_res =
+                                                                parse_PARSER_PARAMS_END::<F>(input,
+                                                                                             state,
+                                                                                             pos);
+                                                            match seq_res {
+                                                                Matched(pos, _) => {
+                                                                    {
+                                                                        let match_str =
+                                                                            &input[start_pos..pos];
+                                                                        Matched(pos, {
+                                                                            F::new_set(set,
+                                                                                       None,
+                                                                                       opt_params)
+                                                                        })
+                                                                    }
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
                                                     }
+                                                    Failed => Failed,
                                                 }
-                                                Failed => Failed,
                                             }
                                         }
+                                        Failed => Failed,
                                     }
-                                    Failed => Failed,
                                 }
                             }
+                            Failed => Failed,
                         }
-                        Failed => Failed,
                     }
                 }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_item_INT<'input, F: ParserFactory>(input: &'input str,
+                                                          state: &mut ParseState<'input>,
+                                                          pos: usize)
+                                                          -> RuleResult<Box<Parser>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_INT::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = match parse_parser_INT_optional_params::<F>(input,
+                                                                                  state,
+                                                                                  pos) {
+                            Matched(newpos, value) => {
+                                Matched(newpos, Some(value))
+                            }
+                            Failed => {
+                                Matched(pos, None)
+                            }
+                        };
+                        match seq_res {
+                            Matched(pos, opt_params) => {
+                                {
+                                    let match_str = &input[start_pos..pos];
+                                    Matched(pos, {
+                                        F::new_int(None, opt_params)
+                                    })
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_item_LINES<'input, F: ParserFactory>(input: &'input str,
+                                                            state: &mut ParseState<'input>,
+                                                            pos: usize)
+                                                            -> RuleResult<Box<Parser>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_LINES::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = parse_PARSER_PARAMS_BEGIN::<F>(input, state, pos);
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = parse_positive_int::<F>(input, state, pos);
+                                    match seq_res {
+                                        Matched(pos, count) => {
+                                            {
+                                                let seq_res =
+                                                    parse_PARSER_PARAMS_END::<F>(input,
+                                                                                 state,
+                                                                                 pos);
+                                                match seq_res {
+                                                    Matched(pos, _) => {
+                                                        {
+                                                            let match_str =
+                                                                &input[start_pos..pos];
+                                                            Matched(pos, {
+                                                                F::new_lines(None, count)
+                                                            })
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_separator<'input, F: ParserFactory>(input: &'input str,
+                                                           state: &mut ParseState<'input>,
+                                                           pos: usize)
+                                                           -> RuleResult<String> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_SEPARATOR::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = slice_eq(input, state, pos, "=");
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = parse_escaped_string::<F>(input, state, pos);
+                                    match seq_res {
+                                        Matched(pos, separator) => {
+                                            {
+                                                let match_str = &input[start_pos..pos];
+                                                Matched(pos, {
+                                                    separator
+                                                })
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_optional_params<'input, F: ParserFactory>
+                                                              (input: &'input str,
+                                                               state: &mut ParseState<'input>,
+                                                               pos: usize)
+                                                               -> RuleResult<Vec<OptionalParameter<'input>>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_comma::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = {
+                            let mut repeat_pos = pos;
+                            let mut repeat_value = vec![];
+                            loop {
+                                let pos = repeat_pos;
+                                let pos = if !repeat_value.is_empty() {
+                                    let sep_res = parse_comma::<F>(input, state, pos);
+                                    match sep_res {
+                                        Matched(newpos, _) => {
+                                            newpos
+                                        }
+                                        Failed => break,
+                                    }
+                                } else {
+                                    pos
+                                };
+                                let step_res = parse_parser_REPEAT_optional_param::<F>(input,
+                                                                                       state,
+                                                                                       pos);
+                                match step_res {
+                                    Matched(newpos, value) => {
+                                        repeat_pos = newpos;
+                                        repeat_value.push(value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            Matched(repeat_pos, repeat_value)
+                        };
+                        match seq_res {
+                            Matched(pos, params) => {
+                                {
+                                    let match_str = &input[start_pos..pos];
+                                    Matched(pos, {
+                                        params
+                                    })
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_REPEAT_optional_param<'input, F: ParserFactory>
+                                                              (input: &'input str,
+                                                               state: &mut ParseState<'input>,
+                                                               pos: usize)
+                                                               -> RuleResult<OptionalParameter<'input>> {
+    {
+        let choice_res = {
+            let start_pos = pos;
+            {
+                let seq_res = parse_MIN::<F>(input, state, pos);
+                match seq_res {
+                    Matched(pos, name) => {
+                        {
+                            let seq_res = slice_eq(input, state, pos, "=");
+                            match seq_res {
+                                Matched(pos, _) => {
+                                    {
+                                        let seq_res = parse_positive_int::<F>(input, state, pos);
+                                        match seq_res {
+                                            Matched(pos, value) => {
+                                                {
+                                                    let match_str = &input[start_pos..pos];
+                                                    Matched(pos, {
+                                                        OptionalParameter::Int(name, value)
+                                                    })
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        };
+        match choice_res {
+            Matched(pos, value) => Matched(pos, value),
+            Failed => {
+                let start_pos = pos;
+                {
+                    let seq_res = parse_MAX::<F>(input, state, pos);
+                    match seq_res {
+                        Matched(pos, name) => {
+                            {
+                                let seq_res = slice_eq(input, state, pos, "=");
+                                match seq_res {
+                                    Matched(pos, _) => {
+                                        {
+                                            let seq_res = parse_positive_int::<F>(input, state, pos);
+                                            match seq_res {
+                                                Matched(pos, value) => {
+                                                    {
+                                                        let match_str = &input[start_pos..pos];
+                                                        Matched(pos, {
+                                                            OptionalParameter::Int(name, value)
+                                                        })
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+            }
+        }
+    }
+}
+fn parse_parser_GREEDY<'input, F: ParserFactory>(input: &'input str,
+                                                 state: &mut ParseState<'input>,
+                                                 pos: usize)
+                                                 -> RuleResult<Vec<TokenType>> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_PARSER_BEGIN::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = parse_GREEDY::<F>(input, state, pos);
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = match parse_parser_name::<F>(input, state, pos) {
+                                        Matched(newpos, value) => {
+                                            Matched(newpos, Some(value))
+                                        }
+                                        Failed => {
+                                            Matched(pos, None)
+                                        }
+                                    };
+                                    match seq_res {
+                                        Matched(pos, name) => {
+                                            {
+                                                let seq_res = parse_PARSER_END::<F>(input,
+                                                                                    state,
+                                                                                    pos);
+                                                match seq_res {
+                                                    Matched(pos, _) => {
+                                                        {
+                                                            let seq_res =
+                                                                match parse_greedy_end_string::<F>(input,
+                                                                                                   state,
+                                                                                                   pos) {
+                                                                    Matched(newpos, value) => {
+                                                                        Matched(newpos, Some(value))
+                                                                    }
+                                                                    Failed => {
+                                                                        Matched(pos, None)
+                                                                    }
+                                                                };
+                                                            match seq_res {
+                                                                Matched(pos, end_string) => {
+                                                                    {
+                                                                        let match_str =
+                                                                            &input[start_pos..pos];
+                                                                        Matched(pos,
+                                                                                {
+                                                                                    let mut tokens =
+                                                                                        Vec::new();
+                                                                                    let parser =
+                                                                                        F::new_greedy(name,
+                                                                                                      end_string.as_ref()
+                                                                                                                .map(|string|
+                                                                                                                         string.as_str()));
+                                                                                    tokens.push(TokenType::Parser(parser));
+                                                                                    if let Some(end_string)
+                                                                                           =
+                                                                                           end_string
+                                                                                           {
+                                                                                        tokens.push(TokenType::Literal(end_string));
+                                                                                    }
+                                                                                    tokens
+                                                                                })
+                                                                    }
+                                                                }
+                                                                Failed => Failed,
+                                                            }
+                                                        }
+                                                    }
+                                                    Failed => Failed,
+                                                }
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_greedy_end_string<'input, F: ParserFactory>(input: &'input str,
+                                                     state: &mut ParseState<'input>,
+                                                     pos: usize)
+                                                     -> RuleResult<String> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = {
+                let mut repeat_pos = pos;
+                let mut repeat_value = vec![];
+                loop {
+                    let pos = repeat_pos;
+                    let step_res = parse_greedy_end_string_piece::<F>(input, state, pos);
+                    match step_res {
+                        Matched(newpos, value) => {
+                            repeat_pos = newpos;
+                            repeat_value.push(value);
+                        }
+                        Failed => {
+                            break;
+                        }
+                    }
+                }
+                if repeat_value.len() >= 1usize {
+                    Matched(repeat_pos, repeat_value)
+                } else {
+                    Failed
+                }
+            };
+            match seq_res {
+                Matched(pos, pieces) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            pieces.concat()
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_greedy_end_string_piece<'input, F: ParserFactory>(input: &'input str,
+                                                           state: &mut ParseState<'input>,
+                                                           pos: usize)
+                                                           -> RuleResult<&'input str> {
+    {
+        let choice_res = parse_newline::<F>(input, state, pos);
+        match choice_res {
+            Matched(pos, value) => Matched(pos, value),
+            Failed => parse_literal::<F>(input, state, pos),
+        }
+    }
+}
+fn parse_newline<'input, F: ParserFactory>(input: &'input str,
+                                           state: &mut ParseState<'input>,
+                                           pos: usize)
+                                           -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = parse_PARSER_BEGIN::<F>(input, state, pos);
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = parse_NEWLINE::<F>(input, state, pos);
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = parse_PARSER_END::<F>(input, state, pos);
+                                    match seq_res {
+                                        Matched(pos, _) => {
+                                            {
+                                                let match_str = &input[start_pos..pos];
+                                                Matched(pos, {
+                                                    grammar::NEWLINE
+                                                })
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_parser_BASE_optional_param<'input, F: ParserFactory>
+                                                              (input: &'input str,
+                                                               state: &mut ParseState<'input>,
+                                                               pos: usize)
+                                                               -> RuleResult<OptionalParameter<'input>> {
+    {
+        let choice_res = {
+            let start_pos = pos;
+            {
+                let seq_res = parse_MIN_LEN::<F>(input, state, pos);
+                match seq_res {
+                    Matched(pos, name) => {
+                        {
+                            let seq_res = slice_eq(input, state, pos, "=");
+                            match seq_res {
+                                Matched(pos, _) => {
+                                    {
+                                        let seq_res = parse_int::<F>(input, state, pos);
+                                        match seq_res {
+                                            Matched(pos, value) => {
+                                                {
+                                                    let match_str = &input[start_pos..pos];
+                                                    Matched(pos, {
+                                                        OptionalParameter::Int(name, value)
+                                                    })
+                                                }
+                                            }
+                                            Failed => Failed,
+                                        }
+                                    }
+                                }
+                                Failed => Failed,
+                            }
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        };
+        match choice_res {
+            Matched(pos, value) => Matched(pos, value),
+            Failed => {
+                let start_pos = pos;
+                {
+                    let seq_res = parse_MAX_LEN::<F>(input, state, pos);
+                    match seq_res {
+                        Matched(pos, name) => {
+                            {
+                                let seq_res = slice_eq(input, state, pos, "=");
+                                match seq_res {
+                                    Matched(pos, _) => {
+                                        {
+                                            let seq_res = parse_int::<F>(input, state, pos);
+                                            match seq_res {
+                                                Matched(pos, value) => {
+                                                    {
+                                                        let match_str = &input[start_pos..pos];
+                                                        Matched(pos, {
+                                                            OptionalParameter::Int(name, value)
+                                                        })
+                                                    }
+                                                }
+                                                Failed => Failed,
+                                            }
+                                        }
+                                    }
+                                    Failed => Failed,
+                                }
+                            }
+                        }
+                        Failed => Failed,
+                    }
+                }
+            }
+        }
+    }
+}
+fn parse_MIN_LEN<'input, F: ParserFactory>(input: &'input str,
+                                           state: &mut ParseState<'input>,
+                                           pos: usize)
+                                           -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "min_len");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_MAX_LEN<'input, F: ParserFactory>(input: &'input str,
+                                           state: &mut ParseState<'input>,
+                                           pos: usize)
+                                           -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "max_len");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_MIN<'input, F: ParserFactory>(input: &'input str,
+                                       state: &mut ParseState<'input>,
+                                       pos: usize)
+                                       -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "min");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_MAX<'input, F: ParserFactory>(input: &'input str,
+                                       state: &mut ParseState<'input>,
+                                       pos: usize)
+                                       -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "max");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_INT<'input, F: ParserFactory>(input: &'input str,
+                                       state: &mut ParseState<'input>,
+                                       pos: usize)
+                                       -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "INT");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
             }
         }
     }
 }
-fn parse_MIN_LEN<'input, F: ParserFactory>(input: &'input str,
-                                           state: &mut ParseState<'input>,
-                                           pos: usize)
-                                           -> RuleResult<&'input str> {
+fn parse_SET<'input, F: ParserFactory>(input: &'input str,
+                                       state: &mut ParseState<'input>,
+                                       pos: usize)
+                                       -> RuleResult<&'input str> {
     {
         let start_pos = pos;
         {
-            let seq_res = slice_eq(input, state, pos, "min_len");
+            let seq_res = slice_eq(input, state, pos, "SET");
             match seq_res {
                 Matched(pos, _) => {
                     {
@@ -735,14 +1519,14 @@ fn parse_MIN_LEN<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
-fn parse_MAX_LEN<'input, F: ParserFactory>(input: &'input str,
-                                           state: &mut ParseState<'input>,
-                                           pos: usize)
-                                           -> RuleResult<&'input str> {
+fn parse_GREEDY<'input, F: ParserFactory>(input: &'input str,
+                                          state: &mut ParseState<'input>,
+                                          pos: usize)
+                                          -> RuleResult<&'input str> {
     {
         let start_pos = pos;
         {
-            let seq_res = slice_eq(input, state, pos, "max_len");
+            let seq_res = slice_eq(input, state, pos, "GREEDY");
             match seq_res {
                 Matched(pos, _) => {
                     {
@@ -757,14 +1541,14 @@ fn parse_MAX_LEN<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
-fn parse_INT<'input, F: ParserFactory>(input: &'input str,
-                                       state: &mut ParseState<'input>,
-                                       pos: usize)
-                                       -> RuleResult<&'input str> {
+fn parse_LINES<'input, F: ParserFactory>(input: &'input str,
+                                         state: &mut ParseState<'input>,
+                                         pos: usize)
+                                         -> RuleResult<&'input str> {
     {
         let start_pos = pos;
         {
-            let seq_res = slice_eq(input, state, pos, "INT");
+            let seq_res = slice_eq(input, state, pos, "LINES");
             match seq_res {
                 Matched(pos, _) => {
                     {
@@ -779,14 +1563,14 @@ fn parse_INT<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
-fn parse_SET<'input, F: ParserFactory>(input: &'input str,
-                                       state: &mut ParseState<'input>,
-                                       pos: usize)
-                                       -> RuleResult<&'input str> {
+fn parse_NEWLINE<'input, F: ParserFactory>(input: &'input str,
+                                           state: &mut ParseState<'input>,
+                                           pos: usize)
+                                           -> RuleResult<&'input str> {
     {
         let start_pos = pos;
         {
-            let seq_res = slice_eq(input, state, pos, "SET");
+            let seq_res = slice_eq(input, state, pos, "NEWLINE");
             match seq_res {
                 Matched(pos, _) => {
                     {
@@ -801,14 +1585,36 @@ fn parse_SET<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
-fn parse_GREEDY<'input, F: ParserFactory>(input: &'input str,
+fn parse_REPEAT<'input, F: ParserFactory>(input: &'input str,
                                           state: &mut ParseState<'input>,
                                           pos: usize)
                                           -> RuleResult<&'input str> {
     {
         let start_pos = pos;
         {
-            let seq_res = slice_eq(input, state, pos, "GREEDY");
+            let seq_res = slice_eq(input, state, pos, "REPEAT");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_SEPARATOR<'input, F: ParserFactory>(input: &'input str,
+                                             state: &mut ParseState<'input>,
+                                             pos: usize)
+                                             -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "separator");
             match seq_res {
                 Matched(pos, _) => {
                     {
@@ -1003,6 +1809,117 @@ fn parse_string<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
+fn parse_escaped_string<'input, F: ParserFactory>(input: &'input str,
+                                                  state: &mut ParseState<'input>,
+                                                  pos: usize)
+                                                  -> RuleResult<String> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = slice_eq(input, state, pos, "\"");
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = {
+                            let mut repeat_pos = pos;
+                            let mut repeat_value = vec![];
+                            loop {
+                                let pos = repeat_pos;
+                                let step_res = parse_escaped_string_piece::<F>(input, state, pos);
+                                match step_res {
+                                    Matched(newpos, value) => {
+                                        repeat_pos = newpos;
+                                        repeat_value.push(value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            if repeat_value.len() >= 1usize {
+                                Matched(repeat_pos, repeat_value)
+                            } else {
+                                Failed
+                            }
+                        };
+                        match seq_res {
+                            Matched(pos, pieces) => {
+                                {
+                                    let seq_res = slice_eq(input, state, pos, "\"");
+                                    match seq_res {
+                                        Matched(pos, _) => {
+                                            {
+                                                let match_str = &input[start_pos..pos];
+                                                Matched(pos, {
+                                                    pieces.concat()
+                                                })
+                                            }
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
+fn parse_escaped_string_piece<'input, F: ParserFactory>(input: &'input str,
+                                                        state: &mut ParseState<'input>,
+                                                        pos: usize)
+                                                        -> RuleResult<&'input str> {
+    {
+        let choice_res = {
+            let start_pos = pos;
+            {
+                let seq_res = slice_eq(input, state, pos, "\\\"");
+                match seq_res {
+                    Matched(pos, _) => {
+                        {
+                            let match_str = &input[start_pos..pos];
+                            Matched(pos, {
+                                "\""
+                            })
+                        }
+                    }
+                    Failed => Failed,
+                }
+            }
+        };
+        match choice_res {
+            Matched(pos, value) => Matched(pos, value),
+            Failed => {
+                let choice_res = {
+                    let start_pos = pos;
+                    {
+                        let seq_res = slice_eq(input, state, pos, "\\\\");
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let match_str = &input[start_pos..pos];
+                                    Matched(pos, {
+                                        "\\"
+                                    })
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                };
+                match choice_res {
+                    Matched(pos, value) => Matched(pos, value),
+                    Failed => {
+                        parse_all_chars_until_quotation_mark_or_backslash::<F>(input, state, pos)
+                    }
+                }
+            }
+        }
+    }
+}
 fn parse_literal<'input, F: ParserFactory>(input: &'input str,
                                            state: &mut ParseState<'input>,
                                            pos: usize)
@@ -1117,6 +2034,78 @@ fn parse_all_chars_until_quotation_mark<'input, F: ParserFactory>(input: &'input
         }
     }
 }
+fn parse_all_chars_until_quotation_mark_or_backslash<'input, F: ParserFactory>
+                                                                             (input: &'input str,
+                                                                              state: &mut ParseState<'input>,
+                                                                              pos: usize)
+                                                                              -> RuleResult<&'input str> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = {
+                let mut repeat_pos = pos;
+                let mut repeat_value = vec![];
+                loop {
+                    let pos = repeat_pos;
+                    let step_res = {
+                        let seq_res = {
+                            let assert_res = slice_eq(input, state, pos, "\"");
+                            match assert_res {
+                                Failed => Matched(pos, ()),
+                                Matched(..) => Failed,
+                            }
+                        };
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let seq_res = {
+                                        let assert_res = slice_eq(input, state, pos, "\\");
+                                        match assert_res {
+                                            Failed => Matched(pos, ()),
+                                            Matched(..) => Failed,
+                                        }
+                                    };
+                                    match seq_res {
+                                        Matched(pos, _) => {
+                                            any_char(input, state, pos)
+                                        }
+                                        Failed => Failed,
+                                    }
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    };
+                    match step_res {
+                        Matched(newpos, value) => {
+                            repeat_pos = newpos;
+                            repeat_value.push(value);
+                        }
+                        Failed => {
+                            break;
+                        }
+                    }
+                }
+                if repeat_value.len() >= 1usize {
+                    Matched(repeat_pos, ())
+                } else {
+                    Failed
+                }
+            };
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let match_str = &input[start_pos..pos];
+                        Matched(pos, {
+                            match_str
+                        })
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
 fn parse_comma<'input, F: ParserFactory>(input: &'input str,
                                          state: &mut ParseState<'input>,
                                          pos: usize)
@@ -1197,6 +2186,69 @@ fn parse_int<'input, F: ParserFactory>(input: &'input str,
         }
     }
 }
+fn parse_positive_int<'input, F: ParserFactory>(input: &'input str,
+                                                state: &mut ParseState<'input>,
+                                                pos: usize)
+                                                -> RuleResult<usize> {
+    {
+        let start_pos = pos;
+        {
+            let seq_res = if input.len() > pos {
+                let (ch, next) = char_range_at(input, pos);
+                match ch {
+                    '1'...'9' => Matched(next, ()),
+                    _ => state.mark_failure(pos, "[1-9]"),
+                }
+            } else {
+                state.mark_failure(pos, "[1-9]")
+            };
+            match seq_res {
+                Matched(pos, _) => {
+                    {
+                        let seq_res = {
+                            let mut repeat_pos = pos;
+                            let mut repeat_value = vec![];
+                            loop {
+                                let pos = repeat_pos;
+                                let step_res = if input.len() > pos {
+                                    let (ch, next) = char_range_at(input, pos);
+                                    match ch {
+                                        '0'...'9' => Matched(next, ()),
+                                        _ => state.mark_failure(pos, "[0-9]"),
+                                    }
+                                } else {
+                                    state.mark_failure(pos, "[0-9]")
+                                };
+                                match step_res {
+                                    Matched(newpos, value) => {
+                                        repeat_pos = newpos;
+                                        repeat_value.push(value);
+                                    }
+                                    Failed => {
+                                        break;
+                                    }
+                                }
+                            }
+                            Matched(repeat_pos, ())
+                        };
+                        match seq_res {
+                            Matched(pos, _) => {
+                                {
+                                    let match_str = &input[start_pos..pos];
+                                    Matched(pos, {
+                                        usize::from_str(match_str).ok().unwrap()
+                                    })
+                                }
+                            }
+                            Failed => Failed,
+                        }
+                    }
+                }
+                Failed => Failed,
+            }
+        }
+    }
+}
 pub fn pattern<F: ParserFactory>(input: &str) -> ParseResult<CompiledPattern> {
     let mut state = ParseState::new();
     if let Matched(pos, value) = parse_pattern::<F>(input, &mut state, 0) {
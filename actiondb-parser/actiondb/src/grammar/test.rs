@@ -1,5 +1,6 @@
 use matcher::compiled_pattern::TokenType;
-use parsers::{SetParser, Parser, IntParser, GreedyParser, HasLengthConstraint};
+use parsers::{SetParser, Parser, IntParser, GreedyParser, LinesParser, RepeatParser,
+              HasLengthConstraint};
 
 fn assert_parser_name_equals(item: Option<&TokenType>, expected_name: Option<&str>) {
     if let Some(&TokenType::Parser(ref parser)) = item {
@@ -210,3 +211,131 @@ fn test_given_valid_pattern_when_it_does_not_have_a_name_then_we_can_parse_the_p
     println!("{:?}", &vec);
     assert_parser_name_equals(vec.get(0), None);
 }
+
+#[test]
+fn test_given_compiled_pattern_when_it_is_formatted_then_we_get_back_the_canonical_pattern() {
+    let pattern =
+        r#"\%\{ %{GREEDY:user} from %{SET("ba",min_len=1):host} %{INT(max_len=5):port}%{INT}"#;
+    let expected =
+        r#"\%\{ %{GREEDY:user} from %{SET("ab",min_len=1):host} %{INT(max_len=5):port}%{INT}"#;
+    let compiled_pattern = ::grammar::parser::pattern(pattern)
+                               .expect("Failed to parse a valid pattern");
+
+    let formatted = ::grammar::format_pattern(&compiled_pattern);
+
+    assert_eq!(formatted, expected);
+    assert!(::grammar::parser::pattern(&formatted).is_ok());
+}
+
+#[test]
+fn test_given_set_parser_with_non_ascii_characters_when_it_is_formatted_then_it_round_trips() {
+    let pattern = r#"name: %{SET("éá-"):name}"#;
+    let expected = r#"name: %{SET("-áé"):name}"#;
+    let compiled_pattern = ::grammar::parser::pattern(pattern)
+                               .expect("Failed to parse a non-ASCII SET parser");
+
+    let formatted = ::grammar::format_pattern(&compiled_pattern);
+
+    assert_eq!(formatted, expected);
+    let reparsed = ::grammar::parser::pattern(&formatted)
+                       .expect("Failed to parse a formatted non-ASCII SET parser");
+    assert_eq!(::grammar::format_pattern(&reparsed), expected);
+}
+
+#[test]
+fn test_given_pattern_when_it_contains_newline_tokens_then_they_are_merged_into_the_literals() {
+    let vec = ::grammar::parser::pattern("foo%{NEWLINE}bar %{INT:int}%{NEWLINE}")
+                  .expect("Failed to parse a pattern with NEWLINE tokens");
+
+    assert_eq!(vec.len(), 3);
+    assert_literal_equals(vec.get(0), "foo\nbar ");
+    assert_parser_name_equals(vec.get(1), Some("int"));
+    assert_literal_equals(vec.get(2), "\n");
+}
+
+#[test]
+fn test_given_greedy_parser_when_it_is_followed_by_a_newline_token_then_it_is_its_end_string() {
+    let vec = ::grammar::parser::pattern("%{GREEDY:first}%{NEWLINE}at %{GREEDY:rest}")
+                  .expect("Failed to parse a pattern with a NEWLINE token");
+
+    assert_eq!(vec.len(), 3);
+    assert_parser_equals(vec.get(0), &GreedyParser::from_str("first", "\nat "));
+    assert_literal_equals(vec.get(1), "\nat ");
+    assert_parser_name_equals(vec.get(2), Some("rest"));
+}
+
+#[test]
+fn test_given_lines_parser_as_a_string_when_it_is_parsed_then_we_get_the_instantiated_parser() {
+    let vec = ::grammar::parser::pattern("%{LINES(3):trace}")
+                  .expect("Failed to parse a LINES parser");
+
+    assert_eq!(vec.len(), 1);
+    assert_parser_equals(vec.get(0), &LinesParser::with_name("trace", 3));
+    ::grammar::parser::pattern("%{LINES:trace}").err().unwrap();
+}
+
+#[test]
+fn test_given_lines_parser_when_its_count_is_zero_then_it_is_rejected() {
+    ::grammar::parser::pattern("%{LINES(0):trace}").err().unwrap();
+    ::grammar::parser::pattern("%{REPEAT(LINES(0), separator=\",\"):traces}").err().unwrap();
+}
+
+#[test]
+fn test_given_multi_line_pattern_when_it_is_formatted_then_the_line_feeds_are_newline_tokens() {
+    let pattern = "Exception: %{GREEDY:message}%{NEWLINE}%{LINES(2):trace}";
+    let compiled_pattern = ::grammar::parser::pattern("Exception: %{GREEDY:message}\n\
+                                                       %{LINES(2):trace}")
+                               .expect("Failed to parse a valid pattern");
+
+    assert_eq!(::grammar::format_pattern(&compiled_pattern), pattern);
+}
+
+#[test]
+fn test_given_repeat_parser_as_a_string_when_it_is_parsed_then_we_get_the_instantiated_parser() {
+    let pattern = "ports %{REPEAT(INT, separator=\",\", min=2, max=5):ports}";
+    let vec = ::grammar::parser::pattern(pattern)
+                  .expect("Failed to parse a REPEAT parser");
+    let mut expected = RepeatParser::with_name("ports", Box::new(IntParser::new()), ",");
+    expected.set_min(2);
+    expected.set_max(Some(5));
+
+    assert_eq!(vec.len(), 2);
+    assert_parser_equals(vec.get(1), &expected);
+    ::grammar::parser::pattern("%{REPEAT(INT):ports}").err().unwrap();
+    ::grammar::parser::pattern("%{REPEAT(GREEDY, separator=\",\"):ports}").err().unwrap();
+}
+
+#[test]
+fn test_given_repeat_parser_when_min_or_max_is_zero_then_it_is_rejected() {
+    ::grammar::parser::pattern("%{REPEAT(INT, separator=\",\", min=0):ports}").err().unwrap();
+    ::grammar::parser::pattern("%{REPEAT(INT, separator=\",\", max=0):ports}").err().unwrap();
+    ::grammar::parser::pattern("%{REPEAT(INT, separator=\",\", max=10):ports}").unwrap();
+}
+
+#[test]
+fn test_given_repeat_parser_when_its_item_parser_has_a_name_then_it_is_rejected() {
+    ::grammar::parser::pattern("%{REPEAT(INT:port, separator=\",\"):ports}").err().unwrap();
+    ::grammar::parser::pattern("%{REPEAT(SET(\"ab\"):item, separator=\",\"):ports}")
+        .err()
+        .unwrap();
+}
+
+#[test]
+fn test_given_repeat_parser_when_its_separator_has_quotes_or_backslashes_then_it_round_trips() {
+    let pattern = r#"list: %{REPEAT(INT,separator="\"\\"):items}"#;
+    let compiled_pattern = ::grammar::parser::pattern(pattern)
+                               .expect("Failed to parse a REPEAT parser with an escaped separator");
+    let expected = RepeatParser::with_name("items", Box::new(IntParser::new()), "\"\\");
+    assert_parser_equals(compiled_pattern.get(1), &expected);
+
+    assert_eq!(::grammar::format_pattern(&compiled_pattern), pattern);
+}
+
+#[test]
+fn test_given_repeat_parser_when_it_is_formatted_then_it_can_be_parsed_back() {
+    let pattern = "users: %{REPEAT(SET(\"abc\",min_len=1),separator=\", \",max=3):users}";
+    let compiled_pattern = ::grammar::parser::pattern(pattern)
+                               .expect("Failed to parse a valid pattern");
+
+    assert_eq!(::grammar::format_pattern(&compiled_pattern), pattern);
+}
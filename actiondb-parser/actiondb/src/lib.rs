@@ -8,10 +8,18 @@ extern crate uuid;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_yaml;
+extern crate xml;
+extern crate glob;
 
 pub mod parsers;
 pub mod utils;
 pub mod matcher;
 pub mod grammar;
+pub mod lint;
+pub mod coverage;
+pub mod diff;
+pub mod pdb;
+pub mod preprocess;
+pub mod suggest;
 
 pub use matcher::Matcher;
@@ -0,0 +1,168 @@
+use uuid::Uuid;
+
+use matcher::compiled_pattern::TokenType;
+use matcher::pattern::Pattern;
+use parsers::{Parser, ParserType};
+use super::{Rule, Severity};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+const DIGITS: &'static [u8] = b"0123456789";
+
+// A problem found in a pattern.
+#[derive(Clone, Debug)]
+pub struct Lint {
+    rule: Rule,
+    severity: Severity,
+    uuid: Uuid,
+    name: Option<String>,
+    message: String,
+}
+
+impl Lint {
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_fmt(format_args!("{}[{}]: {}: uuid={} name={}",
+                                   self.severity,
+                                   self.rule,
+                                   self.message,
+                                   self.uuid.hyphenated(),
+                                   self.name().unwrap_or("")))
+    }
+}
+
+// Checks patterns for common mistakes. Every rule can be suppressed or its
+// severity can be changed. The patterns must be checked before they are
+// inserted into a matcher, because their tokens are consumed by the insertion.
+pub struct Linter {
+    // a missing rule is suppressed
+    severities: BTreeMap<Rule, Severity>,
+}
+
+impl Linter {
+    pub fn new() -> Linter {
+        Linter::default()
+    }
+
+    pub fn allow(&mut self, rule: Rule) {
+        self.severities.remove(&rule);
+    }
+
+    pub fn set_severity(&mut self, rule: Rule, severity: Severity) {
+        self.severities.insert(rule, severity);
+    }
+
+    pub fn lint(&self, pattern: &Pattern) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        {
+            let mut report = |rule: Rule, message: String| {
+                if let Some(severity) = self.severities.get(&rule) {
+                    lints.push(Lint {
+                        rule: rule,
+                        severity: *severity,
+                        uuid: pattern.uuid().clone(),
+                        name: pattern.name().map(|name| name.to_owned()),
+                        message: message,
+                    });
+                }
+            };
+
+            if pattern.test_messages().map_or(true, |messages| messages.is_empty()) {
+                report(Rule::MissingTestMessages,
+                       "The pattern doesn't have test messages".to_owned());
+            }
+
+            let tokens = pattern.pattern();
+            for (index, token) in tokens.iter().enumerate() {
+                if let TokenType::Parser(ref parser) = *token {
+                    Linter::lint_parser(&**parser, tokens.get(index + 1), &mut report);
+                }
+            }
+        }
+        lints
+    }
+
+    fn lint_parser<F>(parser: &Parser, next: Option<&TokenType>, report: &mut F)
+        where F: FnMut(Rule, String)
+    {
+        if parser.name().is_none() {
+            report(Rule::UnnamedParser,
+                   format!("The value of a {} parser is dropped, because it doesn't have a name",
+                           Linter::parser_name(parser)));
+        }
+
+        match (parser.parser_type(), next) {
+            (ParserType::Greedy { end_string: None }, Some(_)) => {
+                report(Rule::GreedyWithoutEndString,
+                       "A GREEDY parser without an end string is followed by other tokens"
+                           .to_owned());
+            }
+            (ParserType::Set { character_set, .. }, Some(&TokenType::Literal(ref literal))) => {
+                if let Some(first) = literal.bytes().next() {
+                    if character_set.contains(&first) {
+                        report(Rule::SetSwallowsNextLiteral,
+                               Linter::swallowed_literal_message(parser, literal));
+                    }
+                }
+            }
+            (ParserType::Int { .. }, Some(&TokenType::Literal(ref literal))) => {
+                if let Some(first) = literal.bytes().next() {
+                    if DIGITS.contains(&first) {
+                        report(Rule::SetSwallowsNextLiteral,
+                               Linter::swallowed_literal_message(parser, literal));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn swallowed_literal_message(parser: &Parser, literal: &str) -> String {
+        format!("The {} parser accepts the first character of the following literal '{}', so \
+                 the literal can never match",
+                Linter::parser_name(parser),
+                literal)
+    }
+
+    fn parser_name(parser: &Parser) -> &'static str {
+        match parser.parser_type() {
+            ParserType::Set { .. } => "SET",
+            ParserType::Int { .. } => "INT",
+            ParserType::Greedy { .. } => "GREEDY",
+            ParserType::Lines { .. } => "LINES",
+            ParserType::Repeat { .. } => "REPEAT",
+        }
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut severities = BTreeMap::new();
+        for rule in Rule::all() {
+            severities.insert(*rule, rule.default_severity());
+        }
+        Linter { severities: severities }
+    }
+}
@@ -0,0 +1,7 @@
+pub use self::linter::{Lint, Linter};
+pub use self::rule::{Rule, Severity};
+
+#[cfg(test)]
+mod test;
+mod linter;
+mod rule;
@@ -0,0 +1,83 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn from_str(severity: &str) -> Option<Severity> {
+        match severity {
+            "info" => Some(Severity::Info),
+            "warning" => Some(Severity::Warning),
+            "error" => Some(Severity::Error),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    // the values of the unnamed parsers are dropped from the match results
+    UnnamedParser,
+    MissingTestMessages,
+    // a GREEDY parser without an end string consumes the rest of the message,
+    // so the following tokens can never match
+    GreedyWithoutEndString,
+    // a SET or INT parser which accepts the first character of the following
+    // literal consumes it, so the literal can never match
+    SetSwallowsNextLiteral,
+}
+
+pub const RULES: &'static [Rule] = &[Rule::UnnamedParser,
+                                     Rule::MissingTestMessages,
+                                     Rule::GreedyWithoutEndString,
+                                     Rule::SetSwallowsNextLiteral];
+
+impl Rule {
+    pub fn all() -> &'static [Rule] {
+        RULES
+    }
+
+    pub fn from_str(rule: &str) -> Option<Rule> {
+        RULES.iter().find(|candidate| candidate.as_str() == rule).cloned()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Rule::UnnamedParser => "unnamed-parser",
+            Rule::MissingTestMessages => "missing-test-messages",
+            Rule::GreedyWithoutEndString => "greedy-without-end-string",
+            Rule::SetSwallowsNextLiteral => "set-swallows-next-literal",
+        }
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        match *self {
+            Rule::UnnamedParser | Rule::MissingTestMessages => Severity::Warning,
+            Rule::GreedyWithoutEndString | Rule::SetSwallowsNextLiteral => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,95 @@
+use matcher::Pattern;
+use matcher::compiled_pattern::CompiledPatternBuilder;
+use parsers::{GreedyParser, IntParser};
+use super::{Linter, Rule, Severity};
+
+fn pattern(pattern: &str, with_test_message: bool) -> Pattern {
+    let test_messages = if with_test_message {
+        r#", "test_messages": [{"message": "user bob logged in"}]"#
+    } else {
+        ""
+    };
+    Pattern::from_json(&format!(r#"{{"uuid": "6d2cba0c-e241-464a-89c3-8035cac8f73e",
+                                    "pattern": "{}"{}}}"#,
+                                pattern,
+                                test_messages))
+        .expect("Failed to deserialize a JSON pattern")
+}
+
+fn rules(linter: &Linter, pattern: &Pattern) -> Vec<Rule> {
+    linter.lint(pattern).iter().map(|lint| lint.rule()).collect()
+}
+
+#[test]
+fn test_given_linter_when_the_pattern_has_no_mistakes_then_there_are_no_lints() {
+    let linter = Linter::new();
+    let pattern = pattern("user %{GREEDY:user} logged in", true);
+
+    assert!(linter.lint(&pattern).is_empty());
+}
+
+#[test]
+fn test_given_linter_when_a_parser_does_not_have_a_name_then_it_is_reported() {
+    let linter = Linter::new();
+    let pattern = pattern("user %{GREEDY} logged in", true);
+
+    let lints = linter.lint(&pattern);
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].rule(), Rule::UnnamedParser);
+    assert_eq!(lints[0].severity(), Severity::Warning);
+}
+
+#[test]
+fn test_given_linter_when_the_pattern_does_not_have_test_messages_then_it_is_reported() {
+    let linter = Linter::new();
+    let pattern = pattern("user %{GREEDY:user} logged in", false);
+
+    assert_eq!(rules(&linter, &pattern), vec![Rule::MissingTestMessages]);
+}
+
+#[test]
+fn test_given_linter_when_a_greedy_parser_without_end_string_is_followed_by_a_parser_then_it_is_reported
+    () {
+    let linter = Linter::new();
+    let mut pattern = pattern("placeholder", true);
+    pattern.set_pattern(CompiledPatternBuilder::new()
+                            .parser(Box::new(GreedyParser::with_name("a".to_owned())))
+                            .parser(Box::new(IntParser::with_name("b".to_owned())))
+                            .build());
+
+    let lints = linter.lint(&pattern);
+    assert_eq!(rules(&linter, &pattern), vec![Rule::GreedyWithoutEndString]);
+    assert_eq!(lints[0].severity(), Severity::Error);
+}
+
+#[test]
+fn test_given_linter_when_a_set_accepts_the_first_character_of_the_next_literal_then_it_is_reported
+    () {
+    let linter = Linter::new();
+    let set = pattern(r#"user %{SET(\"abc.\"):user}. logged in"#, true);
+    let int = pattern("port %{INT:port}0", true);
+
+    assert_eq!(rules(&linter, &set), vec![Rule::SetSwallowsNextLiteral]);
+    assert_eq!(rules(&linter, &int), vec![Rule::SetSwallowsNextLiteral]);
+}
+
+#[test]
+fn test_given_linter_when_a_rule_is_allowed_or_its_severity_is_changed_then_it_is_respected() {
+    let mut linter = Linter::new();
+    linter.allow(Rule::MissingTestMessages);
+    linter.set_severity(Rule::UnnamedParser, Severity::Error);
+    let pattern = pattern("user %{GREEDY} logged in", false);
+
+    let lints = linter.lint(&pattern);
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].rule(), Rule::UnnamedParser);
+    assert_eq!(lints[0].severity(), Severity::Error);
+}
+
+#[test]
+fn test_given_rule_name_when_it_is_parsed_then_we_get_the_rule() {
+    for rule in Rule::all() {
+        assert_eq!(Rule::from_str(rule.as_str()), Some(*rule));
+    }
+    assert_eq!(Rule::from_str("no-such-rule"), None);
+}
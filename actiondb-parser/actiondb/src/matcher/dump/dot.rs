@@ -0,0 +1,48 @@
+use super::{Node, NodeKind};
+
+// Renders the nodes as a Graphviz digraph. The nodes where patterns
+// terminate are drawn with a double border.
+pub fn to_dot(root: &Node) -> String {
+    let mut dot = String::from("digraph matcher {\n");
+    let mut next_id = 0;
+    write_node(root, &mut next_id, &mut dot);
+    dot.push_str("}\n");
+    dot
+}
+
+// Returns the id of the written node.
+fn write_node(node: &Node, next_id: &mut usize, dot: &mut String) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let mut label = node.label();
+    for pattern in node.patterns() {
+        label.push('\n');
+        label.push_str(pattern);
+    }
+    let shape = match *node.kind() {
+        NodeKind::Root => "plaintext",
+        NodeKind::Literal(_) => "box",
+        NodeKind::Parser(_) => "ellipse",
+    };
+    let peripheries = if node.patterns().is_empty() {
+        1
+    } else {
+        2
+    };
+    dot.push_str(&format!("  node{} [shape={}, peripheries={}, label=\"{}\"];\n",
+                          id,
+                          shape,
+                          peripheries,
+                          escape(&label)));
+
+    for child in node.children() {
+        let child_id = write_node(child, next_id, dot);
+        dot.push_str(&format!("  node{} -> node{};\n", id, child_id));
+    }
+    id
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
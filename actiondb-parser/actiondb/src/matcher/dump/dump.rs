@@ -0,0 +1,85 @@
+use grammar;
+use matcher::Pattern;
+use matcher::compiled_pattern::TokenType;
+use parsers::{Parser, ParserType};
+
+// Implemented by the matchers whose structure can be inspected.
+pub trait Dump {
+    fn dump(&self) -> Node;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Root,
+    Literal(String),
+    // The parser in the pattern grammar with its end string if it has one.
+    Parser(String),
+}
+
+// A node of a compiled matcher with the patterns which terminate in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    kind: NodeKind,
+    patterns: Vec<String>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    pub fn root() -> Node {
+        Node::new(NodeKind::Root)
+    }
+
+    pub fn literal(literal: &str) -> Node {
+        Node::new(NodeKind::Literal(literal.to_owned()))
+    }
+
+    pub fn parser(parser: &Parser) -> Node {
+        let mut description = grammar::format_pattern(&[TokenType::Parser(parser.boxed_clone())]);
+        if let ParserType::Greedy { end_string: Some(end_string) } = parser.parser_type() {
+            description.push_str(&format!(" until {:?}", end_string));
+        }
+        Node::new(NodeKind::Parser(description))
+    }
+
+    fn new(kind: NodeKind) -> Node {
+        Node {
+            kind: kind,
+            patterns: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> &NodeKind {
+        &self.kind
+    }
+
+    // The name and UUID of the patterns terminating in this node.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    pub fn add_pattern(&mut self, pattern: &Pattern) {
+        let description = match pattern.name() {
+            Some(name) => format!("{} ({})", name, pattern.uuid()),
+            None => pattern.uuid().to_string(),
+        };
+        self.patterns.push(description);
+    }
+
+    pub fn add_child(&mut self, child: Node) {
+        self.children.push(child);
+    }
+
+    // The text of the node without its patterns.
+    pub fn label(&self) -> String {
+        match self.kind {
+            NodeKind::Root => "root".to_owned(),
+            NodeKind::Literal(ref literal) => format!("literal {:?}", literal),
+            NodeKind::Parser(ref parser) => format!("parser {}", parser),
+        }
+    }
+}
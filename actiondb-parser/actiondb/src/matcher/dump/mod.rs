@@ -0,0 +1,9 @@
+pub use self::dump::{Dump, Node, NodeKind};
+pub use self::dot::to_dot;
+pub use self::text::to_text;
+
+#[cfg(test)]
+mod test;
+mod dump;
+mod dot;
+mod text;
@@ -0,0 +1,76 @@
+use grammar::parser::pattern_with_factory;
+use matcher::{Matcher, MatcherFactory, Pattern};
+use matcher::suffix_array::SuffixArrayMatcherFactory;
+use matcher::trie::factory::TrieMatcherFactory;
+use matcher::trie::parser_factory::TrieParserFactory;
+use super::{to_dot, to_text, Dump, Node};
+use uuid::Uuid;
+
+const APP_UUID: &'static str = "6d2cba0c-e241-464a-89c3-8035cac8f73e";
+const BAMBOO_UUID: &'static str = "9a49c47d-29e9-4072-be84-3b76c6814743";
+
+fn create_pattern(name: &str, uuid: &str, pattern: &str) -> Pattern {
+    Pattern::new(Some(name.to_owned()),
+                 Uuid::parse_str(uuid).unwrap(),
+                 pattern_with_factory::<TrieParserFactory>(pattern).unwrap(),
+                 None,
+                 None,
+                 None)
+        .unwrap()
+}
+
+fn dump_matcher<F>() -> Node
+    where F: MatcherFactory,
+          F::Matcher: Dump
+{
+    let mut matcher = F::new_matcher();
+    matcher.add_pattern(create_pattern("APP", APP_UUID, "app %{INT:num}"));
+    matcher.add_pattern(create_pattern("BAMBOO", BAMBOO_UUID, "bamboo"));
+    matcher.dump()
+}
+
+#[test]
+fn test_given_suffix_table_when_it_is_dumped_as_text_then_the_nodes_are_indented_by_depth() {
+    let expected = format!("root\n  literal \"app \"\n    parser %{{INT:num}} => APP ({})\n  \
+                            literal \"bamboo\" => BAMBOO ({})\n",
+                           APP_UUID,
+                           BAMBOO_UUID);
+
+    let root = dump_matcher::<SuffixArrayMatcherFactory>();
+
+    assert_eq!(to_text(&root), expected);
+}
+
+#[test]
+fn test_given_suffix_tree_when_it_is_dumped_then_the_patterns_are_in_their_terminating_nodes() {
+    let root = dump_matcher::<TrieMatcherFactory>();
+
+    let text = to_text(&root);
+    assert!(text.contains(&format!("parser %{{INT:num}} => APP ({})", APP_UUID)));
+    assert!(text.contains(&format!("literal \"bamboo\" => BAMBOO ({})", BAMBOO_UUID)));
+    assert_eq!(root.patterns().len(), 0);
+}
+
+#[test]
+fn test_given_dump_when_it_is_rendered_as_dot_then_terminating_nodes_have_double_border() {
+    let root = dump_matcher::<SuffixArrayMatcherFactory>();
+
+    let dot = to_dot(&root);
+
+    assert!(dot.starts_with("digraph matcher {\n"));
+    assert!(dot.contains("  node0 -> node1;\n"));
+    assert!(dot.contains(&format!("  node2 [shape=ellipse, peripheries=2, label=\"parser \
+                                   %{{INT:num}}\\nAPP ({})\"];\n",
+                                  APP_UUID)));
+    assert!(dot.contains("  node1 [shape=box, peripheries=1, label=\"literal \\\"app \\\"\"];\n"));
+}
+
+#[test]
+fn test_given_greedy_parser_when_it_is_dumped_then_its_end_string_is_shown() {
+    let mut matcher = SuffixArrayMatcherFactory::new_matcher();
+    matcher.add_pattern(create_pattern("USER", APP_UUID, "user %{GREEDY:user} logged in"));
+
+    let text = to_text(&matcher.dump());
+
+    assert!(text.contains("parser %{GREEDY:user} until \" logged in\""));
+}
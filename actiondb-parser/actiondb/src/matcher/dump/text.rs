@@ -0,0 +1,24 @@
+use super::Node;
+
+// Renders the nodes as an indented tree, the children of a node are indented
+// by two spaces. The patterns terminating in a node follow it after `=>`.
+pub fn to_text(root: &Node) -> String {
+    let mut text = String::new();
+    write_node(root, 0, &mut text);
+    text
+}
+
+fn write_node(node: &Node, depth: usize, text: &mut String) {
+    for _ in 0..depth {
+        text.push_str("  ");
+    }
+    text.push_str(&node.label());
+    if !node.patterns().is_empty() {
+        text.push_str(" => ");
+        text.push_str(&node.patterns().join(", "));
+    }
+    text.push('\n');
+    for child in node.children() {
+        write_node(child, depth + 1, text);
+    }
+}
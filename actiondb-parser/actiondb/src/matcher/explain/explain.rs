@@ -0,0 +1,94 @@
+use grammar;
+use matcher::compiled_pattern::{CompiledPattern, TokenType};
+
+// A token of a pattern and the part of the message it matched. `value` is
+// `None` if the token didn't match the message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step<'a> {
+    token: String,
+    value: Option<&'a str>,
+}
+
+impl<'a> Step<'a> {
+    // The token in the pattern grammar.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn value(&self) -> Option<&'a str> {
+        self.value
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.value.is_some()
+    }
+}
+
+// Describes how far a message could be matched by a pattern. The steps end
+// with the first token which didn't match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    steps: Vec<Step<'a>>,
+    rest: &'a str,
+    unreached: Vec<String>,
+}
+
+impl<'a> Explanation<'a> {
+    pub fn steps(&self) -> &[Step<'a>] {
+        &self.steps
+    }
+
+    // The part of the message which wasn't matched by any token.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    // The tokens after the first failing one.
+    pub fn unreached(&self) -> &[String] {
+        &self.unreached
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.unreached.is_empty() && self.rest.is_empty() &&
+        self.steps.iter().all(|step| step.is_match())
+    }
+}
+
+// Matches the tokens of a pattern one after the other against a message, so
+// it can be told which token stops the pattern from matching.
+pub fn explain<'a>(pattern: &CompiledPattern, message: &'a str) -> Explanation<'a> {
+    let mut steps = Vec::new();
+    let mut unreached = Vec::new();
+    let mut rest = message;
+
+    for token in pattern {
+        let formatted = grammar::format_pattern(&[token.clone()]);
+        if steps.last().map_or(false, |step: &Step| !step.is_match()) {
+            unreached.push(formatted);
+            continue;
+        }
+        let value = match *token {
+            TokenType::Literal(ref literal) => {
+                if rest.starts_with(literal.as_str()) {
+                    Some(&rest[..literal.len()])
+                } else {
+                    None
+                }
+            }
+            TokenType::Parser(ref parser) => parser.parse(rest).map(|result| result.value()),
+        };
+        if let Some(value) = value {
+            rest = &rest[value.len()..];
+        }
+        steps.push(Step {
+            token: formatted,
+            value: value,
+        });
+    }
+
+    Explanation {
+        steps: steps,
+        rest: rest,
+        unreached: unreached,
+    }
+}
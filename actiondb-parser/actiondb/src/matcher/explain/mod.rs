@@ -0,0 +1,5 @@
+pub use self::explain::{explain, Explanation, Step};
+
+#[cfg(test)]
+mod test;
+mod explain;
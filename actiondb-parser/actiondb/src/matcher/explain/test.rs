@@ -0,0 +1,45 @@
+use grammar::parser::pattern_with_factory;
+use matcher::trie::parser_factory::TrieParserFactory;
+use super::explain;
+
+#[test]
+fn test_given_pattern_when_it_matches_the_message_then_every_token_has_a_value() {
+    let pattern = pattern_with_factory::<TrieParserFactory>("user %{GREEDY:user} logged in \
+                                                             from %{INT:port}")
+                      .unwrap();
+
+    let explanation = explain(&pattern, "user bob logged in from 22");
+
+    assert_eq!(explanation.is_match(), true);
+    let values: Vec<Option<&str>> = explanation.steps().iter().map(|step| step.value()).collect();
+    assert_eq!(values,
+               vec![Some("user "), Some("bob"), Some(" logged in from "), Some("22")]);
+    assert_eq!(explanation.steps()[1].token(), "%{GREEDY:user}");
+}
+
+#[test]
+fn test_given_pattern_when_a_token_does_not_match_then_the_steps_end_with_it() {
+    let pattern = pattern_with_factory::<TrieParserFactory>("user %{GREEDY:user} logged in \
+                                                             from %{INT:port}")
+                      .unwrap();
+
+    let explanation = explain(&pattern, "user bob logged out");
+
+    assert_eq!(explanation.is_match(), false);
+    assert_eq!(explanation.steps().len(), 3);
+    assert_eq!(explanation.steps()[2].token(), " logged in from ");
+    assert_eq!(explanation.steps()[2].value(), None);
+    assert_eq!(explanation.rest(), " logged out");
+    assert_eq!(explanation.unreached(), &["%{INT:port}".to_owned()]);
+}
+
+#[test]
+fn test_given_pattern_when_the_message_is_longer_than_the_pattern_then_it_does_not_match() {
+    let pattern = pattern_with_factory::<TrieParserFactory>("port %{INT:port}").unwrap();
+
+    let explanation = explain(&pattern, "port 22 open");
+
+    assert_eq!(explanation.is_match(), false);
+    assert_eq!(explanation.steps().iter().all(|step| step.is_match()), true);
+    assert_eq!(explanation.rest(), " open");
+}
@@ -6,13 +6,17 @@ pub mod factory;
 pub mod pattern_loader;
 pub mod suite;
 pub mod compiled_pattern;
+pub mod testing;
+pub mod explain;
+pub mod dump;
 pub mod suffix_array;
 
 pub use self::pattern::Pattern;
-pub use self::pattern_loader::PatternLoader;
+pub use self::pattern_loader::{PatternLoader, DuplicatePolicy, LoadOptions, LoadedPattern,
+                               TestMessagePolicy};
 pub use self::factory::MatcherFactory;
 pub use self::suite::MatcherSuite;
-pub use self::pattern_source::{FromPatternSource, BuildError};
+pub use self::pattern_source::{FromPatternSource, BuildError, SourceLocation};
 
 use matcher::result::MatchResult;
 use std::fmt;
@@ -1,5 +1,8 @@
-use super::Pattern;
+use super::{Pattern, RawPattern};
 use super::testmessage::TestMessage;
+use super::metadata::Metadata;
+use super::line_ending::{LineEnding, trim_block_scalar};
+use matcher::trie::parser_factory::TrieParserFactory;
 
 use serde;
 use uuid::Uuid;
@@ -9,6 +12,16 @@ use std::collections::BTreeMap;
 impl serde::de::Deserialize for Pattern {
     fn deserialize<D>(deserializer: &mut D) -> Result<Pattern, D::Error>
         where D: serde::de::Deserializer
+    {
+        let raw_pattern = try!(RawPattern::deserialize(deserializer));
+        raw_pattern.compile::<TrieParserFactory>()
+                   .map_err(|error| serde::de::Error::custom(error.to_string()))
+    }
+}
+
+impl serde::de::Deserialize for RawPattern {
+    fn deserialize<D>(deserializer: &mut D) -> Result<RawPattern, D::Error>
+        where D: serde::de::Deserializer
     {
         deserializer.deserialize_struct("Pattern", &[], PatternVisitor)
     }
@@ -21,6 +34,8 @@ enum Field {
     VALUES,
     TAGS,
     TESTMESSAGES,
+    METADATA,
+    LINEENDING,
 }
 
 impl serde::Deserialize for Field {
@@ -42,6 +57,8 @@ impl serde::Deserialize for Field {
                     "values" => Ok(Field::VALUES),
                     "tags" => Ok(Field::TAGS),
                     "test_messages" => Ok(Field::TESTMESSAGES),
+                    "metadata" => Ok(Field::METADATA),
+                    "line_ending" => Ok(Field::LINEENDING),
                     _ => Err(serde::de::Error::custom(format!("Unexpected field: {}", value))),
                 }
             }
@@ -55,9 +72,9 @@ impl serde::Deserialize for Field {
 struct PatternVisitor;
 
 impl serde::de::Visitor for PatternVisitor {
-    type Value = Pattern;
+    type Value = RawPattern;
 
-    fn visit_map<V>(&mut self, mut visitor: V) -> Result<Pattern, V::Error>
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<RawPattern, V::Error>
         where V: serde::de::MapVisitor
     {
         let mut name = None;
@@ -66,6 +83,8 @@ impl serde::de::Visitor for PatternVisitor {
         let mut values: Option<BTreeMap<String, String>> = None;
         let mut tags: Option<Vec<String>> = None;
         let mut test_messages: Option<Vec<TestMessage>> = None;
+        let mut metadata: Option<Metadata> = None;
+        let mut line_ending: Option<LineEnding> = None;
 
         while let Some(field) = try!(visitor.visit_key()) {
             match field {
@@ -75,24 +94,18 @@ impl serde::de::Visitor for PatternVisitor {
                 Field::VALUES => values = Some(try!(visitor.visit_value())),
                 Field::TAGS => tags = Some(try!(visitor.visit_value())),
                 Field::TESTMESSAGES => test_messages = Some(try!(visitor.visit_value())),
+                Field::METADATA => metadata = Some(try!(visitor.visit_value())),
+                Field::LINEENDING => {
+                    let value: String = try!(visitor.visit_value());
+                    line_ending = Some(try!(LineEnding::from_str(&value).ok_or_else(|| {
+                        serde::de::Error::custom(format!("Unknown line ending: {}", value))
+                    })));
+                }
             }
         }
 
-        let pattern = match pattern {
-            Some(pattern) => {
-                match ::grammar::parser::pattern(&pattern) {
-                    Ok(pattern) => pattern,
-                    Err(err) => {
-                        error!("Invalid field 'pattern': pattern={:?} name={:?} uuid={:?} \
-                                error={}",
-                               pattern,
-                               name,
-                               uuid,
-                               err);
-                        try!(Err(serde::de::Error::custom("Invalid field 'pattern'")))
-                    }
-                }
-            }
+        let mut pattern = match pattern {
+            Some(pattern) => pattern,
             None => {
                 error!("Missing field 'pattern': name={:?} uuid={:?}", name, uuid);
                 try!(Err(serde::de::Error::missing_field("pattern")))
@@ -103,6 +116,16 @@ impl serde::de::Visitor for PatternVisitor {
 
         try!(visitor.end());
 
-        Ok(Pattern::new(name, uuid, pattern, test_messages, values, tags))
+        // the multi-line patterns are usually written as YAML block scalars,
+        // whose trailing line feed is never part of a log message
+        pattern = trim_block_scalar(pattern);
+        for test_message in test_messages.iter_mut().flat_map(|messages| messages.iter_mut()) {
+            test_message.trim_block_scalar();
+        }
+
+        let mut raw_pattern = RawPattern::new(name, uuid, pattern, test_messages, values, tags);
+        raw_pattern.set_metadata(metadata);
+        raw_pattern.set_line_ending(line_ending.unwrap_or(LineEnding::default()));
+        Ok(raw_pattern)
     }
 }
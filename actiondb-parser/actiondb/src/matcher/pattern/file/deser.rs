@@ -1,13 +1,29 @@
 use serde;
 
-use super::PatternFile;
-use matcher::pattern::Pattern;
+use super::{PatternFile, RawPatternFile};
+use matcher::pattern::{Pattern, RawPattern};
+
+use std::marker::PhantomData;
 
 impl serde::de::Deserialize for PatternFile {
     fn deserialize<D>(deserializer: &mut D) -> Result<PatternFile, D::Error>
         where D: serde::de::Deserializer
     {
-        deserializer.deserialize_struct("File", &[], FileVisitor)
+        let patterns = try!(deserializer.deserialize_struct("File",
+                                                            &[],
+                                                            FileVisitor::<Pattern>::new()));
+        Ok(PatternFile { patterns: patterns })
+    }
+}
+
+impl serde::de::Deserialize for RawPatternFile {
+    fn deserialize<D>(deserializer: &mut D) -> Result<RawPatternFile, D::Error>
+        where D: serde::de::Deserializer
+    {
+        let patterns = try!(deserializer.deserialize_struct("File",
+                                                            &[],
+                                                            FileVisitor::<RawPattern>::new()));
+        Ok(RawPatternFile { patterns: patterns })
     }
 }
 
@@ -38,15 +54,24 @@ impl serde::Deserialize for Field {
     }
 }
 
-struct FileVisitor;
+// Visits the patterns of a file. They can be either compiled or raw ones.
+struct FileVisitor<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> FileVisitor<T> {
+    fn new() -> FileVisitor<T> {
+        FileVisitor { _marker: PhantomData }
+    }
+}
 
-impl serde::de::Visitor for FileVisitor {
-    type Value = PatternFile;
+impl<T: serde::de::Deserialize> serde::de::Visitor for FileVisitor<T> {
+    type Value = Vec<T>;
 
-    fn visit_map<V>(&mut self, mut visitor: V) -> Result<PatternFile, V::Error>
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<Vec<T>, V::Error>
         where V: serde::de::MapVisitor
     {
-        let mut patterns: Option<Vec<Pattern>> = None;
+        let mut patterns: Option<Vec<T>> = None;
 
         while let Some (field) = try!(visitor.visit_key()) {
             match field {
@@ -61,6 +86,6 @@ impl serde::de::Visitor for FileVisitor {
 
         try!(visitor.end());
 
-        Ok(PatternFile { patterns: patterns_final })
+        Ok(patterns_final)
     }
 }
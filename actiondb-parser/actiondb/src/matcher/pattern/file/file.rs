@@ -1,4 +1,4 @@
-use matcher::pattern::Pattern;
+use matcher::pattern::{Pattern, RawPattern};
 
 pub struct PatternFile {
     pub patterns: Vec<Pattern>,
@@ -9,3 +9,8 @@ impl PatternFile {
         &self.patterns
     }
 }
+
+// A pattern file whose patterns are not compiled yet, see `RawPattern`.
+pub struct RawPatternFile {
+    pub patterns: Vec<RawPattern>,
+}
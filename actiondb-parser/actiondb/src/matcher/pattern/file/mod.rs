@@ -1,5 +1,6 @@
-pub use self::file::PatternFile;
+pub use self::file::{PatternFile, RawPatternFile};
 
 mod deser;
+mod ser;
 mod file;
 mod iter;
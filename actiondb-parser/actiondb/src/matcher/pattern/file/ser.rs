@@ -0,0 +1,48 @@
+use serde;
+use serde_json;
+use serde_yaml;
+
+use super::PatternFile;
+
+impl serde::Serialize for PatternFile {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_struct("File",
+                                    FileMapVisitor {
+                                        value: self,
+                                        visited: false,
+                                    })
+    }
+}
+
+struct FileMapVisitor<'a> {
+    value: &'a PatternFile,
+    visited: bool,
+}
+
+impl<'a> serde::ser::MapVisitor for FileMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
+        where S: serde::Serializer
+    {
+        if self.visited {
+            return Ok(None);
+        }
+        self.visited = true;
+        serializer.serialize_struct_elt("patterns", self.value.patterns()).map(Some)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl PatternFile {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+}
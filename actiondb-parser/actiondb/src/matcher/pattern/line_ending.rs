@@ -0,0 +1,98 @@
+use grammar;
+use matcher::compiled_pattern::{CompiledPattern, TokenType};
+use parsers::{ParserFactory, ParserType};
+
+const CRLF: &'static str = "\r\n";
+
+// The line ending of the multi-line messages of a pattern. The patterns and
+// the test messages are written with `\n` (or `%{NEWLINE}`) line endings, and
+// they are converted when the pattern is compiled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn from_str(line_ending: &str) -> Option<LineEnding> {
+        match line_ending {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::Crlf),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            LineEnding::Lf => "lf",
+            LineEnding::Crlf => "crlf",
+        }
+    }
+
+    // Only the bare line feeds are converted, so converting a text more times
+    // gives the same result.
+    pub fn convert(&self, text: &str) -> String {
+        match *self {
+            LineEnding::Lf => text.to_owned(),
+            LineEnding::Crlf => {
+                let mut converted = String::with_capacity(text.len());
+                let mut previous = None;
+                for c in text.chars() {
+                    if c == '\n' && previous != Some('\r') {
+                        converted.push_str(CRLF);
+                    } else {
+                        converted.push(c);
+                    }
+                    previous = Some(c);
+                }
+                converted
+            }
+        }
+    }
+
+    // The end strings of the GREEDY parsers are converted as well, so the
+    // parsers are created again with `F`.
+    pub fn convert_pattern<F: ParserFactory>(&self, pattern: CompiledPattern) -> CompiledPattern {
+        if *self == LineEnding::Lf {
+            return pattern;
+        }
+        pattern.into_iter()
+               .map(|token| {
+                   match token {
+                       TokenType::Literal(literal) => TokenType::Literal(self.convert(&literal)),
+                       TokenType::Parser(parser) => {
+                           let end_string = match parser.parser_type() {
+                               ParserType::Greedy { end_string: Some(end_string) }
+                                   if end_string.contains(grammar::NEWLINE) => {
+                                   Some(self.convert(end_string))
+                               }
+                               _ => None,
+                           };
+                           match end_string {
+                               Some(end_string) => {
+                                   TokenType::Parser(F::new_greedy(parser.name(),
+                                                                   Some(&end_string)))
+                               }
+                               None => TokenType::Parser(parser),
+                           }
+                       }
+                   }
+               })
+               .collect()
+    }
+}
+
+impl Default for LineEnding {
+    fn default() -> LineEnding {
+        LineEnding::Lf
+    }
+}
+
+// YAML block scalars (`|`) end with a line feed, but a log message never
+// does, so it's removed from every pattern.
+pub fn trim_block_scalar(mut text: String) -> String {
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    text
+}
@@ -0,0 +1,233 @@
+use super::{ExtraValue, Metadata, Reference, Severity};
+use serde;
+
+use std::collections::BTreeMap;
+use std::i64;
+
+impl serde::Deserialize for Metadata {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Metadata, D::Error>
+        where D: serde::de::Deserializer
+    {
+        deserializer.deserialize_struct("Metadata", &[], MetadataVisitor)
+    }
+}
+
+enum Field {
+    DESCRIPTION,
+    SEVERITY,
+    REFERENCES,
+    DEPRECATED,
+    OWNER,
+    CLASS,
+    OTHER(String),
+}
+
+impl serde::Deserialize for Field {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Field, D::Error>
+        where D: serde::de::Deserializer
+    {
+        struct FieldVisitor;
+
+        impl serde::de::Visitor for FieldVisitor {
+            type Value = Field;
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<Field, E>
+                where E: serde::de::Error
+            {
+                match value {
+                    "description" => Ok(Field::DESCRIPTION),
+                    "severity" => Ok(Field::SEVERITY),
+                    "references" => Ok(Field::REFERENCES),
+                    "deprecated" => Ok(Field::DEPRECATED),
+                    "owner" => Ok(Field::OWNER),
+                    "class" => Ok(Field::CLASS),
+                    _ => Ok(Field::OTHER(value.to_owned())),
+                }
+            }
+        }
+
+        deserializer.deserialize(FieldVisitor)
+    }
+}
+
+struct MetadataVisitor;
+
+impl serde::de::Visitor for MetadataVisitor {
+    type Value = Metadata;
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<Metadata, V::Error>
+        where V: serde::de::MapVisitor
+    {
+        let mut metadata = Metadata::new();
+
+        while let Some(field) = try!(visitor.visit_key()) {
+            match field {
+                Field::DESCRIPTION => metadata.set_description(Some(try!(visitor.visit_value()))),
+                Field::SEVERITY => metadata.set_severity(Some(try!(visitor.visit_value()))),
+                Field::REFERENCES => metadata.set_references(Some(try!(visitor.visit_value()))),
+                Field::DEPRECATED => metadata.set_deprecated(try!(visitor.visit_value())),
+                Field::OWNER => metadata.set_owner(Some(try!(visitor.visit_value()))),
+                Field::CLASS => metadata.set_class(Some(try!(visitor.visit_value()))),
+                Field::OTHER(key) => metadata.insert_extra(key, try!(visitor.visit_value())),
+            }
+        }
+
+        try!(visitor.end());
+
+        Ok(metadata)
+    }
+}
+
+impl serde::Deserialize for Severity {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Severity, D::Error>
+        where D: serde::de::Deserializer
+    {
+        struct SeverityVisitor;
+
+        impl serde::de::Visitor for SeverityVisitor {
+            type Value = Severity;
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<Severity, E>
+                where E: serde::de::Error
+            {
+                Severity::from_str(value).ok_or_else(|| {
+                    serde::de::Error::custom(format!("Unknown severity: {}", value))
+                })
+            }
+        }
+
+        deserializer.deserialize(SeverityVisitor)
+    }
+}
+
+// A reference is a map with exactly one entry, like `{"mitre": "T1110"}`.
+impl serde::Deserialize for Reference {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Reference, D::Error>
+        where D: serde::de::Deserializer
+    {
+        deserializer.deserialize_map(ReferenceVisitor)
+    }
+}
+
+struct ReferenceVisitor;
+
+impl serde::de::Visitor for ReferenceVisitor {
+    type Value = Reference;
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<Reference, V::Error>
+        where V: serde::de::MapVisitor
+    {
+        let kind: String = match try!(visitor.visit_key()) {
+            Some(kind) => kind,
+            None => {
+                return Err(serde::de::Error::custom("A reference must contain exactly one \
+                                                     key-value pair"))
+            }
+        };
+        let value: String = try!(visitor.visit_value());
+
+        if try!(visitor.visit_key::<String>()).is_some() {
+            return Err(serde::de::Error::custom("A reference must contain exactly one key-value \
+                                                 pair"));
+        }
+
+        try!(visitor.end());
+
+        Ok(Reference::new(kind, value))
+    }
+}
+
+impl serde::Deserialize for ExtraValue {
+    fn deserialize<D>(deserializer: &mut D) -> Result<ExtraValue, D::Error>
+        where D: serde::de::Deserializer
+    {
+        deserializer.deserialize(ExtraValueVisitor)
+    }
+}
+
+struct ExtraValueVisitor;
+
+impl serde::de::Visitor for ExtraValueVisitor {
+    type Value = ExtraValue;
+
+    fn visit_bool<E>(&mut self, value: bool) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::Bool(value))
+    }
+
+    fn visit_i64<E>(&mut self, value: i64) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::Int(value))
+    }
+
+    // the JSON deserializer visits the positive integers as `u64`
+    fn visit_u64<E>(&mut self, value: u64) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        if value <= i64::MAX as u64 {
+            Ok(ExtraValue::Int(value as i64))
+        } else {
+            Ok(ExtraValue::UInt(value))
+        }
+    }
+
+    fn visit_f64<E>(&mut self, value: f64) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::Float(value))
+    }
+
+    fn visit_str<E>(&mut self, value: &str) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(&mut self, value: String) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::String(value))
+    }
+
+    fn visit_unit<E>(&mut self) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::Null)
+    }
+
+    fn visit_none<E>(&mut self) -> Result<ExtraValue, E>
+        where E: serde::de::Error
+    {
+        Ok(ExtraValue::Null)
+    }
+
+    fn visit_some<D>(&mut self, deserializer: &mut D) -> Result<ExtraValue, D::Error>
+        where D: serde::de::Deserializer
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<V>(&mut self, mut visitor: V) -> Result<ExtraValue, V::Error>
+        where V: serde::de::SeqVisitor
+    {
+        let mut values = Vec::new();
+        while let Some(value) = try!(visitor.visit()) {
+            values.push(value);
+        }
+        try!(visitor.end());
+        Ok(ExtraValue::List(values))
+    }
+
+    fn visit_map<V>(&mut self, mut visitor: V) -> Result<ExtraValue, V::Error>
+        where V: serde::de::MapVisitor
+    {
+        let mut values = BTreeMap::new();
+        while let Some((key, value)) = try!(visitor.visit()) {
+            values.insert(key, value);
+        }
+        try!(visitor.end());
+        Ok(ExtraValue::Map(values))
+    }
+}
@@ -0,0 +1,184 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Descriptive information about a pattern. It isn't used for matching but
+// it's available in the match results.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    description: Option<String>,
+    severity: Option<Severity>,
+    references: Option<Vec<Reference>>,
+    deprecated: bool,
+    owner: Option<String>,
+    class: Option<String>,
+    extra: BTreeMap<String, ExtraValue>,
+}
+
+impl Metadata {
+    pub fn new() -> Metadata {
+        Metadata::default()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref().map(|description| description.borrow())
+    }
+
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    pub fn references(&self) -> Option<&[Reference]> {
+        self.references.as_ref().map(|references| references.borrow())
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_ref().map(|owner| owner.borrow())
+    }
+
+    // The patterndb class of the pattern, like `system` or `violation`.
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_ref().map(|class| class.borrow())
+    }
+
+    // The keys which are not known by actiondb, like `ticket: SEC-1234`.
+    // They are kept, so the files can be written back without losing them.
+    pub fn extra(&self) -> &BTreeMap<String, ExtraValue> {
+        &self.extra
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
+    pub fn set_severity(&mut self, severity: Option<Severity>) {
+        self.severity = severity;
+    }
+
+    pub fn set_references(&mut self, references: Option<Vec<Reference>>) {
+        self.references = references;
+    }
+
+    pub fn set_deprecated(&mut self, deprecated: bool) {
+        self.deprecated = deprecated;
+    }
+
+    pub fn set_owner(&mut self, owner: Option<String>) {
+        self.owner = owner;
+    }
+
+    pub fn set_class(&mut self, class: Option<String>) {
+        self.class = class;
+    }
+
+    pub fn insert_extra(&mut self, key: String, value: ExtraValue) {
+        self.extra.insert(key, value);
+    }
+}
+
+// The value of a key which is not known by actiondb. It can be any YAML or
+// JSON value, like `SEC-1234`, `3` or `[secops, noc]`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtraValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    // only the integers which don't fit into an `i64`
+    UInt(u64),
+    Float(f64),
+    String(String),
+    List(Vec<ExtraValue>),
+    Map(BTreeMap<String, ExtraValue>),
+}
+
+impl ExtraValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            ExtraValue::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+// The syslog severities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Emergency,
+    Alert,
+    Critical,
+    Error,
+    Warning,
+    Notice,
+    Informational,
+    Debug,
+}
+
+impl Severity {
+    pub fn from_str(severity: &str) -> Option<Severity> {
+        match severity {
+            "emerg" | "emergency" => Some(Severity::Emergency),
+            "alert" => Some(Severity::Alert),
+            "crit" | "critical" => Some(Severity::Critical),
+            "err" | "error" => Some(Severity::Error),
+            "warn" | "warning" => Some(Severity::Warning),
+            "notice" => Some(Severity::Notice),
+            "info" | "informational" => Some(Severity::Informational),
+            "debug" => Some(Severity::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Severity::Emergency => "emergency",
+            Severity::Alert => "alert",
+            Severity::Critical => "critical",
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Notice => "notice",
+            Severity::Informational => "informational",
+            Severity::Debug => "debug",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        formatter.write_str(self.as_str())
+    }
+}
+
+// A pointer to external information, like `mitre: T1110` or
+// `url: https://wiki.example.com/sshd`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    kind: String,
+    value: String,
+}
+
+impl Reference {
+    pub fn new(kind: String, value: String) -> Reference {
+        Reference {
+            kind: kind,
+            value: value,
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        formatter.write_fmt(format_args!("{}:{}", self.kind, self.value))
+    }
+}
@@ -0,0 +1,7 @@
+pub use self::metadata::{ExtraValue, Metadata, Reference, Severity};
+
+#[cfg(test)]
+mod test;
+mod deser;
+mod ser;
+mod metadata;
@@ -0,0 +1,134 @@
+use super::{ExtraValue, Metadata, Reference, Severity};
+
+use serde;
+
+use std::collections::btree_map;
+
+impl serde::Serialize for Metadata {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_struct("Metadata",
+                                    MetadataMapVisitor {
+                                        value: self,
+                                        state: 0,
+                                        extra: self.extra().iter(),
+                                    })
+    }
+}
+
+struct MetadataMapVisitor<'a> {
+    value: &'a Metadata,
+    state: u8,
+    extra: btree_map::Iter<'a, String, ExtraValue>,
+}
+
+impl<'a> serde::ser::MapVisitor for MetadataMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
+        where S: serde::Serializer
+    {
+        loop {
+            self.state += 1;
+            match self.state {
+                1 => {
+                    if let Some(description) = self.value.description() {
+                        return serializer.serialize_struct_elt("description", description)
+                                         .map(Some);
+                    }
+                }
+                2 => {
+                    if let Some(severity) = self.value.severity() {
+                        return serializer.serialize_struct_elt("severity", severity).map(Some);
+                    }
+                }
+                3 => {
+                    if let Some(references) = self.value.references() {
+                        return serializer.serialize_struct_elt("references", references)
+                                         .map(Some);
+                    }
+                }
+                4 => {
+                    if self.value.is_deprecated() {
+                        return serializer.serialize_struct_elt("deprecated", true).map(Some);
+                    }
+                }
+                5 => {
+                    if let Some(owner) = self.value.owner() {
+                        return serializer.serialize_struct_elt("owner", owner).map(Some);
+                    }
+                }
+                6 => {
+                    if let Some(class) = self.value.class() {
+                        return serializer.serialize_struct_elt("class", class).map(Some);
+                    }
+                }
+                7 => {
+                    // the keys of the unknown fields aren't static strings
+                    if let Some((key, value)) = self.extra.next() {
+                        self.state -= 1;
+                        return serializer.serialize_map_elt(key, value).map(Some);
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+}
+
+impl serde::Serialize for Severity {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl serde::Serialize for ExtraValue {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        match *self {
+            ExtraValue::Null => serializer.serialize_unit(),
+            ExtraValue::Bool(value) => serializer.serialize_bool(value),
+            ExtraValue::Int(value) => serializer.serialize_i64(value),
+            ExtraValue::UInt(value) => serializer.serialize_u64(value),
+            ExtraValue::Float(value) => serializer.serialize_f64(value),
+            ExtraValue::String(ref value) => serializer.serialize_str(value),
+            ExtraValue::List(ref values) => serde::Serialize::serialize(values, serializer),
+            ExtraValue::Map(ref values) => serde::Serialize::serialize(values, serializer),
+        }
+    }
+}
+
+// A reference is a map with exactly one key-value pair, like `mitre: T1110`.
+impl serde::Serialize for Reference {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_map(ReferenceMapVisitor {
+            value: self,
+            visited: false,
+        })
+    }
+}
+
+struct ReferenceMapVisitor<'a> {
+    value: &'a Reference,
+    visited: bool,
+}
+
+impl<'a> serde::ser::MapVisitor for ReferenceMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
+        where S: serde::Serializer
+    {
+        if self.visited {
+            return Ok(None);
+        }
+        self.visited = true;
+        serializer.serialize_map_elt(self.value.kind(), self.value.value()).map(Some)
+    }
+
+    fn len(&self) -> Option<usize> {
+        Some(1)
+    }
+}
@@ -0,0 +1,94 @@
+use super::{ExtraValue, Metadata, Reference, Severity};
+use serde_json;
+use serde_yaml;
+
+#[test]
+fn test_given_yaml_metadata_when_it_is_deserialized_then_we_get_the_right_instance() {
+    let buffer = r#"
+description: "Failed password authentication"
+severity: warning
+references:
+  - mitre: T1110
+  - url: "https://wiki.example.com/sshd"
+deprecated: true
+owner: secops
+class: violation
+"#;
+    let metadata = serde_yaml::from_str::<Metadata>(buffer)
+                       .expect("Failed to deserialize valid metadata from YAML");
+    assert_eq!(metadata.description(), Some("Failed password authentication"));
+    assert_eq!(metadata.severity(), Some(Severity::Warning));
+    assert_eq!(metadata.references(),
+               Some(&[Reference::new("mitre".to_owned(), "T1110".to_owned()),
+                      Reference::new("url".to_owned(),
+                                     "https://wiki.example.com/sshd".to_owned())][..]));
+    assert!(metadata.is_deprecated());
+    assert_eq!(metadata.owner(), Some("secops"));
+    assert_eq!(metadata.class(), Some("violation"));
+}
+
+#[test]
+fn test_given_json_metadata_when_it_does_not_have_the_optional_fields_then_it_can_be_loaded() {
+    let metadata = serde_json::from_str::<Metadata>("{}")
+                       .expect("Failed to deserialize empty metadata from JSON");
+    assert_eq!(metadata, Metadata::new());
+}
+
+#[test]
+fn test_given_json_metadata_when_the_severity_is_unknown_then_we_get_an_error() {
+    let _ = serde_json::from_str::<Metadata>(r#"{"severity": "catastrophic"}"#)
+                .err()
+                .expect("We deserialized metadata with an unknown severity");
+}
+
+#[test]
+fn test_given_json_metadata_when_a_reference_has_more_entries_then_we_get_an_error() {
+    let buffer = r#"{"references": [{"mitre": "T1110", "url": "https://example.com"}]}"#;
+    let _ = serde_json::from_str::<Metadata>(buffer)
+                .err()
+                .expect("We deserialized a reference with more than one entry");
+}
+
+#[test]
+fn test_given_json_metadata_when_it_contains_unknown_fields_then_they_are_kept() {
+    let metadata = serde_json::from_str::<Metadata>(r#"{"owner": "secops", "ticket": "SEC-1234"}"#)
+                       .expect("Failed to deserialize metadata with an unknown field");
+    assert_eq!(metadata.owner(), Some("secops"));
+    assert_eq!(metadata.extra().get("ticket").and_then(|value| value.as_str()),
+               Some("SEC-1234"));
+
+    let json = serde_json::to_string(&metadata).expect("Failed to serialize metadata");
+    assert_eq!(serde_json::from_str::<Metadata>(&json).expect("Failed to deserialize metadata"),
+               metadata);
+}
+
+#[test]
+fn test_given_yaml_metadata_when_unknown_fields_are_not_strings_then_they_are_kept() {
+    let buffer = r#"
+priority: 3
+reviewed: false
+teams:
+  - secops
+  - noc
+escalation:
+  after: 1.5
+"#;
+    let metadata = serde_yaml::from_str::<Metadata>(buffer)
+                       .expect("Failed to deserialize metadata with non-string unknown fields");
+    let extra = metadata.extra();
+    assert_eq!(extra.get("priority"), Some(&ExtraValue::Int(3)));
+    assert_eq!(extra.get("reviewed"), Some(&ExtraValue::Bool(false)));
+    assert_eq!(extra.get("teams"),
+               Some(&ExtraValue::List(vec![ExtraValue::String("secops".to_owned()),
+                                           ExtraValue::String("noc".to_owned())])));
+    match extra.get("escalation") {
+        Some(&ExtraValue::Map(ref escalation)) => {
+            assert_eq!(escalation.get("after"), Some(&ExtraValue::Float(1.5)));
+        }
+        other => panic!("The escalation is not a map: {:?}", other),
+    }
+
+    let json = serde_json::to_string(&metadata).expect("Failed to serialize metadata");
+    assert_eq!(serde_json::from_str::<Metadata>(&json).expect("Failed to deserialize metadata"),
+               metadata);
+}
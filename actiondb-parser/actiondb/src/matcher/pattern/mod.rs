@@ -1,10 +1,16 @@
 pub use self::pattern::Pattern;
+pub use self::raw::RawPattern;
 pub use self::source::PatternSource;
 
 #[cfg(test)]
 mod test;
 mod pattern;
 mod deser;
+mod ser;
+mod raw;
 pub mod source;
 pub mod file;
 pub mod testmessage;
+pub mod template;
+pub mod metadata;
+pub mod line_ending;
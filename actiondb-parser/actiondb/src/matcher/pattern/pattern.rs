@@ -2,7 +2,12 @@ use uuid::Uuid;
 use serde_json;
 
 use matcher::compiled_pattern::{CompiledPattern, TokenType};
+use matcher::BuildError;
+use parsers::ParserType;
 use super::testmessage::TestMessage;
+use super::template::{self, Template};
+use super::metadata::Metadata;
+use super::line_ending::LineEnding;
 
 use std::borrow::Borrow;
 use std::collections::BTreeMap;
@@ -13,30 +18,82 @@ pub struct Pattern {
     uuid: Uuid,
     pattern: CompiledPattern,
     values: Option<BTreeMap<String, String>>,
+    templates: Option<BTreeMap<String, Template>>,
     tags: Option<Vec<String>>,
     test_messages: Option<Vec<TestMessage>>,
+    metadata: Option<Metadata>,
+    line_ending: LineEnding,
 }
 
 impl Pattern {
     pub fn with_uuid(uuid: Uuid) -> Pattern {
         Pattern::new(None, uuid, Vec::new(), None, None, None)
+            .expect("Failed to create a pattern without values")
     }
 
+    // The values are templates, so they are checked against the values
+    // extracted by `pattern`.
     pub fn new(name: Option<String>,
                uuid: Uuid,
                pattern: CompiledPattern,
                test_messages: Option<Vec<TestMessage>>,
                values: Option<BTreeMap<String, String>>,
                tags: Option<Vec<String>>)
-               -> Pattern {
-        Pattern {
+               -> Result<Pattern, BuildError> {
+        let templates = match values {
+            Some(ref values) => Some(try!(Pattern::parse_templates(&uuid, &pattern, values))),
+            None => None,
+        };
+        Ok(Pattern {
             uuid: uuid,
             name: name,
             pattern: pattern,
             values: values,
+            templates: templates,
             tags: tags,
             test_messages: test_messages,
+            metadata: None,
+            line_ending: LineEnding::default(),
+        })
+    }
+
+    // Parses the values as templates which reference only values extracted
+    // by the pattern.
+    fn parse_templates(uuid: &Uuid,
+                       pattern: &CompiledPattern,
+                       values: &BTreeMap<String, String>)
+                       -> Result<BTreeMap<String, Template>, BuildError> {
+        let mut templates = BTreeMap::new();
+        for (key, value) in values {
+            let template = try!(Template::parse(value).map_err(|error| {
+                BuildError::invalid_value(uuid, key, error)
+            }));
+            for reference in template.references() {
+                if !Pattern::is_extracted(pattern, reference) {
+                    let error = template::Error::UnknownReference(reference.to_owned());
+                    return Err(BuildError::invalid_value(uuid, key, error));
+                }
+            }
+            templates.insert(key.clone(), template);
         }
+        Ok(templates)
+    }
+
+    // The values of the named parsers can be referenced by their names. The
+    // items of a `REPEAT` parser are referenced by their indexes, like
+    // `ports.0` and `ports.count` (see `MatchResult::indexed_values()`).
+    fn is_extracted(pattern: &CompiledPattern, reference: &str) -> bool {
+        pattern.iter().any(|token| {
+            let parser = match *token {
+                TokenType::Parser(ref parser) => parser,
+                TokenType::Literal(_) => return false,
+            };
+            match (parser.name(), parser.parser_type()) {
+                (Some(name), ParserType::Repeat { .. }) => is_indexed_value(name, reference),
+                (Some(name), _) => name == reference,
+                (None, _) => false,
+            }
+        })
     }
 
     pub fn with_random_uuid() -> Pattern {
@@ -59,10 +116,34 @@ impl Pattern {
         self.values.as_ref()
     }
 
+    pub fn templates(&self) -> Option<&BTreeMap<String, Template>> {
+        self.templates.as_ref()
+    }
+
     pub fn tags(&self) -> Option<&[String]> {
         self.tags.as_ref().map(|tags| tags.borrow())
     }
 
+    pub fn test_messages(&self) -> Option<&[TestMessage]> {
+        self.test_messages.as_ref().map(|test_messages| test_messages.borrow())
+    }
+
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    pub fn set_metadata(&mut self, metadata: Option<Metadata>) {
+        self.metadata = metadata;
+    }
+
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+    }
+
     pub fn from_json(doc: &str) -> Result<Pattern, serde_json::error::Error> {
         serde_json::from_str::<Pattern>(doc)
     }
@@ -79,7 +160,19 @@ impl Pattern {
         }
     }
 
+    pub fn set_test_messages(&mut self, test_messages: Option<Vec<TestMessage>>) {
+        self.test_messages = test_messages;
+    }
+
     pub fn pop_test_message(&mut self) -> Option<TestMessage> {
         self.test_messages.as_mut().map_or(None, |x| x.pop())
     }
 }
+
+fn is_indexed_value(name: &str, reference: &str) -> bool {
+    if !reference.starts_with(name) || !reference[name.len()..].starts_with('.') {
+        return false;
+    }
+    let index = &reference[name.len() + 1..];
+    index == "count" || (!index.is_empty() && index.chars().all(|c| c.is_digit(10)))
+}
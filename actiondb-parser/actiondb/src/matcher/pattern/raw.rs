@@ -0,0 +1,94 @@
+use uuid::Uuid;
+
+use grammar;
+use matcher::BuildError;
+use parsers::ParserFactory;
+use super::Pattern;
+use super::testmessage::TestMessage;
+use super::metadata::Metadata;
+use super::line_ending::LineEnding;
+
+use std::collections::BTreeMap;
+
+// A deserialized pattern whose pattern string is not compiled yet. Compiling
+// it separately keeps the details of a grammar error, so they can be
+// reported with the location of the pattern.
+#[derive(Clone, Debug)]
+pub struct RawPattern {
+    name: Option<String>,
+    uuid: Uuid,
+    pattern: String,
+    values: Option<BTreeMap<String, String>>,
+    tags: Option<Vec<String>>,
+    test_messages: Option<Vec<TestMessage>>,
+    metadata: Option<Metadata>,
+    line_ending: LineEnding,
+}
+
+impl RawPattern {
+    pub fn new(name: Option<String>,
+               uuid: Uuid,
+               pattern: String,
+               test_messages: Option<Vec<TestMessage>>,
+               values: Option<BTreeMap<String, String>>,
+               tags: Option<Vec<String>>)
+               -> RawPattern {
+        RawPattern {
+            name: name,
+            uuid: uuid,
+            pattern: pattern,
+            values: values,
+            tags: tags,
+            test_messages: test_messages,
+            metadata: None,
+            line_ending: LineEnding::default(),
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn set_metadata(&mut self, metadata: Option<Metadata>) {
+        self.metadata = metadata;
+    }
+
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+    }
+
+    pub fn compile<F: ParserFactory>(self) -> Result<Pattern, BuildError> {
+        match grammar::parser::pattern_with_factory::<F>(&self.pattern) {
+            Ok(compiled_pattern) => {
+                let line_ending = self.line_ending;
+                let compiled_pattern = line_ending.convert_pattern::<F>(compiled_pattern);
+                let test_messages = self.test_messages.map(|test_messages| {
+                    test_messages.into_iter()
+                                 .map(|mut test_message| {
+                                     test_message.convert_line_endings(line_ending);
+                                     test_message
+                                 })
+                                 .collect()
+                });
+                let mut pattern = try!(Pattern::new(self.name,
+                                                    self.uuid,
+                                                    compiled_pattern,
+                                                    test_messages,
+                                                    self.values,
+                                                    self.tags));
+                pattern.set_metadata(self.metadata);
+                pattern.set_line_ending(line_ending);
+                Ok(pattern)
+            }
+            Err(error) => Err(BuildError::invalid_pattern(&self.uuid, self.name, self.pattern, error)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::Pattern;
+use super::line_ending::LineEnding;
+use grammar;
+
+use serde;
+
+impl serde::Serialize for Pattern {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_struct("Pattern",
+                                    PatternMapVisitor {
+                                        value: self,
+                                        state: 0,
+                                    })
+    }
+}
+
+// The fields are serialized in a canonical order, the ones without a value
+// are left out.
+struct PatternMapVisitor<'a> {
+    value: &'a Pattern,
+    state: u8,
+}
+
+impl<'a> serde::ser::MapVisitor for PatternMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
+        where S: serde::Serializer
+    {
+        loop {
+            self.state += 1;
+            match self.state {
+                1 => {
+                    let uuid = self.value.uuid().hyphenated().to_string();
+                    return serializer.serialize_struct_elt("uuid", uuid).map(Some);
+                }
+                2 => {
+                    if let Some(name) = self.value.name() {
+                        return serializer.serialize_struct_elt("name", name).map(Some);
+                    }
+                }
+                3 => {
+                    let mut pattern = grammar::format_pattern(self.value.pattern());
+                    // the line endings are converted back when the pattern
+                    // is loaded
+                    if self.value.line_ending() == LineEnding::Crlf {
+                        pattern = pattern.replace("\r%{NEWLINE}", "%{NEWLINE}");
+                    }
+                    return serializer.serialize_struct_elt("pattern", pattern).map(Some);
+                }
+                4 => {
+                    let line_ending = self.value.line_ending();
+                    if line_ending != LineEnding::default() {
+                        return serializer.serialize_struct_elt("line_ending", line_ending.as_str())
+                                         .map(Some);
+                    }
+                }
+                5 => {
+                    if let Some(values) = self.value.values() {
+                        return serializer.serialize_struct_elt("values", values).map(Some);
+                    }
+                }
+                6 => {
+                    if let Some(tags) = self.value.tags() {
+                        return serializer.serialize_struct_elt("tags", tags).map(Some);
+                    }
+                }
+                7 => {
+                    if let Some(metadata) = self.value.metadata() {
+                        return serializer.serialize_struct_elt("metadata", metadata).map(Some);
+                    }
+                }
+                8 => {
+                    match self.value.test_messages() {
+                        Some(test_messages) if !test_messages.is_empty() => {
+                            return serializer.serialize_struct_elt("test_messages", test_messages)
+                                             .map(Some);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+}
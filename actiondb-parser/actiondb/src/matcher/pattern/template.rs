@@ -0,0 +1,172 @@
+// Pattern values can reference the values extracted by the parsers of the
+// pattern, like `login failure for ${user} from ${src_ip}`. A literal `$` can
+// be written as `$$`.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt;
+
+const REFERENCE_BEGIN: &'static str = "${";
+const REFERENCE_END: char = '}';
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Fragment {
+    Literal(String),
+    Reference(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    fragments: Vec<Fragment>,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Template, Error> {
+        let mut fragments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+
+        while let Some(pos) = rest.find('$') {
+            literal.push_str(&rest[..pos]);
+            let position = template.len() - rest.len() + pos;
+            rest = &rest[pos..];
+
+            if rest.starts_with("$$") {
+                literal.push('$');
+                rest = &rest[2..];
+            } else if rest.starts_with(REFERENCE_BEGIN) {
+                let end = try!(rest.find(REFERENCE_END).ok_or(Error::Unterminated(position)));
+                let name = &rest[REFERENCE_BEGIN.len()..end];
+                if name.is_empty() {
+                    return Err(Error::EmptyName(position));
+                }
+                if !literal.is_empty() {
+                    fragments.push(Fragment::Literal(literal));
+                    literal = String::new();
+                }
+                fragments.push(Fragment::Reference(name.to_owned()));
+                rest = &rest[end + 1..];
+            } else {
+                literal.push('$');
+                rest = &rest[1..];
+            }
+        }
+
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            fragments.push(Fragment::Literal(literal));
+        }
+        Ok(Template { fragments: fragments })
+    }
+
+    pub fn references<'a>(&'a self) -> Box<Iterator<Item = &'a str> + 'a> {
+        Box::new(self.fragments.iter().filter_map(|fragment| {
+            match *fragment {
+                Fragment::Reference(ref name) => Some(name.as_str()),
+                Fragment::Literal(_) => None,
+            }
+        }))
+    }
+
+    // Substitutes the references with the given values. Missing values are
+    // substituted with empty strings. Templates without references are not
+    // copied.
+    pub fn format<'a>(&'a self, values: &BTreeMap<&str, &str>) -> Cow<'a, str> {
+        match self.fragments.len() {
+            0 => Cow::Borrowed(""),
+            1 => {
+                match self.fragments[0] {
+                    Fragment::Literal(ref literal) => Cow::Borrowed(literal),
+                    Fragment::Reference(ref name) => {
+                        Cow::Owned(values.get(name.as_str()).map_or("", |value| *value).to_owned())
+                    }
+                }
+            }
+            _ => {
+                let mut formatted = String::new();
+                for fragment in &self.fragments {
+                    match *fragment {
+                        Fragment::Literal(ref literal) => formatted.push_str(literal),
+                        Fragment::Reference(ref name) => {
+                            formatted.push_str(values.get(name.as_str()).map_or("", |value| *value))
+                        }
+                    }
+                }
+                Cow::Owned(formatted)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Unterminated(usize),
+    EmptyName(usize),
+    UnknownReference(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Error::Unterminated(position) => {
+                formatter.write_fmt(format_args!("Unterminated reference: position={}", position))
+            }
+            Error::EmptyName(position) => {
+                formatter.write_fmt(format_args!("Reference without a name: position={}", position))
+            }
+            Error::UnknownReference(ref name) => {
+                formatter.write_fmt(format_args!("The referenced value is not extracted by the \
+                                                  pattern: name={}",
+                                                 name))
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Unterminated(_) => "Unterminated reference",
+            Error::EmptyName(_) => "Reference without a name",
+            Error::UnknownReference(_) => "The referenced value is not extracted by the pattern",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Error, Template};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_given_template_when_it_is_formatted_then_the_references_are_substituted() {
+        let template = Template::parse("login failure for ${user} from ${src_ip}")
+                           .expect("Failed to parse a valid template");
+        let mut values = BTreeMap::new();
+        values.insert("user", "bob");
+        values.insert("src_ip", "10.0.0.1");
+        assert_eq!(template.format(&values), "login failure for bob from 10.0.0.1");
+        assert_eq!(template.references().collect::<Vec<&str>>(), vec!["user", "src_ip"]);
+    }
+
+    #[test]
+    fn test_given_template_when_a_referenced_value_is_missing_then_it_is_substituted_with_an_empty_string
+        () {
+        let template = Template::parse("${user}@corp").expect("Failed to parse a valid template");
+        assert_eq!(template.format(&BTreeMap::new()), "@corp");
+    }
+
+    #[test]
+    fn test_given_template_when_it_contains_escaped_or_lonely_dollar_signs_then_they_are_kept() {
+        let template = Template::parse("$$100 or $5").expect("Failed to parse a valid template");
+        assert_eq!(template.format(&BTreeMap::new()), "$100 or $5");
+        assert_eq!(template.references().count(), 0);
+    }
+
+    #[test]
+    fn test_given_template_when_a_reference_is_invalid_then_we_get_an_error() {
+        assert_eq!(Template::parse("foo ${user"), Err(Error::Unterminated(4)));
+        assert_eq!(Template::parse("foo ${}"), Err(Error::EmptyName(4)));
+    }
+}
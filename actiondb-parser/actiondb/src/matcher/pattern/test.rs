@@ -1,5 +1,14 @@
 use super::Pattern;
+use super::file::PatternFile;
+use super::line_ending::LineEnding;
+use grammar;
+use matcher::{BuildError, Matcher, MatcherFactory};
+use matcher::trie::factory::TrieMatcherFactory;
 use uuid::Uuid;
+use serde_json;
+use serde_yaml;
+
+use std::collections::BTreeMap;
 
 #[test]
 fn test_given_json_document_when_it_does_not_contain_errors_then_pattern_can_be_created_from_it
@@ -163,3 +172,219 @@ fn test_invalid_uuid_wont_make_uuid_library_panic_during_deserialization() {
 
     let _ = Pattern::from_json(buffer).err().expect("We should not panic when 'uuid' field is invalid");
 }
+
+#[test]
+fn test_given_pattern_when_it_is_serialized_then_the_fields_are_in_canonical_order() {
+    let buffer = r#"{
+  "test_messages": [{"negative": true, "message": "user bob logged out"}],
+  "tags": ["login"],
+  "values": {"action": "login"},
+  "pattern": "user %{GREEDY:user} logged in",
+  "metadata": {"owner": "secops", "severity": "warning", "references": [{"mitre": "T1078"}]},
+  "name": "USER_LOGIN",
+  "uuid": "6d2cba0c-e241-464a-89c3-8035cac8f73e"
+}"#;
+    let expected = "{\"uuid\":\"6d2cba0c-e241-464a-89c3-8035cac8f73e\",\"name\":\"USER_LOGIN\",\
+                    \"pattern\":\"user %{GREEDY:user} logged in\",\"values\":{\"action\":\
+                    \"login\"},\"tags\":[\"login\"],\"metadata\":{\"severity\":\"warning\",\
+                    \"references\":[{\"mitre\":\"T1078\"}],\"owner\":\"secops\"},\
+                    \"test_messages\":[{\"message\":\"user bob logged out\",\
+                    \"negative\":true}]}";
+    let pattern = Pattern::from_json(buffer).expect("Failed to deserialize a JSON Pattern");
+
+    let serialized = serde_json::to_string(&pattern).expect("Failed to serialize a Pattern");
+
+    assert_eq!(serialized, expected);
+}
+
+#[test]
+fn test_given_pattern_file_when_it_is_serialized_as_yaml_then_it_can_be_deserialized() {
+    let buffer = r#"
+patterns:
+  - uuid: 6d2cba0c-e241-464a-89c3-8035cac8f73e
+    name: USER_LOGIN
+    pattern: "\\%\\{ user %{GREEDY:user} logged in from %{INT(min_len=2):port}"
+    test_messages:
+      - message: "%{ user bob logged in from 22"
+        values:
+          user: bob
+          port: "22"
+"#;
+    let file = serde_yaml::from_str::<PatternFile>(buffer)
+                   .expect("Failed to deserialize a YAML pattern file");
+
+    let serialized = file.to_yaml().expect("Failed to serialize a pattern file");
+    let file = serde_yaml::from_str::<PatternFile>(&serialized)
+                   .expect("Failed to deserialize a serialized pattern file");
+
+    assert_eq!(file.to_yaml().unwrap(), serialized);
+    let pattern = &file.patterns()[0];
+    assert_eq!(pattern.name(), Some("USER_LOGIN"));
+    assert_eq!(pattern.test_messages().unwrap()[0].values().get("port").map(|port| port.as_str()),
+               Some("22"));
+}
+
+#[test]
+fn test_given_yaml_pattern_with_crlf_line_ending_when_it_is_loaded_then_the_line_endings_are_converted
+    () {
+    let buffer = r#"
+patterns:
+  - uuid: 6d2cba0c-e241-464a-89c3-8035cac8f73e
+    line_ending: crlf
+    pattern: |
+      Exception: %{GREEDY:message}
+      %{LINES(2):trace}
+    test_messages:
+      - message: |
+          Exception: disk is full
+          at Foo.bar
+          at Foo.baz
+        values:
+          message: disk is full
+          trace: "at Foo.bar\nat Foo.baz"
+"#;
+    let file = serde_yaml::from_str::<PatternFile>(buffer)
+                   .expect("Failed to deserialize a YAML pattern file with block scalars");
+    let pattern = &file.patterns()[0];
+    let message = "Exception: disk is full\r\nat Foo.bar\r\nat Foo.baz";
+
+    assert_eq!(pattern.line_ending(), LineEnding::Crlf);
+    assert_eq!(pattern.test_messages().unwrap()[0].message(), message);
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(pattern.clone());
+    let result = matcher.parse(message).expect("Failed to parse a CRLF message");
+    assert_eq!(result.values().get("trace"), Some(&"at Foo.bar\r\nat Foo.baz"));
+
+    let serialized = file.to_yaml().expect("Failed to serialize a pattern file");
+    assert!(serialized.contains("Exception: %{GREEDY:message}%{NEWLINE}%{LINES(2):trace}"));
+    let file = serde_yaml::from_str::<PatternFile>(&serialized)
+                   .expect("Failed to deserialize a serialized pattern file");
+    assert_eq!(file.patterns()[0].line_ending(), LineEnding::Crlf);
+    assert_eq!(file.patterns()[0].test_messages().unwrap()[0].message(), message);
+}
+
+#[test]
+fn test_given_json_pattern_without_line_ending_when_it_ends_with_a_line_feed_then_it_is_trimmed() {
+    let buffer = r#"{"uuid": "6d2cba0c-e241-464a-89c3-8035cac8f73e", "pattern": "job done\n",
+                     "test_messages": [{"message": "job done\n"}]}"#;
+    let pattern = Pattern::from_json(buffer).expect("Failed to deserialize a JSON pattern");
+
+    assert_eq!(pattern.test_messages().unwrap()[0].message(), "job done");
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(pattern.clone());
+    assert!(matcher.parse("job done").is_some());
+}
+
+#[test]
+fn test_given_yaml_pattern_without_line_ending_when_it_is_a_block_scalar_then_it_is_trimmed() {
+    let buffer = r#"
+patterns:
+  - uuid: 6d2cba0c-e241-464a-89c3-8035cac8f73e
+    pattern: |
+      job %{INT:id}
+      done
+    test_messages:
+      - message: |
+          job 42
+          done
+"#;
+    let file = serde_yaml::from_str::<PatternFile>(buffer)
+                   .expect("Failed to deserialize a YAML pattern file with block scalars");
+    let pattern = &file.patterns()[0];
+
+    assert_eq!(pattern.test_messages().unwrap()[0].message(), "job 42\ndone");
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(pattern.clone());
+    assert!(matcher.parse("job 42\ndone").is_some());
+}
+
+#[test]
+fn test_given_yaml_pattern_with_lf_line_ending_when_it_is_loaded_then_block_scalars_are_trimmed() {
+    let buffer = r#"
+patterns:
+  - uuid: 6d2cba0c-e241-464a-89c3-8035cac8f73e
+    line_ending: lf
+    pattern: |
+      job %{INT:id} done
+    test_messages:
+      - message: |
+          job 42 done
+"#;
+    let file = serde_yaml::from_str::<PatternFile>(buffer)
+                   .expect("Failed to deserialize a YAML pattern file with block scalars");
+    let pattern = &file.patterns()[0];
+
+    assert_eq!(pattern.test_messages().unwrap()[0].message(), "job 42 done");
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(pattern.clone());
+    assert!(matcher.parse("job 42 done").is_some());
+}
+
+#[test]
+fn test_given_json_pattern_when_its_line_ending_is_unknown_then_pattern_cannot_be_built_from_it() {
+    let buffer = r#"{"uuid": "6d2cba0c-e241-464a-89c3-8035cac8f73e", "pattern": "foo",
+                     "line_ending": "cr"}"#;
+    let _ = Pattern::from_json(buffer)
+                .err()
+                .expect("We loaded a pattern with an invalid line ending");
+}
+
+#[test]
+fn test_given_pattern_with_repeat_parser_when_a_message_is_parsed_then_the_items_are_indexed() {
+    let buffer = r#"
+patterns:
+  - uuid: 6d2cba0c-e241-464a-89c3-8035cac8f73e
+    pattern: "ports %{REPEAT(INT, separator=\",\"):ports} are open"
+    test_messages:
+      - message: "ports 22,80,443 are open"
+        values:
+          ports.0: "22"
+          ports.1: "80"
+          ports.2: "443"
+          ports.count: "3"
+"#;
+    let file = serde_yaml::from_str::<PatternFile>(buffer)
+                   .expect("Failed to deserialize a pattern file with a REPEAT parser");
+    let pattern = &file.patterns()[0];
+    let test_message = &pattern.test_messages().unwrap()[0];
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(pattern.clone());
+
+    let result = matcher.parse(test_message.message());
+    test_message.test_result(pattern.uuid(), result.as_ref())
+                .expect("The indexed values of the REPEAT parser don't match");
+    let result = matcher.parse("ports 22 are open").expect("Failed to parse one item");
+    assert_eq!(result.indexed_values().get("ports.count").map(|count| count.as_ref()),
+               Some("1"));
+    assert!(matcher.parse("ports are open").is_none());
+}
+
+fn pattern_with_value(pattern: &str, value: &str) -> Result<Pattern, BuildError> {
+    let compiled_pattern = grammar::parser::pattern(pattern).expect("Failed to compile a pattern");
+    let mut values = BTreeMap::new();
+    values.insert("summary".to_owned(), value.to_owned());
+    Pattern::new(None, Uuid::new_v4(), compiled_pattern, None, Some(values), None)
+}
+
+#[test]
+fn test_given_pattern_when_it_is_created_with_invalid_templates_then_it_fails() {
+    let _ = pattern_with_value("user %{GREEDY:user} logged in", "${user")
+                .err()
+                .expect("We created a pattern with an unterminated reference");
+    let _ = pattern_with_value("user %{GREEDY:user} logged in", "${src_ip}")
+                .err()
+                .expect("We created a pattern referencing a value which is not extracted");
+}
+
+#[test]
+fn test_given_pattern_with_repeat_parser_when_a_template_references_its_items_then_it_is_valid() {
+    let pattern = "ports %{REPEAT(INT, separator=\",\"):ports} are open";
+    pattern_with_value(pattern, "${ports.count} ports, the first is ${ports.0}")
+        .expect("Failed to create a pattern referencing the items of a REPEAT parser");
+    let _ = pattern_with_value(pattern, "${ports}")
+                .err()
+                .expect("We created a pattern referencing the name of a REPEAT parser");
+    let _ = pattern_with_value(pattern, "${ports.first}")
+                .err()
+                .expect("We created a pattern referencing an invalid index");
+}
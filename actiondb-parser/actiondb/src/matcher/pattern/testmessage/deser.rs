@@ -1,5 +1,6 @@
 use super::TestMessage;
 use serde;
+use uuid::Uuid;
 
 impl serde::Deserialize for TestMessage {
     fn deserialize<D>(deserializer: &mut D) -> Result<TestMessage, D::Error>
@@ -13,6 +14,8 @@ enum Field {
     MESSAGE,
     VALUES,
     TAGS,
+    NEGATIVE,
+    EXPECTEDUUID,
 }
 
 impl serde::Deserialize for Field {
@@ -31,6 +34,8 @@ impl serde::Deserialize for Field {
                     "message" => Ok(Field::MESSAGE),
                     "values" => Ok(Field::VALUES),
                     "tags" => Ok(Field::TAGS),
+                    "negative" => Ok(Field::NEGATIVE),
+                    "expected_uuid" => Ok(Field::EXPECTEDUUID),
                     _ => Err(serde::de::Error::custom(format!("Unexpected field: {}", value))),
                 }
             }
@@ -52,12 +57,16 @@ impl serde::de::Visitor for TestMessageVisitor {
         let mut message = None;
         let mut values = None;
         let mut tags = None;
+        let mut negative = None;
+        let mut expected_uuid: Option<Uuid> = None;
 
         while let Some(field) = try!(visitor.visit_key()) {
             match field {
                 Field::MESSAGE => message = Some(try!(visitor.visit_value())),
                 Field::VALUES => values = Some(try!(visitor.visit_value())),
                 Field::TAGS => tags = Some(try!(visitor.visit_value())),
+                Field::NEGATIVE => negative = Some(try!(visitor.visit_value())),
+                Field::EXPECTEDUUID => expected_uuid = Some(try!(visitor.visit_value())),
             }
         }
 
@@ -66,10 +75,21 @@ impl serde::de::Visitor for TestMessageVisitor {
             None => try!(visitor.missing_field("message")),
         };
 
+        let negative = negative.unwrap_or(false);
+        // a negative test message doesn't match its pattern, so there is
+        // nothing to expect from the match
+        if negative && (values.is_some() || expected_uuid.is_some()) {
+            return Err(serde::de::Error::custom("A negative test message cannot have values \
+                                                 or an expected_uuid"));
+        }
+
         let values = values.unwrap_or_default();
 
         try!(visitor.end());
 
-        Ok(TestMessage::new(message_final, values, tags))
+        let mut test_message = TestMessage::new(message_final, values, tags);
+        test_message.set_negative(negative);
+        test_message.set_expected_uuid(expected_uuid);
+        Ok(test_message)
     }
 }
@@ -22,6 +22,7 @@ pub enum Error {
         message: String,
     },
     MatchedToOtherPattern {
+        pattern_uuid: String,
         expected_uuid: String,
         got_uuid: String,
         message: String,
@@ -31,6 +32,10 @@ pub enum Error {
         expected: Option<Vec<String>>,
         got: Option<Vec<String>>,
     },
+    UnexpectedMatch {
+        pattern_uuid: String,
+        message: String,
+    },
 }
 
 impl Error {
@@ -61,17 +66,26 @@ impl Error {
         }
     }
 
-    pub fn matched_to_other_pattern(expected_uuid: &Uuid,
+    pub fn matched_to_other_pattern(pattern_uuid: &Uuid,
+                                    expected_uuid: &Uuid,
                                     got_uuid: &Uuid,
                                     test_message: &str)
                                     -> Error {
         Error::MatchedToOtherPattern {
+            pattern_uuid: pattern_uuid.hyphenated().to_string(),
             expected_uuid: expected_uuid.hyphenated().to_string(),
             got_uuid: got_uuid.hyphenated().to_string(),
             message: test_message.to_owned(),
         }
     }
 
+    pub fn unexpected_match(pattern_uuid: &Uuid, test_msg: &TestMessage) -> Error {
+        Error::UnexpectedMatch {
+            pattern_uuid: pattern_uuid.hyphenated().to_string(),
+            message: test_msg.message().to_owned(),
+        }
+    }
+
     pub fn unexpected_tags(pattern_uuid: &Uuid,
                            expected: Option<Vec<String>>,
                            got: Option<Vec<String>>)
@@ -84,6 +98,20 @@ impl Error {
     }
 }
 
+impl Error {
+    // The UUID of the pattern whose test message failed.
+    pub fn pattern_uuid(&self) -> &str {
+        match *self {
+            Error::ValueNotMatch { ref pattern_uuid, .. } |
+            Error::KeyNotFound { ref pattern_uuid, .. } |
+            Error::TestMessageDoesntMatch { ref pattern_uuid, .. } |
+            Error::UnexpectedTags { ref pattern_uuid, .. } |
+            Error::UnexpectedMatch { ref pattern_uuid, .. } |
+            Error::MatchedToOtherPattern { ref pattern_uuid, .. } => pattern_uuid,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
@@ -107,9 +135,14 @@ impl fmt::Display for Error {
                                            pattern_uuid,
                                            message))
             }
-            Error::MatchedToOtherPattern{ref expected_uuid, ref got_uuid, ref message} => {
+            Error::MatchedToOtherPattern{ref pattern_uuid,
+                                         ref expected_uuid,
+                                         ref got_uuid,
+                                         ref message} => {
                 fmt.write_fmt(format_args!("The test message matched to an other pattern: \
-                                            expected_uuid={} got_uuid={} test_message='{}'",
+                                            uuid={} expected_uuid={} got_uuid={} \
+                                            test_message='{}'",
+                                           pattern_uuid,
                                            expected_uuid,
                                            got_uuid,
                                            message))
@@ -122,6 +155,12 @@ impl fmt::Display for Error {
                                            expected,
                                            got))
             }
+            Error::UnexpectedMatch{ref pattern_uuid, ref message} => {
+                fmt.write_fmt(format_args!("A negative test message matched its pattern: uuid={} \
+                                            message='{}'",
+                                           pattern_uuid,
+                                           message))
+            }
         }
     }
 }
@@ -144,6 +183,9 @@ impl error::Error for Error {
             Error::UnexpectedTags{..} => {
                 "Unexpected tags found either in the parse result or among the expected ones"
             }
+            Error::UnexpectedMatch{..} => {
+                "A negative test message matched its pattern"
+            }
         }
     }
 }
@@ -1,7 +1,10 @@
+use uuid::Uuid;
+
 use std::collections::BTreeMap;
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 
 use matcher::result::MatchResult;
+use matcher::pattern::line_ending::LineEnding;
 use super::Error;
 
 #[derive(Clone, Debug)]
@@ -9,6 +12,8 @@ pub struct TestMessage {
     message: String,
     values: BTreeMap<String, String>,
     tags: Option<Vec<String>>,
+    negative: bool,
+    expected_uuid: Option<Uuid>,
 }
 
 impl TestMessage {
@@ -20,6 +25,8 @@ impl TestMessage {
             message: message,
             values: values,
             tags: tags,
+            negative: false,
+            expected_uuid: None,
         }
     }
 
@@ -35,7 +42,68 @@ impl TestMessage {
         self.tags.as_ref().map(|x| x.borrow())
     }
 
-    pub fn test_result(&self, result: &MatchResult) -> Result<(), Error> {
+    // A negative test message must not be matched by its pattern. It can
+    // be matched by an other one or by none of them.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // The UUID of the pattern which must claim the message. If it's not set,
+    // the message must be claimed by the pattern it belongs to.
+    pub fn expected_uuid(&self) -> Option<&Uuid> {
+        self.expected_uuid.as_ref()
+    }
+
+    pub fn set_negative(&mut self, negative: bool) {
+        self.negative = negative;
+    }
+
+    pub fn set_expected_uuid(&mut self, expected_uuid: Option<Uuid>) {
+        self.expected_uuid = expected_uuid;
+    }
+
+    // Removes the trailing line feed of a message written as a YAML block
+    // scalar.
+    pub fn trim_block_scalar(&mut self) {
+        if self.message.ends_with('\n') {
+            self.message.pop();
+        }
+    }
+
+    // Converts the line endings of the message and the expected values to
+    // the line ending of the pattern.
+    pub fn convert_line_endings(&mut self, line_ending: LineEnding) {
+        self.message = line_ending.convert(&self.message);
+        for value in self.values.values_mut() {
+            *value = line_ending.convert(value);
+        }
+    }
+
+    // Checks the result of parsing the message with the fully built matcher.
+    // `pattern_uuid` is the UUID of the pattern the message belongs to.
+    pub fn test_result(&self,
+                       pattern_uuid: &Uuid,
+                       result: Option<&MatchResult>)
+                       -> Result<(), Error> {
+        if self.negative {
+            return match result {
+                Some(result) if result.pattern().uuid() == pattern_uuid => {
+                    Err(Error::unexpected_match(pattern_uuid, self))
+                }
+                _ => Ok(()),
+            };
+        }
+
+        let result = try!(result.ok_or(Error::test_message_does_not_match(pattern_uuid, self)));
+        let expected_uuid = self.expected_uuid.as_ref().unwrap_or(pattern_uuid);
+
+        if result.pattern().uuid() != expected_uuid {
+            return Err(Error::matched_to_other_pattern(pattern_uuid,
+                                                       expected_uuid,
+                                                       result.pattern().uuid(),
+                                                       self.message()));
+        }
+
         try!(self.test_tags(result));
         self.test_values(result)
     }
@@ -51,7 +119,7 @@ impl TestMessage {
 
     fn test_value(key: &str,
                   value: &str,
-                  values: &BTreeMap<&str, &str>,
+                  values: &BTreeMap<Cow<str>, Cow<str>>,
                   result: &MatchResult)
                   -> Result<(), Error> {
         if let Some(got_value) = values.get(key) {
@@ -66,16 +134,18 @@ impl TestMessage {
         }
     }
 
-    fn merge_values<'a>(result: &'a MatchResult) -> BTreeMap<&'a str, &'a str> {
-        let mut merged_values: BTreeMap<&str, &str> = BTreeMap::new();
+    fn merge_values<'a>(result: &'a MatchResult) -> BTreeMap<Cow<'a, str>, Cow<'a, str>> {
+        let mut merged_values: BTreeMap<Cow<str>, Cow<str>> =
+            result.additional_values()
+                  .into_iter()
+                  .map(|(key, value)| (Cow::Borrowed(key), value))
+                  .collect();
 
-        if let Some(values) = result.pattern().values() {
-            for (key, value) in values {
-                merged_values.insert(key.borrow(), value.borrow());
-            }
-        }
         for (key, value) in result.values() {
-            merged_values.insert(key, value);
+            merged_values.insert(Cow::Borrowed(*key), Cow::Borrowed(*value));
+        }
+        for (key, value) in result.indexed_values() {
+            merged_values.insert(Cow::Owned(key), value);
         }
 
         merged_values
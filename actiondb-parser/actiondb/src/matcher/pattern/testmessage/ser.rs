@@ -0,0 +1,61 @@
+use super::TestMessage;
+
+use serde;
+
+impl serde::Serialize for TestMessage {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: serde::Serializer
+    {
+        serializer.serialize_struct("TestMessage",
+                                    TestMessageMapVisitor {
+                                        value: self,
+                                        state: 0,
+                                    })
+    }
+}
+
+// The fields are serialized in a canonical order, the ones with a default
+// value are left out.
+struct TestMessageMapVisitor<'a> {
+    value: &'a TestMessage,
+    state: u8,
+}
+
+impl<'a> serde::ser::MapVisitor for TestMessageMapVisitor<'a> {
+    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
+        where S: serde::Serializer
+    {
+        loop {
+            self.state += 1;
+            match self.state {
+                1 => {
+                    return serializer.serialize_struct_elt("message", self.value.message())
+                                     .map(Some);
+                }
+                2 => {
+                    if !self.value.values().is_empty() {
+                        return serializer.serialize_struct_elt("values", self.value.values())
+                                         .map(Some);
+                    }
+                }
+                3 => {
+                    if let Some(tags) = self.value.tags() {
+                        return serializer.serialize_struct_elt("tags", tags).map(Some);
+                    }
+                }
+                4 => {
+                    if self.value.is_negative() {
+                        return serializer.serialize_struct_elt("negative", true).map(Some);
+                    }
+                }
+                5 => {
+                    if let Some(uuid) = self.value.expected_uuid() {
+                        let uuid = uuid.hyphenated().to_string();
+                        return serializer.serialize_struct_elt("expected_uuid", uuid).map(Some);
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::TestMessage;
 use serde_json;
+use uuid::Uuid;
 use std::borrow::Borrow;
 
 #[test]
@@ -80,3 +81,56 @@ fn test_given_json_test_message_when_it_contains_not_just_the_valid_fields_then_
     let _ = result.err().expect("Failed to return an error when a serialized TestMessage \
                                  contains non-valid fields");
 }
+
+#[test]
+fn test_given_json_test_message_when_it_is_negative_then_it_is_deserialized_properly() {
+    let buffer = r#"
+{
+"message": "user root logged in",
+"negative": true
+}
+"#;
+    let msg = serde_json::from_str::<TestMessage>(buffer)
+                  .expect("Failed to deserialize a negative TestMessage from JSON");
+    assert!(msg.is_negative());
+    assert_eq!(msg.expected_uuid(), None);
+}
+
+#[test]
+fn test_given_json_test_message_when_it_is_negative_and_has_expectations_then_we_get_an_error() {
+    let with_values = r#"
+{
+"message": "user root logged in",
+"negative": true,
+"values": {"user": "root"}
+}
+"#;
+    let with_expected_uuid = r#"
+{
+"message": "user root logged in",
+"negative": true,
+"expected_uuid": "2bb2a5e6-7e5d-4b5e-9aa6-91d9fc8a0ee4"
+}
+"#;
+    let _ = serde_json::from_str::<TestMessage>(with_values)
+                .err()
+                .expect("We deserialized a negative TestMessage with values");
+    let _ = serde_json::from_str::<TestMessage>(with_expected_uuid)
+                .err()
+                .expect("We deserialized a negative TestMessage with an expected UUID");
+}
+
+#[test]
+fn test_given_json_test_message_when_it_has_an_expected_uuid_then_it_is_deserialized_properly() {
+    let buffer = r#"
+{
+"message": "user root logged in",
+"expected_uuid": "2bb2a5e6-7e5d-4b5e-9aa6-91d9fc8a0ee4"
+}
+"#;
+    let msg = serde_json::from_str::<TestMessage>(buffer)
+                  .expect("Failed to deserialize a TestMessage with an expected UUID from JSON");
+    let expected_uuid = Uuid::parse_str("2bb2a5e6-7e5d-4b5e-9aa6-91d9fc8a0ee4").unwrap();
+    assert!(!msg.is_negative());
+    assert_eq!(msg.expected_uuid(), Some(&expected_uuid));
+}
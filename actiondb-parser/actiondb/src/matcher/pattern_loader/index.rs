@@ -0,0 +1,86 @@
+use uuid::Uuid;
+
+use matcher::BuildError;
+use matcher::compiled_pattern::TokenType;
+use matcher::pattern::Pattern;
+
+use std::collections::HashMap;
+
+// Remembers the already loaded patterns and the files they came from, so
+// duplicated UUIDs and patterns with identical token sequences can be reported
+// with both file names.
+pub struct PatternIndex {
+    uuids: HashMap<Uuid, String>,
+    token_sequences: HashMap<String, (Uuid, String)>,
+}
+
+impl PatternIndex {
+    pub fn new() -> PatternIndex {
+        PatternIndex {
+            uuids: HashMap::new(),
+            token_sequences: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, file: &str, pattern: &Pattern) -> Result<(), BuildError> {
+        let uuid = pattern.uuid();
+
+        if let Some(first_file) = self.uuids.get(uuid) {
+            return Err(BuildError::DuplicateUuid {
+                uuid: uuid.hyphenated().to_string(),
+                first_file: first_file.clone(),
+                second_file: file.to_owned(),
+            });
+        }
+
+        let key = PatternIndex::token_sequence(pattern);
+        if let Some(&(ref first_uuid, ref first_file)) = self.token_sequences.get(&key) {
+            return Err(BuildError::IdenticalPatterns {
+                first_uuid: first_uuid.hyphenated().to_string(),
+                first_file: first_file.clone(),
+                second_uuid: uuid.hyphenated().to_string(),
+                second_file: file.to_owned(),
+            });
+        }
+
+        self.uuids.insert(uuid.clone(), file.to_owned());
+        self.token_sequences.insert(key, (uuid.clone(), file.to_owned()));
+        Ok(())
+    }
+
+    // The UUID of the loaded pattern which prevents `pattern` from being
+    // inserted: the one with the same UUID or with the same token sequence.
+    pub fn conflicting(&self, pattern: &Pattern) -> Option<Uuid> {
+        if self.uuids.contains_key(pattern.uuid()) {
+            return Some(pattern.uuid().clone());
+        }
+        let key = PatternIndex::token_sequence(pattern);
+        self.token_sequences.get(&key).map(|&(ref uuid, _)| uuid.clone())
+    }
+
+    // Forgets a pattern, so an other one with the same UUID or token sequence
+    // can be inserted.
+    pub fn remove(&mut self, pattern: &Pattern) {
+        self.uuids.remove(pattern.uuid());
+        let key = PatternIndex::token_sequence(pattern);
+        let is_same_pattern = self.token_sequences
+                                  .get(&key)
+                                  .map_or(false, |&(ref uuid, _)| uuid == pattern.uuid());
+        if is_same_pattern {
+            self.token_sequences.remove(&key);
+        }
+    }
+
+    // Parser names don't take part in matching, so two patterns which differ
+    // only in their parser names are identical for the matcher.
+    fn token_sequence(pattern: &Pattern) -> String {
+        let mut key = String::new();
+        for token in pattern.pattern() {
+            match *token {
+                TokenType::Literal(ref literal) => key.push_str(&format!("{:?}", literal)),
+                TokenType::Parser(ref parser) => key.push_str(&format!("{:?}", parser.parser_type())),
+            }
+        }
+        key
+    }
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+// A pattern which is loaded into a matcher by `PatternLoader` with the path
+// of its pattern file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedPattern {
+    uuid: Uuid,
+    name: Option<String>,
+    path: String,
+}
+
+impl LoadedPattern {
+    pub fn new(uuid: Uuid, name: Option<String>, path: String) -> LoadedPattern {
+        LoadedPattern {
+            uuid: uuid,
+            name: name,
+            path: path,
+        }
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
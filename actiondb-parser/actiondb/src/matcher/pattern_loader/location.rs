@@ -0,0 +1,79 @@
+use std::ascii::AsciiExt;
+
+use matcher::{BuildError, SourceLocation};
+
+// Serde doesn't keep track of the positions of the deserialized values, so
+// the location of an error is determined by looking up the offending pattern
+// (or its UUID) in the content of the pattern file.
+pub fn locate(path: &str, content: &str, error: &BuildError) -> SourceLocation {
+    let mut location = SourceLocation::new(path);
+
+    match *error {
+        BuildError::InvalidPattern { ref uuid, ref name, ref pattern, error: ref parse_error } => {
+            let offset = pattern[..parse_error.offset].chars().count();
+            location.set_uuid(Some(uuid.clone()));
+            location.set_name(name.clone());
+            location.set_offset(Some(offset));
+
+            match content.find(pattern.as_str()) {
+                Some(position) if !pattern.contains('\n') => {
+                    let (line, text, column) = describe_position(content, position);
+                    location.set_line(Some(line));
+                    location.set_snippet(Some((text, column + offset)));
+                }
+                _ => {
+                    // the pattern is escaped in the file, so we can only point
+                    // into the pattern string itself
+                    let text = pattern.lines().nth(parse_error.line - 1).unwrap_or("");
+                    location.set_line(line_of_uuid(content, uuid));
+                    location.set_snippet(Some((text.to_owned(), parse_error.column - 1)));
+                }
+            }
+        }
+        BuildError::InvalidValue { ref uuid, ref key, .. } => {
+            location.set_uuid(Some(uuid.clone()));
+            location.set_line(line_of_uuid(content, uuid));
+            // the key is looked up after the UUID, so it's likely the key of
+            // the offending pattern
+            if let Some(uuid_position) = find_uuid(content, uuid) {
+                if let Some(position) = content[uuid_position..].find(key.as_str()) {
+                    let (line, text, column) = describe_position(content,
+                                                                 uuid_position + position);
+                    location.set_line(Some(line));
+                    location.set_snippet(Some((text, column)));
+                }
+            }
+        }
+        BuildError::TestMessage(ref error) => {
+            location.set_uuid(Some(error.pattern_uuid().to_owned()));
+            location.set_line(line_of_uuid(content, error.pattern_uuid()));
+        }
+        _ => {}
+    }
+
+    location
+}
+
+fn line_of_uuid(content: &str, uuid: &str) -> Option<usize> {
+    find_uuid(content, uuid).map(|position| describe_position(content, position).0)
+}
+
+// The UUIDs are formatted in lowercase, but they can be written in any case
+// in the pattern file. The ASCII lowercase conversion keeps the byte offsets.
+fn find_uuid(content: &str, uuid: &str) -> Option<usize> {
+    content.to_ascii_lowercase().find(&uuid.to_ascii_lowercase())
+}
+
+// Returns the 1-based line number, the text of the line and the character
+// column of `position`.
+fn describe_position(content: &str, position: usize) -> (usize, String, usize) {
+    let line_start = content[..position].rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    let line_end = content[position..]
+                       .find('\n')
+                       .map(|pos| position + pos)
+                       .unwrap_or(content.len());
+    let line = content[..position].matches('\n').count() + 1;
+    let text = content[line_start..line_end].trim_right_matches('\r').to_owned();
+    let column = content[line_start..position].chars().count();
+    (line, text, column)
+}
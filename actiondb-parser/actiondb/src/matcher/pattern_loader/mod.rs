@@ -0,0 +1,314 @@
+use super::pattern::file::{PatternFile, RawPatternFile};
+use matcher::trie::parser_factory::TrieParserFactory;
+use matcher::{BuildError, SourceLocation};
+use matcher::MatcherFactory;
+use matcher::FromPatternSource;
+use matcher::Pattern;
+use pdb::Importer;
+use self::index::PatternIndex;
+use self::location::locate;
+
+pub use self::loaded::LoadedPattern;
+pub use self::options::LoadOptions;
+pub use self::policy::{DuplicatePolicy, TestMessagePolicy};
+
+use std::collections::HashMap;
+use std::path::{self, Path};
+use std::fs::{self, File};
+use std::io::{self, Read};
+
+use glob;
+use serde_json;
+use serde_yaml;
+
+mod index;
+mod loaded;
+mod location;
+mod options;
+mod policy;
+
+const GLOB_CHARACTERS: &'static [char] = &['*', '?', '['];
+
+pub struct PatternLoader;
+
+impl PatternLoader {
+    fn read(pattern_file_path: &str) -> Result<String, BuildError>
+    {
+        let mut buffer = String::new();
+        let mut file = try!(File::open(pattern_file_path));
+        let _ = file.read_to_string(&mut buffer);
+        Ok(buffer)
+    }
+
+    // The `pattern_file_path` can be a single file, a directory or a glob
+    // pattern. In the last two cases the patterns of every matching file are
+    // merged into one matcher.
+    pub fn from_file_ignore_errors<F>(pattern_file_path: &str) -> Result<F::Matcher, BuildError>
+        where F: MatcherFactory
+    {
+        PatternLoader::build::<F, _>(&[pattern_file_path], &LoadOptions::new(), |error| {
+            error!("{}", error);
+            Ok(())
+        })
+        .map(|(matcher, _)| matcher)
+    }
+
+    pub fn from_file<F>(pattern_file_path: &str) -> Result<F::Matcher, BuildError>
+        where F: MatcherFactory
+    {
+        PatternLoader::build::<F, _>(&[pattern_file_path], &LoadOptions::new(), Err)
+            .map(|(matcher, _)| matcher)
+    }
+
+    // Merges the patterns of more pattern files, directories or glob patterns
+    // into one matcher. `options` decide what happens with the patterns whose
+    // UUID is already loaded and with the failing test messages.
+    pub fn from_files<F>(pattern_file_paths: &[&str],
+                         options: &LoadOptions)
+                         -> Result<F::Matcher, BuildError>
+        where F: MatcherFactory
+    {
+        PatternLoader::build::<F, _>(pattern_file_paths, options, Err).map(|(matcher, _)| matcher)
+    }
+
+    // Like `from_files()`, but the patterns which are loaded into the matcher
+    // are returned with the path of their pattern file, in the order they
+    // were loaded.
+    pub fn from_files_with_sources<F>(pattern_file_paths: &[&str],
+                                      options: &LoadOptions)
+                                      -> Result<(F::Matcher, Vec<LoadedPattern>), BuildError>
+        where F: MatcherFactory
+    {
+        PatternLoader::build::<F, _>(pattern_file_paths, options, Err)
+    }
+
+    fn build<F, H>(pattern_file_paths: &[&str],
+                   options: &LoadOptions,
+                   mut handle_error: H)
+                   -> Result<(F::Matcher, Vec<LoadedPattern>), BuildError>
+        where F: MatcherFactory,
+              H: FnMut(BuildError) -> Result<(), BuildError>
+    {
+        let mut index = PatternIndex::new();
+        let mut sources = Vec::new();
+        // a pattern is replaced by `None` if a later one wins over it
+        let mut patterns: Vec<Option<(usize, Pattern)>> = Vec::new();
+        let mut positions = HashMap::new();
+
+        for pattern_file_path in pattern_file_paths {
+            for path in try!(PatternLoader::resolve(pattern_file_path)) {
+                let content = try!(PatternLoader::read(&path).map_err(|error| {
+                    BuildError::in_file(SourceLocation::new(&path), error)
+                }));
+                let file = try!(PatternLoader::load_content(&path, &content).map_err(|error| {
+                    BuildError::in_file(locate(&path, &content, &error), error)
+                }));
+                for result in file {
+                    let pattern = match result {
+                        Ok(pattern) => pattern,
+                        Err(error) => {
+                            try!(handle_error(BuildError::in_file(locate(&path, &content, &error),
+                                                                  error)));
+                            continue;
+                        }
+                    };
+                    if let Err(error) = index.insert(&path, &pattern) {
+                        match PatternLoader::duplicate_policy_of(&error, options) {
+                            DuplicatePolicy::Error => {
+                                try!(handle_error(error));
+                                continue;
+                            }
+                            DuplicatePolicy::FirstWins => {
+                                warn!("{}, the first pattern is used", error);
+                                continue;
+                            }
+                            DuplicatePolicy::LastWins => {
+                                warn!("{}, the last pattern is used", error);
+                                // it can conflict with two patterns: one with
+                                // the same UUID and one with the same tokens
+                                while let Some(uuid) = index.conflicting(&pattern) {
+                                    let replaced = positions.get(&uuid)
+                                                            .and_then(|&position| {
+                                                                patterns[position].take()
+                                                            });
+                                    match replaced {
+                                        Some((_, replaced)) => index.remove(&replaced),
+                                        None => break,
+                                    }
+                                }
+                                if let Err(error) = index.insert(&path, &pattern) {
+                                    try!(handle_error(error));
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+                    positions.insert(pattern.uuid().clone(), patterns.len());
+                    patterns.push(Some((sources.len(), pattern)));
+                }
+                sources.push((path, content));
+            }
+        }
+
+        let mut matcher = F::new_matcher();
+        let mut test_messages = Vec::new();
+        let mut loaded = Vec::new();
+        for (source, pattern) in patterns.into_iter().filter_map(|pattern| pattern) {
+            let name = pattern.name().map(|name| name.to_owned());
+            loaded.push(LoadedPattern::new(pattern.uuid().clone(),
+                                           name.clone(),
+                                           sources[source].0.clone()));
+            let (uuid, messages) = F::Matcher::insert_pattern(&mut matcher, pattern);
+            test_messages.push((source, name, uuid, messages));
+        }
+
+        if options.test_message_policy() == TestMessagePolicy::Skip {
+            return Ok((matcher, loaded));
+        }
+        for (source, name, uuid, messages) in test_messages {
+            if let Err(error) = F::Matcher::check_test_messages(&matcher, &messages, &uuid) {
+                let (ref path, ref content) = sources[source];
+                let mut location = locate(path, content, &error);
+                location.set_name(name);
+                let error = BuildError::in_file(location, error);
+                if options.test_message_policy() == TestMessagePolicy::Warn {
+                    warn!("{}", error);
+                } else {
+                    try!(handle_error(error));
+                }
+            }
+        }
+        Ok((matcher, loaded))
+    }
+
+    // Identical patterns are only reported, even if duplicates are errors:
+    // they used to be accepted and the matcher used the last one of them.
+    fn duplicate_policy_of(error: &BuildError, options: &LoadOptions) -> DuplicatePolicy {
+        match (error, options.duplicate_policy()) {
+            (&BuildError::IdenticalPatterns { .. }, DuplicatePolicy::Error) => {
+                DuplicatePolicy::LastWins
+            }
+            (_, policy) => policy,
+        }
+    }
+
+    // Returns the pattern files denoted by `pattern_file_path` in a stable
+    // order. An existing file is never treated as a glob pattern, even if its
+    // name contains glob characters.
+    pub fn resolve(pattern_file_path: &str) -> Result<Vec<String>, BuildError> {
+        let path = Path::new(pattern_file_path);
+        let mut paths = if path.is_dir() {
+            try!(PatternLoader::read_dir(path))
+        } else if !path.exists() && pattern_file_path.contains(GLOB_CHARACTERS) {
+            try!(PatternLoader::glob(pattern_file_path))
+        } else {
+            return Ok(vec![pattern_file_path.to_owned()]);
+        };
+
+        if paths.is_empty() {
+            return Err(BuildError::NoPatternFiles(pattern_file_path.to_owned()));
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn read_dir(dir: &Path) -> Result<Vec<String>, BuildError> {
+        let mut paths = Vec::new();
+        for entry in try!(fs::read_dir(dir)) {
+            let path = try!(entry).path();
+            if path.is_file() && PatternLoader::is_pattern_file(&path) {
+                paths.push(try!(PatternLoader::path_to_string(&path)));
+            }
+        }
+        Ok(paths)
+    }
+
+    fn glob(pattern: &str) -> Result<Vec<String>, BuildError> {
+        let mut paths = Vec::new();
+        for entry in try!(glob::glob(pattern)) {
+            let path = try!(entry.map_err(|error| {
+                io::Error::new(error.error().kind(), error.to_string())
+            }));
+            if path.is_file() {
+                paths.push(try!(PatternLoader::path_to_string(&path)));
+            }
+        }
+        Ok(paths)
+    }
+
+    fn is_pattern_file(path: &Path) -> bool {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(PatternLoader::format_of_extension)
+            .is_some()
+    }
+
+    fn path_to_string(path: &Path) -> Result<String, BuildError> {
+        path.to_str().map(|path| path.to_owned()).ok_or(BuildError::NotUtf8FileName)
+    }
+
+    pub fn load_file(pattern_file_path: &str) -> Result<PatternFile, BuildError> {
+        try!(PatternLoader::file_format(pattern_file_path));
+        let content = try!(PatternLoader::read(pattern_file_path));
+        PatternLoader::load_content(pattern_file_path, &content)
+    }
+
+    // Like `load_file()` but the errors are annotated with their location in
+    // the pattern file.
+    pub fn load_file_with_location(pattern_file_path: &str) -> Result<PatternFile, BuildError> {
+        let content = try!(PatternLoader::read(pattern_file_path).map_err(|error| {
+            BuildError::in_file(SourceLocation::new(pattern_file_path), error)
+        }));
+        PatternLoader::load_content(pattern_file_path, &content).map_err(|error| {
+            BuildError::in_file(locate(pattern_file_path, &content, &error), error)
+        })
+    }
+
+    fn load_content(pattern_file_path: &str, content: &str) -> Result<PatternFile, BuildError> {
+        let raw_file = match try!(PatternLoader::file_format(pattern_file_path)) {
+            FileFormat::Json => try!(serde_json::from_str::<RawPatternFile>(content)),
+            FileFormat::Yaml => try!(serde_yaml::from_str::<RawPatternFile>(content)),
+            FileFormat::Pdb => {
+                let import = try!(Importer::new().import(content));
+                for untranslatable in import.untranslatable() {
+                    warn!("{}", untranslatable);
+                }
+                return Ok(import.into_file());
+            }
+        };
+
+        let mut patterns = Vec::new();
+        for raw_pattern in raw_file.patterns {
+            patterns.push(try!(raw_pattern.compile::<TrieParserFactory>()));
+        }
+        Ok(PatternFile { patterns: patterns })
+    }
+
+    fn file_format(pattern_file_path: &str) -> Result<FileFormat, BuildError> {
+        let path = path::Path::new(pattern_file_path);
+        match path.extension() {
+            Some(extension) => {
+                let extension = try!(extension.to_str().ok_or(BuildError::NotUtf8FileName));
+                PatternLoader::format_of_extension(extension)
+                    .ok_or(BuildError::UnsupportedFileExtension)
+            }
+            None => Err(BuildError::UnsupportedFileExtension),
+        }
+    }
+
+    // The extensions are matched case-insensitively, like `.JSON` or `.Pdb`.
+    fn format_of_extension(extension: &str) -> Option<FileFormat> {
+        match &extension.to_lowercase()[..] {
+            "json" => Some(FileFormat::Json),
+            "yaml" | "yml" => Some(FileFormat::Yaml),
+            "xml" | "pdb" => Some(FileFormat::Pdb),
+            _ => None,
+        }
+    }
+}
+
+enum FileFormat {
+    Json,
+    Yaml,
+    Pdb,
+}
@@ -0,0 +1,31 @@
+use super::policy::{DuplicatePolicy, TestMessagePolicy};
+
+// Controls how `PatternLoader::from_files()` handles the problems which are
+// not necessarily fatal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    duplicate_policy: DuplicatePolicy,
+    test_message_policy: TestMessagePolicy,
+}
+
+impl LoadOptions {
+    pub fn new() -> LoadOptions {
+        LoadOptions::default()
+    }
+
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    pub fn test_message_policy(&self) -> TestMessagePolicy {
+        self.test_message_policy
+    }
+
+    pub fn set_test_message_policy(&mut self, policy: TestMessagePolicy) {
+        self.test_message_policy = policy;
+    }
+}
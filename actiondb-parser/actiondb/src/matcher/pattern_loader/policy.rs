@@ -0,0 +1,54 @@
+// Decides which pattern is used when two patterns have the same UUID or
+// compile to identical token sequences, in the same file or in different
+// ones. Identical patterns are never errors: with `Error` the last one is
+// used and the conflict is logged as a warning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    Error,
+    FirstWins,
+    LastWins,
+}
+
+impl DuplicatePolicy {
+    pub fn from_str(policy: &str) -> Option<DuplicatePolicy> {
+        match policy {
+            "error" => Some(DuplicatePolicy::Error),
+            "first-wins" => Some(DuplicatePolicy::FirstWins),
+            "last-wins" => Some(DuplicatePolicy::LastWins),
+            _ => None,
+        }
+    }
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> DuplicatePolicy {
+        DuplicatePolicy::Error
+    }
+}
+
+// Decides whether a failing test message is an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestMessagePolicy {
+    Strict,
+    // the failures are logged as warnings
+    Warn,
+    // the test messages are not checked at all
+    Skip,
+}
+
+impl TestMessagePolicy {
+    pub fn from_str(policy: &str) -> Option<TestMessagePolicy> {
+        match policy {
+            "strict" => Some(TestMessagePolicy::Strict),
+            "warn" => Some(TestMessagePolicy::Warn),
+            "skip" => Some(TestMessagePolicy::Skip),
+            _ => None,
+        }
+    }
+}
+
+impl Default for TestMessagePolicy {
+    fn default() -> TestMessagePolicy {
+        TestMessagePolicy::Strict
+    }
+}
@@ -1,4 +1,12 @@
 use matcher::pattern::testmessage;
+use grammar::parser::ParseError;
+use matcher::pattern::template;
+use pdb;
+use super::SourceLocation;
+
+use uuid::Uuid;
+
+use glob;
 
 use serde_json;
 use serde_yaml;
@@ -12,10 +20,95 @@ pub enum BuildError {
     Io(io::Error),
     DeserJson(serde_json::Error),
     DeserYaml(serde_yaml::Error),
+    Pdb(pdb::Error),
+    Glob(glob::PatternError),
+    InvalidPattern {
+        uuid: String,
+        name: Option<String>,
+        pattern: String,
+        error: ParseError,
+    },
+    InvalidValue {
+        uuid: String,
+        key: String,
+        error: template::Error,
+    },
+    File {
+        location: SourceLocation,
+        error: Box<BuildError>,
+    },
+    DuplicateUuid {
+        uuid: String,
+        first_file: String,
+        second_file: String,
+    },
+    IdenticalPatterns {
+        first_uuid: String,
+        first_file: String,
+        second_uuid: String,
+        second_file: String,
+    },
+    NoPatternFiles(String),
     UnsupportedFileExtension,
     NotUtf8FileName,
 }
 
+impl BuildError {
+    pub fn invalid_pattern(uuid: &Uuid,
+                           name: Option<String>,
+                           pattern: String,
+                           error: ParseError)
+                           -> BuildError {
+        BuildError::InvalidPattern {
+            uuid: uuid.hyphenated().to_string(),
+            name: name,
+            pattern: pattern,
+            error: error,
+        }
+    }
+
+    pub fn invalid_value(uuid: &Uuid, key: &str, error: template::Error) -> BuildError {
+        BuildError::InvalidValue {
+            uuid: uuid.hyphenated().to_string(),
+            key: key.to_owned(),
+            error: error,
+        }
+    }
+
+    pub fn in_file(location: SourceLocation, error: BuildError) -> BuildError {
+        BuildError::File {
+            location: location,
+            error: Box::new(error),
+        }
+    }
+
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match *self {
+            BuildError::File { ref location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    // Renders the error with the offending line of the pattern file and a
+    // caret pointing to the exact position, if it's known.
+    pub fn report(&self) -> String {
+        match self.location().and_then(|location| location.caret()) {
+            Some(caret) => format!("{}\n{}", self, caret),
+            None => self.to_string(),
+        }
+    }
+
+    // The file in which the error occurred, if it's known.
+    pub fn file(&self) -> Option<&str> {
+        match *self {
+            BuildError::File { ref location, .. } => Some(location.path()),
+            BuildError::DuplicateUuid { ref second_file, .. } |
+            BuildError::IdenticalPatterns { ref second_file, .. } => Some(second_file),
+            _ => None,
+        }
+    }
+}
+
 impl From<testmessage::Error> for BuildError {
     fn from(error: testmessage::Error) -> BuildError {
         BuildError::TestMessage(error)
@@ -40,6 +133,18 @@ impl From<serde_yaml::Error> for BuildError {
     }
 }
 
+impl From<pdb::Error> for BuildError {
+    fn from(error: pdb::Error) -> BuildError {
+        BuildError::Pdb(error)
+    }
+}
+
+impl From<glob::PatternError> for BuildError {
+    fn from(error: glob::PatternError) -> BuildError {
+        BuildError::Glob(error)
+    }
+}
+
 impl fmt::Display for BuildError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
@@ -47,6 +152,48 @@ impl fmt::Display for BuildError {
             BuildError::Io(ref error) => error.fmt(formatter),
             BuildError::DeserJson(ref error) => error.fmt(formatter),
             BuildError::DeserYaml(ref error) => error.fmt(formatter),
+            BuildError::Pdb(ref error) => error.fmt(formatter),
+            BuildError::Glob(ref error) => error.fmt(formatter),
+            BuildError::InvalidPattern { ref uuid, ref name, ref pattern, ref error } => {
+                formatter.write_fmt(format_args!("Invalid field 'pattern': pattern={:?} name={:?} \
+                                                  uuid={} error={}",
+                                                 pattern,
+                                                 name,
+                                                 uuid,
+                                                 error))
+            }
+            BuildError::InvalidValue { ref uuid, ref key, ref error } => {
+                formatter.write_fmt(format_args!("Invalid value in field 'values': uuid={} key={} \
+                                                  error={}",
+                                                 uuid,
+                                                 key,
+                                                 error))
+            }
+            BuildError::File { ref location, ref error } => {
+                formatter.write_fmt(format_args!("{}: {}", location, error))
+            }
+            BuildError::DuplicateUuid { ref uuid, ref first_file, ref second_file } => {
+                formatter.write_fmt(format_args!("Duplicate pattern UUID: uuid={} first_file={} \
+                                                  second_file={}",
+                                                 uuid,
+                                                 first_file,
+                                                 second_file))
+            }
+            BuildError::IdenticalPatterns { ref first_uuid,
+                                            ref first_file,
+                                            ref second_uuid,
+                                            ref second_file } => {
+                formatter.write_fmt(format_args!("Patterns compile to identical token sequences: \
+                                                  first_uuid={} first_file={} second_uuid={} \
+                                                  second_file={}",
+                                                 first_uuid,
+                                                 first_file,
+                                                 second_uuid,
+                                                 second_file))
+            }
+            BuildError::NoPatternFiles(ref path) => {
+                formatter.write_fmt(format_args!("No pattern files were found: path={}", path))
+            }
             BuildError::UnsupportedFileExtension =>
                 formatter.write_fmt(format_args!("The given file extension is not suppoted")),
             BuildError::NotUtf8FileName =>
@@ -62,6 +209,14 @@ impl error::Error for BuildError {
             BuildError::Io(ref error) => error.description(),
             BuildError::DeserJson(ref error) => error.description(),
             BuildError::DeserYaml(ref error) => error.description(),
+            BuildError::Pdb(ref error) => error.description(),
+            BuildError::Glob(ref error) => error.description(),
+            BuildError::InvalidPattern { ref error, .. } => error.description(),
+            BuildError::InvalidValue { ref error, .. } => error.description(),
+            BuildError::File { ref error, .. } => error.description(),
+            BuildError::DuplicateUuid { .. } => "Duplicate pattern UUID",
+            BuildError::IdenticalPatterns { .. } => "Patterns compile to identical token sequences",
+            BuildError::NoPatternFiles(_) => "No pattern files were found",
             BuildError::UnsupportedFileExtension => "The given file extension is not supported",
             BuildError::NotUtf8FileName => "The given filename contains non Utf-8 characters",
         }
@@ -73,7 +228,16 @@ impl error::Error for BuildError {
             BuildError::Io(ref error) => error.cause(),
             BuildError::DeserJson(ref error) => error.cause(),
             BuildError::DeserYaml(ref error) => error.cause(),
-            BuildError::UnsupportedFileExtension | BuildError::NotUtf8FileName => None,
+            BuildError::Pdb(ref error) => error.cause(),
+            BuildError::Glob(ref error) => error.cause(),
+            BuildError::InvalidPattern { ref error, .. } => Some(error),
+            BuildError::InvalidValue { ref error, .. } => Some(error),
+            BuildError::File { ref error, .. } => Some(&**error),
+            BuildError::DuplicateUuid { .. } |
+            BuildError::IdenticalPatterns { .. } |
+            BuildError::NoPatternFiles(_) |
+            BuildError::UnsupportedFileExtension |
+            BuildError::NotUtf8FileName => None,
         }
     }
 }
@@ -0,0 +1,103 @@
+use std::fmt;
+
+// Points to the place in a pattern file where an error occurred. Only the
+// path is mandatory, the rest is filled in when it can be determined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    path: String,
+    line: Option<usize>,
+    uuid: Option<String>,
+    name: Option<String>,
+    offset: Option<usize>,
+    snippet: Option<(String, usize)>,
+}
+
+impl SourceLocation {
+    pub fn new(path: &str) -> SourceLocation {
+        SourceLocation {
+            path: path.to_owned(),
+            line: None,
+            uuid: None,
+            name: None,
+            offset: None,
+            snippet: None,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    // The 1-based line in the pattern file.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_ref().map(|uuid| uuid.as_str())
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    // The character offset in the pattern string.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn set_line(&mut self, line: Option<usize>) {
+        self.line = line;
+    }
+
+    pub fn set_uuid(&mut self, uuid: Option<String>) {
+        self.uuid = uuid;
+    }
+
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    pub fn set_offset(&mut self, offset: Option<usize>) {
+        self.offset = offset;
+    }
+
+    // A line of text and the character column in it which is pointed to by
+    // the caret.
+    pub fn set_snippet(&mut self, snippet: Option<(String, usize)>) {
+        self.snippet = snippet;
+    }
+
+    // Renders the snippet and a caret under the offending character, like:
+    //
+    //     "pattern": "foo %{INT:bar",
+    //                             ^
+    pub fn caret(&self) -> Option<String> {
+        self.snippet.as_ref().map(|&(ref text, column)| {
+            let padding: String = text.chars()
+                                      .take(column)
+                                      .map(|c| if c == '\t' { '\t' } else { ' ' })
+                                      .collect();
+            format!("{}\n{}^", text, padding)
+        })
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        try!(formatter.write_str(&self.path));
+        if let Some(line) = self.line {
+            try!(formatter.write_fmt(format_args!(":{}", line)));
+        }
+        if let Some(ref uuid) = self.uuid {
+            try!(formatter.write_fmt(format_args!(" uuid={}", uuid)));
+        }
+        if let Some(ref name) = self.name {
+            try!(formatter.write_fmt(format_args!(" name={}", name)));
+        }
+        if let Some(offset) = self.offset {
+            try!(formatter.write_fmt(format_args!(" offset={}", offset)));
+        }
+        Ok(())
+    }
+}
@@ -1,41 +1,56 @@
 use uuid::Uuid;
 
 use matcher::pattern::{Pattern, PatternSource};
-use matcher::pattern::testmessage::{self, TestMessage};
+use matcher::pattern::testmessage::TestMessage;
 use matcher::{Matcher, MatcherFactory};
-use matcher::result::MatchResult;
-use matcher::pattern::source::BuildResult;
 pub use self::error::BuildError;
+pub use self::location::SourceLocation;
 
 mod error;
+mod location;
 
+// The test messages are checked only after every pattern is inserted into the
+// matcher, so they are validated against the patterns they will compete with.
 pub trait FromPatternSource {
     fn from_source<F: MatcherFactory>(from: &mut PatternSource) -> Result<F::Matcher, BuildError> {
         let mut matcher = F::new_matcher();
+        let mut test_messages = Vec::new();
         for pattern in from {
-            try!(Self::check_pattern::<F::Matcher>(&mut matcher, pattern));
+            let pattern = try!(pattern);
+            test_messages.push(Self::insert_pattern(&mut matcher, pattern));
+        }
+        for &(ref uuid, ref messages) in &test_messages {
+            try!(Self::check_test_messages(&matcher, messages, uuid));
         }
         Ok(matcher)
     }
 
     fn from_source_ignore_errors<F: MatcherFactory>(from: &mut PatternSource) -> F::Matcher {
         let mut matcher = F::new_matcher();
+        let mut test_messages = Vec::new();
         for pattern in from {
-            let result = Self::check_pattern::<F::Matcher>(&mut matcher, pattern);
-            if let Err(error) = result {
+            match pattern {
+                Ok(pattern) => test_messages.push(Self::insert_pattern(&mut matcher, pattern)),
+                Err(error) => error!("{}", error),
+            }
+        }
+        for &(ref uuid, ref messages) in &test_messages {
+            if let Err(error) = Self::check_test_messages(&matcher, messages, uuid) {
                 error!("{}", error);
             }
         }
         matcher
     }
 
-    fn check_pattern<M: Matcher>(matcher: &mut M, result: BuildResult) -> Result<(), BuildError> {
-        let mut pattern = try!(result);
+    // Inserts the pattern into the matcher and returns its test messages
+    // which can be checked when every pattern is inserted.
+    fn insert_pattern<M: Matcher>(matcher: &mut M,
+                                  mut pattern: Pattern)
+                                  -> (Uuid, Vec<TestMessage>) {
         let uuid = pattern.uuid().to_owned();
         let test_messages = Self::extract_test_messages(&mut pattern);
         matcher.add_pattern(pattern);
-        debug!("validating pattern: {}", uuid.hyphenated().to_string());
-        Self::check_test_messages(matcher, &test_messages, &uuid)
+        (uuid, test_messages)
     }
 
     fn extract_test_messages(pattern: &mut Pattern) -> Vec<TestMessage> {
@@ -51,27 +66,13 @@ pub trait FromPatternSource {
                                        messages: &[TestMessage],
                                        uuid: &Uuid)
                                        -> Result<(), BuildError> {
+        debug!("validating pattern: {}", uuid.hyphenated().to_string());
         for msg in messages {
-            let result = try!(matcher.parse(msg.message())
-                                     .ok_or(testmessage::Error::test_message_does_not_match(uuid,
-                                                                                            msg)));
-            try!(Self::check_test_message(msg, &result, uuid));
+            let result = matcher.parse(msg.message());
+            try!(msg.test_result(uuid, result.as_ref()));
         }
         Ok(())
     }
-
-    fn check_test_message(message: &TestMessage,
-                          result: &MatchResult,
-                          expected_uuid: &Uuid)
-                          -> Result<(), testmessage::Error> {
-        if result.pattern().uuid() == expected_uuid {
-            message.test_result(result)
-        } else {
-            Err(testmessage::Error::matched_to_other_pattern(expected_uuid,
-                result.pattern().uuid(),
-                message.message()))
-        }
-    }
 }
 
 impl<T> FromPatternSource for T where T: Matcher {
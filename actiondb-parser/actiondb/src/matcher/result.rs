@@ -1,12 +1,15 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 
 use parsers::ParseResult;
 use matcher::Pattern;
+use matcher::pattern::metadata::Metadata;
 
 #[derive(Debug)]
 pub struct MatchResult<'a, 'b> {
     pattern: &'a Pattern,
     values: BTreeMap<&'a str, &'b str>,
+    repeated_values: BTreeMap<&'a str, Vec<&'b str>>,
 }
 
 impl <'a, 'b> MatchResult<'a, 'b> {
@@ -14,12 +17,21 @@ impl <'a, 'b> MatchResult<'a, 'b> {
         MatchResult {
             pattern: pattern,
             values: BTreeMap::new(),
+            repeated_values: BTreeMap::new(),
         }
     }
 
     pub fn insert(&mut self, result: ParseResult<'a, 'b>) {
         if let Some(name) = result.parser().name() {
-            self.values.insert(name, result.value());
+            let value = result.value();
+            match result.into_items() {
+                Some(items) => {
+                    self.repeated_values.insert(name, items);
+                }
+                None => {
+                    self.values.insert(name, value);
+                }
+            }
         }
     }
 
@@ -27,17 +39,56 @@ impl <'a, 'b> MatchResult<'a, 'b> {
         self.pattern
     }
 
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.pattern.metadata()
+    }
+
     pub fn values(&self) -> &BTreeMap<&'a str, &'b str> {
         &self.values
     }
+
+    // The items of the repeated parsers as `name.0`, `name.1`, ... and their
+    // number as `name.count`.
+    pub fn indexed_values(&self) -> BTreeMap<String, Cow<'b, str>> {
+        let mut values = BTreeMap::new();
+        for (name, items) in &self.repeated_values {
+            for (index, item) in items.iter().enumerate() {
+                values.insert(format!("{}.{}", name, index), Cow::Borrowed(*item));
+            }
+            values.insert(format!("{}.count", name), Cow::Owned(items.len().to_string()));
+        }
+        values
+    }
+
+    // The values of the pattern with their templates evaluated against the
+    // parsed and the indexed values.
+    pub fn additional_values(&self) -> BTreeMap<&'a str, Cow<'a, str>> {
+        let mut values = BTreeMap::new();
+        if let Some(templates) = self.pattern.templates() {
+            let indexed_values = self.indexed_values();
+            let mut context: BTreeMap<&str, &str> = self.values
+                                                        .iter()
+                                                        .map(|(key, value)| (*key, *value))
+                                                        .collect();
+            for (key, value) in &indexed_values {
+                context.insert(key.as_str(), value.as_ref());
+            }
+            for (key, template) in templates {
+                values.insert(key.as_str(), template.format(&context));
+            }
+        }
+        values
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use parsers::{IntParser, ParseResult};
+    use parsers::{IntParser, ParseResult, Parser, RepeatParser};
     use super::MatchResult;
     use matcher::Pattern;
+    use matcher::compiled_pattern::TokenType;
     use std::collections::BTreeMap;
+    use uuid::Uuid;
 
     #[test]
     fn test_given_match_result_when_a_parse_result_is_inserted_then_we_use_only_the_ones_where_the_parser_has_a_name
@@ -56,4 +107,81 @@ mod test {
         match_result.insert(ParseResult::new(&parser_with_name, "c"));
         assert_eq!(match_result.values(), &expected_values);
     }
+
+    #[test]
+    fn test_given_match_result_when_the_pattern_has_templated_values_then_they_are_evaluated() {
+        let parser = IntParser::with_name("pid".to_owned());
+        let mut values = BTreeMap::new();
+        values.insert("process".to_owned(), "sshd[${pid}]".to_owned());
+        values.insert("static".to_owned(), "value".to_owned());
+        let compiled_pattern = vec![TokenType::Parser(parser.boxed_clone())];
+        let pattern = Pattern::new(None,
+                                   Uuid::new_v4(),
+                                   compiled_pattern,
+                                   None,
+                                   Some(values),
+                                   None)
+                          .expect("Failed to create a pattern with valid templates");
+        let mut match_result = MatchResult::new(&pattern);
+        match_result.insert(ParseResult::new(&parser, "42"));
+        let additional_values = match_result.additional_values();
+        assert_eq!(additional_values.get("process").map(|value| value.as_ref()),
+                   Some("sshd[42]"));
+        assert_eq!(additional_values.get("static").map(|value| value.as_ref()),
+                   Some("value"));
+    }
+
+    #[test]
+    fn test_given_match_result_when_a_template_references_repeated_items_then_they_are_evaluated
+        () {
+        let parser = RepeatParser::with_name("ports", Box::new(IntParser::new()), ",");
+        let mut values = BTreeMap::new();
+        values.insert("summary".to_owned(),
+                      "${ports.count} ports, first: ${ports.0}, third: ${ports.2}".to_owned());
+        let compiled_pattern = vec![TokenType::Parser(parser.boxed_clone())];
+        let pattern = Pattern::new(None,
+                                   Uuid::new_v4(),
+                                   compiled_pattern,
+                                   None,
+                                   Some(values),
+                                   None)
+                          .expect("Failed to create a pattern referencing repeated items");
+        let mut match_result = MatchResult::new(&pattern);
+        match_result.insert(ParseResult::with_items(&parser, "22,80", vec!["22", "80"]));
+        let additional_values = match_result.additional_values();
+        assert_eq!(additional_values.get("summary").map(|value| value.as_ref()),
+                   Some("2 ports, first: 22, third: "));
+    }
+
+    // Before templates were introduced, the values were copied verbatim, so
+    // a `$$` in an existing value now becomes a single `$`.
+    #[test]
+    fn test_given_match_result_when_a_static_value_contains_double_dollars_then_one_is_kept() {
+        let mut values = BTreeMap::new();
+        values.insert("price".to_owned(), "$$100 or $5".to_owned());
+        let pattern = Pattern::new(None, Uuid::new_v4(), Vec::new(), None, Some(values), None)
+                          .expect("Failed to create a pattern with a static value");
+        let match_result = MatchResult::new(&pattern);
+        let additional_values = match_result.additional_values();
+        assert_eq!(additional_values.get("price").map(|value| value.as_ref()),
+                   Some("$100 or $5"));
+    }
+
+    #[test]
+    fn test_given_match_result_when_a_repeated_parse_result_is_inserted_then_its_items_are_indexed
+        () {
+        let parser = RepeatParser::with_name("ports", Box::new(IntParser::new()), ",");
+        let pattern = Pattern::with_random_uuid();
+        let mut match_result = MatchResult::new(&pattern);
+        match_result.insert(ParseResult::with_items(&parser, "22,80", vec!["22", "80"]));
+        assert_eq!(match_result.values().is_empty(), true);
+        let indexed_values = match_result.indexed_values();
+        let indexed_values: Vec<(&str, &str)> = indexed_values.iter()
+                                                              .map(|(key, value)| {
+                                                                  (key.as_str(), value.as_ref())
+                                                              })
+                                                              .collect();
+        assert_eq!(indexed_values,
+                   vec![("ports.0", "22"), ("ports.1", "80"), ("ports.count", "2")]);
+    }
 }
@@ -0,0 +1,34 @@
+use matcher::dump::{Dump, Node};
+use super::impls::SuffixTable;
+use super::interface::{Entry, LiteralEntry, ParserEntry};
+
+impl Dump for SuffixTable {
+    fn dump(&self) -> Node {
+        let mut root = Node::root();
+        dump_children(self, &mut root);
+        root
+    }
+}
+
+fn dump_children(table: &SuffixTable, node: &mut Node) {
+    for entry in table.literal_entries() {
+        let mut child = Node::literal(entry.literal());
+        if let Some(pattern) = entry.pattern() {
+            child.add_pattern(pattern);
+        }
+        if let Some(table) = entry.child() {
+            dump_children(table, &mut child);
+        }
+        node.add_child(child);
+    }
+    for entry in table.parser_entries() {
+        let mut child = Node::parser(&**entry.parser());
+        if let Some(pattern) = entry.pattern() {
+            child.add_pattern(pattern);
+        }
+        if let Some(table) = entry.child() {
+            dump_children(table, &mut child);
+        }
+        node.add_child(child);
+    }
+}
@@ -82,6 +82,14 @@ impl SuffixTable {
         }
     }
 
+    pub fn literal_entries(&self) -> &[LiteralE] {
+        &self.literal_entries
+    }
+
+    pub fn parser_entries(&self) -> &[ParserE] {
+        &self.parser_entries
+    }
+
     pub fn longest_common_prefix<'a, 'b>(&'a self, value: &'b str) -> Option<&'a LiteralE> {
         let result = self.literal_entries.binary_search_by(|probe| {
             let s: &str = probe.literal().borrow();
@@ -7,6 +7,7 @@ use self::interface::SuffixArray;
 
 mod interface;
 mod impls;
+mod dump;
 #[cfg(test)]
 mod test;
 
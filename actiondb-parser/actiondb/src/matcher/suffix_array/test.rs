@@ -175,3 +175,10 @@ fn test_given_parser_when_it_receives_empty_message_and_there_is_no_matching_pat
 
     assert_eq!(true, root.parse("").is_none());
 }
+
+fn assert_send_and_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_suffix_table_can_be_shared_between_threads() {
+    assert_send_and_sync::<SuffixTable>();
+}
@@ -1,9 +1,10 @@
 use matcher::Matcher;
 use matcher::MatcherFactory;
+use matcher::dump::Dump;
 use parsers::ParserFactory;
 
 pub trait MatcherSuite {
-    type Matcher: Matcher;
+    type Matcher: Matcher + Dump + Send + Sync + 'static;
     type ParserFactory: ParserFactory;
     type MatcherFactory: MatcherFactory<Matcher=Self::Matcher>;
 }
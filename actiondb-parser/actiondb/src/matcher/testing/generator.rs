@@ -0,0 +1,123 @@
+use uuid::Uuid;
+
+use matcher::Matcher;
+use matcher::pattern::file::PatternFile;
+use matcher::pattern::testmessage::TestMessage;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const DEFAULT_MAX_SAMPLES: usize = 3;
+
+// The sample messages of one pattern and the values parsed from them.
+struct Samples {
+    test_messages: Vec<TestMessage>,
+    seen_values: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Samples {
+    fn new() -> Samples {
+        Samples {
+            test_messages: Vec::new(),
+            seen_values: BTreeMap::new(),
+        }
+    }
+}
+
+// Collects sample messages of the patterns to be used as test messages. A
+// message is kept only if it gives a new value for at least one of the
+// parsed keys, so the samples of a pattern are diverse.
+pub struct TestGenerator {
+    max_samples: usize,
+    samples: HashMap<Uuid, Samples>,
+}
+
+impl TestGenerator {
+    pub fn new() -> TestGenerator {
+        TestGenerator {
+            max_samples: DEFAULT_MAX_SAMPLES,
+            samples: HashMap::new(),
+        }
+    }
+
+    // The maximum number of test messages generated for a pattern.
+    pub fn set_max_samples(&mut self, max_samples: usize) {
+        self.max_samples = max_samples;
+    }
+
+    pub fn add_message<M: Matcher + ?Sized>(&mut self, matcher: &M, message: &str) {
+        let result = match matcher.parse(message) {
+            Some(result) => result,
+            None => return,
+        };
+        let max_samples = self.max_samples;
+        let samples = self.samples
+                          .entry(result.pattern().uuid().clone())
+                          .or_insert_with(Samples::new);
+        if samples.test_messages.len() >= max_samples {
+            return;
+        }
+        let mut values: BTreeMap<String, String> = result.values()
+                                                        .iter()
+                                                        .map(|(key, value)| {
+                                                            (key.to_string(), value.to_string())
+                                                        })
+                                                        .collect();
+        for (key, value) in result.indexed_values() {
+            values.insert(key, value.into_owned());
+        }
+        let has_new_value = values.iter().any(|(key, value)| {
+            !samples.seen_values.get(key).map_or(false, |seen| seen.contains(value))
+        });
+        if !samples.test_messages.is_empty() && !has_new_value {
+            return;
+        }
+
+        for (key, value) in &values {
+            samples.seen_values
+                   .entry(key.clone())
+                   .or_insert_with(BTreeSet::new)
+                   .insert(value.clone());
+        }
+        let tags = result.pattern().tags().map(|tags| tags.to_vec());
+        samples.test_messages.push(TestMessage::new(message.to_owned(), values, tags));
+    }
+
+    pub fn test_messages(&self, uuid: &Uuid) -> Option<&[TestMessage]> {
+        self.samples.get(uuid).map(|samples| &samples.test_messages[..])
+    }
+
+    // Adds the generated test messages to the patterns of the file. The
+    // patterns which already have test messages are skipped unless `force`
+    // is set, in which case their test messages are kept and only the new
+    // messages are added. Returns the number of updated patterns.
+    pub fn apply(&self, file: &mut PatternFile, force: bool) -> usize {
+        let mut updated = 0;
+        for pattern in &mut file.patterns {
+            let generated = match self.test_messages(pattern.uuid()) {
+                Some(generated) => generated,
+                None => continue,
+            };
+            let mut test_messages = pattern.test_messages()
+                                           .map(|test_messages| test_messages.to_vec())
+                                           .unwrap_or_else(Vec::new);
+            if !test_messages.is_empty() && !force {
+                continue;
+            }
+            let count = test_messages.len();
+            for test_message in generated {
+                let is_new = test_messages.iter()
+                                          .all(|existing| {
+                                              existing.message() != test_message.message()
+                                          });
+                if is_new {
+                    test_messages.push(test_message.clone());
+                }
+            }
+            if test_messages.len() > count {
+                pattern.set_test_messages(Some(test_messages));
+                updated += 1;
+            }
+        }
+        updated
+    }
+}
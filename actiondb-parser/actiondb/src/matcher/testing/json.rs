@@ -0,0 +1,64 @@
+use serde_json::{self, Value};
+use serde_json::builder::{ArrayBuilder, ObjectBuilder};
+
+use matcher::BuildError;
+use super::{PatternReport, TestCase, TestReport};
+
+impl TestReport {
+    // Renders the report as a JSON document with the totals, the load errors
+    // and the result of every test message grouped by their patterns.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let errors = self.errors()
+                         .iter()
+                         .fold(ArrayBuilder::new(),
+                               |builder, error| builder.push(error_to_json(error)))
+                         .build();
+        let patterns = self.patterns()
+                           .iter()
+                           .fold(ArrayBuilder::new(),
+                                 |builder, pattern| builder.push(pattern_to_json(pattern)))
+                           .build();
+        let report = ObjectBuilder::new()
+                         .insert("success", self.is_success())
+                         .insert("passed", self.passed())
+                         .insert("failed", self.failed())
+                         .insert("errors", errors)
+                         .insert("patterns", patterns)
+                         .build();
+        serde_json::to_string_pretty(&report)
+    }
+}
+
+fn error_to_json(error: &BuildError) -> Value {
+    ObjectBuilder::new()
+        .insert("file", error.file())
+        .insert("error", error.to_string())
+        .build()
+}
+
+fn pattern_to_json(pattern: &PatternReport) -> Value {
+    let test_messages = pattern.test_cases()
+                               .iter()
+                               .fold(ArrayBuilder::new(), |builder, test_case| {
+                                   builder.push(test_case_to_json(test_case))
+                               })
+                               .build();
+    ObjectBuilder::new()
+        .insert("file", pattern.file())
+        .insert("uuid", pattern.uuid().hyphenated().to_string())
+        .insert("name", pattern.name())
+        .insert("success", pattern.is_success())
+        .insert("passed", pattern.passed())
+        .insert("failed", pattern.failed())
+        .insert("test_messages", test_messages)
+        .build()
+}
+
+fn test_case_to_json(test_case: &TestCase) -> Value {
+    ObjectBuilder::new()
+        .insert("message", test_case.test_message().message())
+        .insert("negative", test_case.test_message().is_negative())
+        .insert("success", test_case.is_success())
+        .insert("error", test_case.error().map(|error| error.to_string()))
+        .build()
+}
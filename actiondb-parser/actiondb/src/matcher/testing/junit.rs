@@ -0,0 +1,100 @@
+use xml::writer::{EmitterConfig, EventWriter, Error, XmlEvent};
+
+use matcher::BuildError;
+use super::{PatternReport, TestCase, TestReport};
+
+use std::error;
+use std::io::Write;
+
+const TESTSUITES_NAME: &'static str = "actiondb";
+const LOAD_ERROR_TESTCASE: &'static str = "load";
+
+impl TestReport {
+    // Renders the report as a JUnit XML document. Every pattern is a test
+    // suite and every test message is a test case in it. A pattern file which
+    // cannot be loaded is a test suite with one erroneous test case.
+    pub fn to_junit(&self) -> Result<String, Error> {
+        let mut buffer = Vec::new();
+        {
+            let mut writer = EmitterConfig::new().perform_indent(true).create_writer(&mut buffer);
+            try!(write_report(&mut writer, self));
+        }
+        Ok(String::from_utf8(buffer).expect("The XML emitter produced invalid UTF-8"))
+    }
+}
+
+fn write_report<W: Write>(writer: &mut EventWriter<W>, report: &TestReport) -> Result<(), Error> {
+    let tests = (report.passed() + report.failed() + report.errors().len()).to_string();
+    let failures = report.failed().to_string();
+    let errors = report.errors().len().to_string();
+
+    try!(writer.write(XmlEvent::start_element("testsuites")
+                          .attr("name", TESTSUITES_NAME)
+                          .attr("tests", &tests)
+                          .attr("failures", &failures)
+                          .attr("errors", &errors)));
+    for error in report.errors() {
+        try!(write_error(writer, error));
+    }
+    for pattern in report.patterns() {
+        try!(write_pattern(writer, pattern));
+    }
+    writer.write(XmlEvent::end_element())
+}
+
+fn write_error<W: Write>(writer: &mut EventWriter<W>, error: &BuildError) -> Result<(), Error> {
+    let file = error.file().unwrap_or("");
+    let message = error.to_string();
+
+    try!(writer.write(XmlEvent::start_element("testsuite")
+                          .attr("name", file)
+                          .attr("tests", "1")
+                          .attr("failures", "0")
+                          .attr("errors", "1")));
+    try!(writer.write(XmlEvent::start_element("testcase")
+                          .attr("name", LOAD_ERROR_TESTCASE)
+                          .attr("classname", file)));
+    try!(writer.write(XmlEvent::start_element("error")
+                          .attr("message", &message)
+                          .attr("type", error::Error::description(error))));
+    try!(writer.write(XmlEvent::end_element()));
+    try!(writer.write(XmlEvent::end_element()));
+    writer.write(XmlEvent::end_element())
+}
+
+fn write_pattern<W: Write>(writer: &mut EventWriter<W>,
+                           pattern: &PatternReport)
+                           -> Result<(), Error> {
+    let uuid = pattern.uuid().hyphenated().to_string();
+    let tests = pattern.test_cases().len().to_string();
+    let failures = pattern.failed().to_string();
+
+    try!(writer.write(XmlEvent::start_element("testsuite")
+                          .attr("name", pattern.name().unwrap_or(&uuid))
+                          .attr("id", &uuid)
+                          .attr("package", pattern.file())
+                          .attr("tests", &tests)
+                          .attr("failures", &failures)
+                          .attr("errors", "0")));
+    for test_case in pattern.test_cases() {
+        try!(write_test_case(writer, &uuid, test_case));
+    }
+    writer.write(XmlEvent::end_element())
+}
+
+fn write_test_case<W: Write>(writer: &mut EventWriter<W>,
+                             uuid: &str,
+                             test_case: &TestCase)
+                             -> Result<(), Error> {
+    try!(writer.write(XmlEvent::start_element("testcase")
+                          .attr("name", test_case.test_message().message())
+                          .attr("classname", uuid)));
+    if let Some(error) = test_case.error() {
+        let message = error.to_string();
+        try!(writer.write(XmlEvent::start_element("failure")
+                              .attr("message", &message)
+                              .attr("type", error::Error::description(error))));
+        try!(writer.write(XmlEvent::end_element()));
+    }
+    writer.write(XmlEvent::end_element())
+}
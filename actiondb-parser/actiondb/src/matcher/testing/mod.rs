@@ -0,0 +1,11 @@
+pub use self::report::{TestReport, PatternReport, TestCase};
+pub use self::runner::TestRunner;
+pub use self::generator::TestGenerator;
+
+#[cfg(test)]
+mod test;
+mod generator;
+mod json;
+mod junit;
+mod report;
+mod runner;
@@ -0,0 +1,125 @@
+use uuid::Uuid;
+
+use matcher::BuildError;
+use matcher::pattern::testmessage::{self, TestMessage};
+
+// The outcome of checking every test message of some pattern files.
+#[derive(Debug, Default)]
+pub struct TestReport {
+    patterns: Vec<PatternReport>,
+    errors: Vec<BuildError>,
+}
+
+impl TestReport {
+    pub fn new() -> TestReport {
+        TestReport::default()
+    }
+
+    pub fn patterns(&self) -> &[PatternReport] {
+        &self.patterns
+    }
+
+    // The errors which prevented a pattern file from being loaded.
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    pub fn add_pattern(&mut self, pattern: PatternReport) {
+        self.patterns.push(pattern);
+    }
+
+    pub fn add_error(&mut self, error: BuildError) {
+        self.errors.push(error);
+    }
+
+    pub fn passed(&self) -> usize {
+        self.patterns.iter().map(|pattern| pattern.passed()).fold(0, |sum, x| sum + x)
+    }
+
+    pub fn failed(&self) -> usize {
+        self.patterns.iter().map(|pattern| pattern.failed()).fold(0, |sum, x| sum + x)
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty() && self.failed() == 0
+    }
+}
+
+#[derive(Debug)]
+pub struct PatternReport {
+    file: String,
+    uuid: Uuid,
+    name: Option<String>,
+    test_cases: Vec<TestCase>,
+}
+
+impl PatternReport {
+    pub fn new(file: String, uuid: Uuid, name: Option<String>) -> PatternReport {
+        PatternReport {
+            file: file,
+            uuid: uuid,
+            name: name,
+            test_cases: Vec::new(),
+        }
+    }
+
+    // The pattern file which contains the pattern.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn test_cases(&self) -> &[TestCase] {
+        &self.test_cases
+    }
+
+    pub fn add_test_case(&mut self, test_case: TestCase) {
+        self.test_cases.push(test_case);
+    }
+
+    pub fn passed(&self) -> usize {
+        self.test_cases.iter().filter(|test_case| test_case.is_success()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.test_cases.len() - self.passed()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+#[derive(Debug)]
+pub struct TestCase {
+    test_message: TestMessage,
+    error: Option<testmessage::Error>,
+}
+
+impl TestCase {
+    pub fn new(test_message: TestMessage, error: Option<testmessage::Error>) -> TestCase {
+        TestCase {
+            test_message: test_message,
+            error: error,
+        }
+    }
+
+    pub fn test_message(&self) -> &TestMessage {
+        &self.test_message
+    }
+
+    pub fn error(&self) -> Option<&testmessage::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
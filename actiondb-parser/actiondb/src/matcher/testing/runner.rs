@@ -0,0 +1,65 @@
+use matcher::{FromPatternSource, Matcher, MatcherFactory, PatternLoader};
+use preprocess::Preprocessor;
+use super::{PatternReport, TestCase, TestReport};
+
+pub struct TestRunner;
+
+impl TestRunner {
+    // Every pattern is inserted into one matcher before the test messages are
+    // checked, so they compete with each other just like when the matcher is
+    // built by `PatternLoader`. The run doesn't stop at the first failure,
+    // every test message and every load error is recorded in the report.
+    pub fn run<F: MatcherFactory>(pattern_file_paths: &[&str]) -> TestReport {
+        TestRunner::run_with_preprocessor::<F>(pattern_file_paths, &Preprocessor::new())
+    }
+
+    // Like `run()`, but the test messages are preprocessed before they are
+    // parsed, like the messages of a parser with the same preprocessor.
+    pub fn run_with_preprocessor<F: MatcherFactory>(pattern_file_paths: &[&str],
+                                                    preprocessor: &Preprocessor)
+                                                    -> TestReport {
+        let mut matcher = F::new_matcher();
+        let mut report = TestReport::new();
+        let mut test_messages = Vec::new();
+
+        for pattern_file_path in pattern_file_paths {
+            let paths = match PatternLoader::resolve(pattern_file_path) {
+                Ok(paths) => paths,
+                Err(error) => {
+                    report.add_error(error);
+                    continue;
+                }
+            };
+            for path in paths {
+                let file = match PatternLoader::load_file_with_location(&path) {
+                    Ok(file) => file,
+                    Err(error) => {
+                        report.add_error(error);
+                        continue;
+                    }
+                };
+                for pattern in file.patterns {
+                    let name = pattern.name().map(|name| name.to_owned());
+                    let (uuid, mut messages) = F::Matcher::insert_pattern(&mut matcher, pattern);
+                    // the test messages are popped from the pattern, so they
+                    // are in reverse order
+                    messages.reverse();
+                    test_messages.push((PatternReport::new(path.clone(), uuid, name), messages));
+                }
+            }
+        }
+
+        for (mut pattern_report, messages) in test_messages {
+            for message in messages {
+                let error = {
+                    let input = preprocessor.process(message.message());
+                    let result = matcher.parse(&input);
+                    message.test_result(pattern_report.uuid(), result.as_ref()).err()
+                };
+                pattern_report.add_test_case(TestCase::new(message, error));
+            }
+            report.add_pattern(pattern_report);
+        }
+        report
+    }
+}
@@ -0,0 +1,148 @@
+use uuid::Uuid;
+
+use grammar::parser::pattern_with_factory;
+use matcher::{BuildError, Matcher, MatcherFactory, Pattern};
+use matcher::pattern::file::PatternFile;
+use matcher::pattern::testmessage::{self, TestMessage};
+use matcher::suffix_array::SuffixArrayMatcherFactory;
+use matcher::trie::parser_factory::TrieParserFactory;
+use super::{PatternReport, TestCase, TestGenerator, TestReport};
+
+use std::collections::BTreeMap;
+
+const UUID: &'static str = "6d2cba0c-e241-464a-89c3-8035cac8f73e";
+
+fn test_message(message: &str) -> TestMessage {
+    TestMessage::new(message.to_owned(), BTreeMap::new(), None)
+}
+
+fn report_with_a_failure() -> TestReport {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+    let mut pattern = PatternReport::new("patterns.yaml".to_owned(),
+                                         uuid,
+                                         Some("USER_LOGIN".to_owned()));
+    let passing = test_message("user bob logged in");
+    let failing = test_message("user alice logged out");
+    let error = testmessage::Error::test_message_does_not_match(&uuid, &failing);
+    pattern.add_test_case(TestCase::new(passing, None));
+    pattern.add_test_case(TestCase::new(failing, Some(error)));
+
+    let mut report = TestReport::new();
+    report.add_pattern(pattern);
+    report
+}
+
+#[test]
+fn test_given_test_report_when_every_test_message_passes_then_it_is_successful() {
+    let mut pattern = PatternReport::new("patterns.yaml".to_owned(), Uuid::new_v4(), None);
+    pattern.add_test_case(TestCase::new(test_message("user bob logged in"), None));
+    let mut report = TestReport::new();
+    report.add_pattern(pattern);
+
+    assert_eq!(report.passed(), 1);
+    assert_eq!(report.failed(), 0);
+    assert!(report.is_success());
+}
+
+#[test]
+fn test_given_test_report_when_a_test_message_fails_then_it_is_not_successful() {
+    let report = report_with_a_failure();
+
+    assert_eq!(report.passed(), 1);
+    assert_eq!(report.failed(), 1);
+    assert!(!report.patterns()[0].is_success());
+    assert!(!report.is_success());
+}
+
+#[test]
+fn test_given_test_report_when_a_pattern_file_cannot_be_loaded_then_it_is_not_successful() {
+    let mut report = TestReport::new();
+    report.add_error(BuildError::UnsupportedFileExtension);
+
+    assert_eq!(report.failed(), 0);
+    assert!(!report.is_success());
+}
+
+#[test]
+fn test_given_test_report_when_it_is_rendered_as_junit_then_failures_are_reported() {
+    let junit = report_with_a_failure().to_junit().unwrap();
+
+    assert!(junit.contains("<testsuites name=\"actiondb\" tests=\"2\" failures=\"1\" \
+                            errors=\"0\">"));
+    assert!(junit.contains(&format!("<testsuite name=\"USER_LOGIN\" id=\"{}\"", UUID)));
+    assert!(junit.contains("<testcase name=\"user bob logged in\""));
+    assert!(junit.contains("<failure message=\"A test message did not match its pattern"));
+}
+
+#[test]
+fn test_given_test_report_when_it_is_rendered_as_json_then_failures_are_reported() {
+    let json = report_with_a_failure().to_json().unwrap();
+
+    assert!(json.contains("\"success\": false"));
+    assert!(json.contains("\"failed\": 1"));
+    assert!(json.contains(&format!("\"uuid\": \"{}\"", UUID)));
+    assert!(json.contains("\"message\": \"user alice logged out\""));
+}
+
+fn pattern_file_with_login_pattern() -> PatternFile {
+    let pattern = Pattern::new(Some("USER_LOGIN".to_owned()),
+                               Uuid::parse_str(UUID).unwrap(),
+                               pattern_with_factory::<TrieParserFactory>("user %{GREEDY:user} \
+                                                                          logged in")
+                                   .unwrap(),
+                               None,
+                               None,
+                               Some(vec!["login".to_owned()]))
+                      .unwrap();
+    PatternFile { patterns: vec![pattern] }
+}
+
+fn generate_test_messages(messages: &[&str]) -> TestGenerator {
+    let mut matcher = SuffixArrayMatcherFactory::new_matcher();
+    for pattern in pattern_file_with_login_pattern().patterns {
+        matcher.add_pattern(pattern);
+    }
+    let mut generator = TestGenerator::new();
+    generator.set_max_samples(2);
+    for message in messages {
+        generator.add_message(&matcher, message);
+    }
+    generator
+}
+
+#[test]
+fn test_given_test_generator_when_messages_have_the_same_values_then_only_one_is_kept() {
+    let generator = generate_test_messages(&["user bob logged in",
+                                             "user bob logged in",
+                                             "user alice logged in",
+                                             "user eve logged in",
+                                             "user bob logged out"]);
+
+    let test_messages = generator.test_messages(&Uuid::parse_str(UUID).unwrap()).unwrap();
+
+    let messages: Vec<&str> = test_messages.iter().map(|message| message.message()).collect();
+    assert_eq!(messages, vec!["user bob logged in", "user alice logged in"]);
+    assert_eq!(test_messages[1].values().get("user").map(|user| user.as_str()),
+               Some("alice"));
+    assert_eq!(test_messages[1].tags(), Some(&["login".to_owned()][..]));
+}
+
+#[test]
+fn test_given_test_generator_when_a_pattern_has_test_messages_then_it_is_updated_only_if_forced
+    () {
+    let generator = generate_test_messages(&["user bob logged in", "user alice logged in"]);
+    let mut file = pattern_file_with_login_pattern();
+    file.patterns[0].set_test_messages(Some(vec![test_message("user bob logged in")]));
+
+    assert_eq!(generator.apply(&mut file, false), 0);
+    assert_eq!(file.patterns[0].test_messages().unwrap().len(), 1);
+
+    assert_eq!(generator.apply(&mut file, true), 1);
+    let messages: Vec<&str> = file.patterns[0]
+                                  .test_messages()
+                                  .unwrap()
+                                  .iter()
+                                  .map(|message| message.message())
+                                  .collect();
+    assert_eq!(messages, vec!["user bob logged in", "user alice logged in"]);
+}
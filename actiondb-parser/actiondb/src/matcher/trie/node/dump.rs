@@ -0,0 +1,34 @@
+use matcher::dump::{Dump, Node};
+use super::SuffixTree;
+use super::interface::Entry;
+
+impl Dump for SuffixTree {
+    fn dump(&self) -> Node {
+        let mut root = Node::root();
+        dump_children(self, &mut root);
+        root
+    }
+}
+
+fn dump_children(tree: &SuffixTree, node: &mut Node) {
+    for literal_node in tree.literal_children.iter() {
+        let mut child = Node::literal(literal_node.literal());
+        if let Some(pattern) = literal_node.pattern() {
+            child.add_pattern(pattern);
+        }
+        if let Some(tree) = literal_node.node() {
+            dump_children(tree, &mut child);
+        }
+        node.add_child(child);
+    }
+    for parser_node in &tree.parser_children {
+        let mut child = Node::parser(parser_node.parser());
+        if let Some(pattern) = parser_node.pattern() {
+            child.add_pattern(pattern);
+        }
+        if let Some(tree) = parser_node.node() {
+            dump_children(tree, &mut child);
+        }
+        node.add_child(child);
+    }
+}
@@ -6,6 +6,7 @@ use matcher::compiled_pattern::TokenType;
 
 mod literal;
 mod parser;
+mod dump;
 pub mod interface;
 
 use self::interface::Entry;
@@ -1,5 +1,5 @@
-use parsers::{GreedyParser, IntParser, OptionalParameter, Parser, ParserFactory, SetParser,
-              HasLengthConstraint};
+use parsers::{GreedyParser, IntParser, LinesParser, OptionalParameter, Parser, ParserFactory,
+              RepeatParser, SetParser, HasLengthConstraint};
 
 macro_rules! set_optinal_param {
     ($parser:expr, $param:expr) => {
@@ -59,4 +59,27 @@ impl ParserFactory for TrieParserFactory {
         parser.set_name(name);
         Box::new(parser)
     }
+    fn new_lines(name: Option<&str>, count: usize) -> Box<Parser> {
+        let mut parser = LinesParser::new(count);
+        let name = name.map(|name| name.to_owned());
+        parser.set_name(name);
+        Box::new(parser)
+    }
+    fn new_repeat(name: Option<&str>,
+                  item: Box<Parser>,
+                  separator: &str,
+                  opt_params: Option<Vec<OptionalParameter>>)
+                  -> Box<Parser> {
+        let mut parser = RepeatParser::new(item, separator);
+        for param in opt_params.into_iter().flat_map(|params| params.into_iter()) {
+            match param {
+                OptionalParameter::Int("min", value) => parser.set_min(value),
+                OptionalParameter::Int("max", value) => parser.set_max(Some(value)),
+                OptionalParameter::Int(..) => (),
+            }
+        }
+        let name = name.map(|name| name.to_owned());
+        parser.set_name(name);
+        Box::new(parser)
+    }
 }
@@ -1,5 +1,5 @@
 use std::hash::{SipHasher, Hash, Hasher};
-use super::{ParserBase, Parser, ObjectSafeHash, ParseResult};
+use super::{ParserBase, Parser, ObjectSafeHash, ParseResult, ParserType};
 
 #[derive(Clone, Debug, Hash)]
 pub struct GreedyParser {
@@ -26,6 +26,10 @@ impl GreedyParser {
         GreedyParser::default()
     }
 
+    pub fn end_string(&self) -> Option<&str> {
+        self.end_string.as_ref().map(|end_string| &end_string[..])
+    }
+
     pub fn set_end_string(&mut self, end_string: Option<String>) {
         self.end_string = end_string;
     }
@@ -69,6 +73,10 @@ impl Parser for GreedyParser {
     fn boxed_clone(&self) -> Box<Parser> {
         Box::new(self.clone())
     }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Greedy { end_string: self.end_string() }
+    }
 }
 
 #[cfg(test)]
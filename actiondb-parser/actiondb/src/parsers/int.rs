@@ -1,6 +1,6 @@
 use std::hash::{SipHasher, Hash, Hasher};
 
-use parsers::{Parser, ObjectSafeHash, SetParser, ParseResult, HasLengthConstraint};
+use parsers::{Parser, ObjectSafeHash, SetParser, ParseResult, HasLengthConstraint, ParserType};
 
 #[derive(Clone, Debug, Hash)]
 pub struct IntParser {
@@ -34,6 +34,13 @@ impl Parser for IntParser {
     fn boxed_clone(&self) -> Box<Parser> {
         Box::new(self.clone())
     }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Int {
+            min_length: self.delegate.min_length(),
+            max_length: self.delegate.max_length(),
+        }
+    }
 }
 
 impl Default for IntParser {
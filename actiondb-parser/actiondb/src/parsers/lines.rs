@@ -0,0 +1,112 @@
+use std::hash::{SipHasher, Hash, Hasher};
+use super::{ParserBase, Parser, ObjectSafeHash, ParseResult, ParserType};
+
+// Matches a fixed number of lines. The value doesn't contain the line ending
+// of the last line (neither `\n` nor `\r\n`), so it can be matched by the
+// next token, like `%{NEWLINE}`.
+#[derive(Clone, Debug, Hash)]
+pub struct LinesParser {
+    base: ParserBase,
+    count: usize,
+}
+
+impl LinesParser {
+    pub fn new(count: usize) -> LinesParser {
+        LinesParser {
+            base: ParserBase::new(),
+            count: count,
+        }
+    }
+
+    pub fn with_name<S: Into<String>>(name: S, count: usize) -> LinesParser {
+        LinesParser {
+            base: ParserBase::with_name(name.into()),
+            count: count,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl ObjectSafeHash for LinesParser {
+    fn hash_os(&self) -> u64 {
+        let mut hasher = SipHasher::new();
+        "parser:lines".hash(&mut hasher);
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Parser for LinesParser {
+    fn parse<'a, 'b>(&'a self, value: &'b str) -> Option<ParseResult<'a, 'b>> {
+        if self.count == 0 {
+            return Some(ParseResult::new(self, &value[..0]));
+        }
+
+        let mut start = 0;
+        for _ in 1..self.count {
+            match value[start..].find('\n') {
+                Some(pos) => start += pos + 1,
+                None => return None,
+            }
+        }
+        let end = match value[start..].find('\n') {
+            Some(pos) if value[..start + pos].ends_with('\r') => start + pos - 1,
+            Some(pos) => start + pos,
+            None => value.len(),
+        };
+        Some(ParseResult::new(self, &value[..end]))
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.base.name()
+    }
+
+    fn set_name(&mut self, name: Option<String>) {
+        self.base.set_name(name);
+    }
+
+    fn boxed_clone(&self) -> Box<Parser> {
+        Box::new(self.clone())
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Lines { count: self.count }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use parsers::{LinesParser, Parser};
+
+    #[test]
+    fn test_given_lines_parser_when_the_value_has_more_lines_then_it_matches_the_first_ones() {
+        let parser = LinesParser::with_name("trace", 2);
+        let res = parser.parse("at Foo.bar\nat Foo.baz\nat Foo.qux").unwrap();
+        assert_eq!(res.parser().name(), Some("trace"));
+        assert_eq!(res.value(), "at Foo.bar\nat Foo.baz");
+    }
+
+    #[test]
+    fn test_given_lines_parser_when_the_lines_end_with_crlf_then_the_last_line_ending_is_not_matched
+        () {
+        let parser = LinesParser::with_name("trace", 2);
+        let res = parser.parse("at Foo.bar\r\nat Foo.baz\r\nat Foo.qux").unwrap();
+        assert_eq!(res.value(), "at Foo.bar\r\nat Foo.baz");
+    }
+
+    #[test]
+    fn test_given_lines_parser_when_the_last_line_ends_the_value_then_it_matches() {
+        let parser = LinesParser::with_name("trace", 2);
+        assert_eq!(parser.parse("at Foo.bar\nat Foo.baz").unwrap().value(),
+                   "at Foo.bar\nat Foo.baz");
+    }
+
+    #[test]
+    fn test_given_lines_parser_when_the_value_has_less_lines_then_it_does_not_match() {
+        let parser = LinesParser::with_name("trace", 3);
+        assert!(parser.parse("at Foo.bar\nat Foo.baz").is_none());
+    }
+}
@@ -3,23 +3,59 @@ mod base;
 mod int;
 pub mod has_length_constraint;
 mod greedy;
+mod lines;
+mod repeat;
 
+use std::collections::BTreeSet;
 use std::fmt::Debug;
 pub use self::set::SetParser;
 pub use self::base::ParserBase;
 pub use self::int::IntParser;
 pub use self::has_length_constraint::HasLengthConstraint;
 pub use self::greedy::GreedyParser;
+pub use self::lines::LinesParser;
+pub use self::repeat::RepeatParser;
 
 pub trait ObjectSafeHash {
     fn hash_os(&self) -> u64;
 }
 
-pub trait Parser: Debug + ObjectSafeHash {
+// Parsers are `Send` and `Sync`, so a built matcher can be shared between
+// threads.
+pub trait Parser: Debug + ObjectSafeHash + Send + Sync {
     fn parse<'a, 'b>(&'a self, value: &'b str) -> Option<ParseResult<'a, 'b>>;
     fn name(&self) -> Option<&str>;
     fn set_name(&mut self, Option<String>);
     fn boxed_clone(&self) -> Box<Parser>;
+    fn parser_type(&self) -> ParserType;
+}
+
+// Describes a parser's type and parameters, so a compiled pattern can be
+// inspected or translated back into text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParserType<'a> {
+    Set {
+        character_set: &'a BTreeSet<u8>,
+        characters: &'a str,
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+    },
+    Int {
+        min_length: Option<usize>,
+        max_length: Option<usize>,
+    },
+    Greedy {
+        end_string: Option<&'a str>,
+    },
+    Lines {
+        count: usize,
+    },
+    Repeat {
+        item: Box<ParserType<'a>>,
+        separator: &'a str,
+        min: usize,
+        max: Option<usize>,
+    },
 }
 
 #[derive(Debug)]
@@ -27,10 +63,13 @@ pub enum OptionalParameter<'a> {
     Int(&'a str, usize),
 }
 
+// The result of a parser. Parsers which match more values (like REPEAT)
+// return them as items besides the whole matched value.
 #[derive(Debug)]
 pub struct ParseResult<'a, 'b> {
     parser: &'a Parser,
     value: &'b str,
+    items: Option<Vec<&'b str>>,
 }
 
 impl<'a, 'b> ParseResult<'a, 'b> {
@@ -38,6 +77,18 @@ impl<'a, 'b> ParseResult<'a, 'b> {
         ParseResult {
             parser: parser,
             value: value,
+            items: None,
+        }
+    }
+
+    pub fn with_items(parser: &'a Parser,
+                      value: &'b str,
+                      items: Vec<&'b str>)
+                      -> ParseResult<'a, 'b> {
+        ParseResult {
+            parser: parser,
+            value: value,
+            items: Some(items),
         }
     }
 
@@ -48,6 +99,14 @@ impl<'a, 'b> ParseResult<'a, 'b> {
     pub fn value(&self) -> &'b str {
         self.value
     }
+
+    pub fn items(&self) -> Option<&[&'b str]> {
+        self.items.as_ref().map(|items| &items[..])
+    }
+
+    pub fn into_items(self) -> Option<Vec<&'b str>> {
+        self.items
+    }
 }
 
 pub trait ParserFactory: {
@@ -59,4 +118,10 @@ pub trait ParserFactory: {
                    opt_params: Option<Vec<OptionalParameter>>)
                    -> Box<Parser>;
     fn new_greedy(name: Option<&str>, end_string: Option<&str>) -> Box<Parser>;
+    fn new_lines(name: Option<&str>, count: usize) -> Box<Parser>;
+    fn new_repeat(name: Option<&str>,
+                  item: Box<Parser>,
+                  separator: &str,
+                  opt_params: Option<Vec<OptionalParameter>>)
+                  -> Box<Parser>;
 }
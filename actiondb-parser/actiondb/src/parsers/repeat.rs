@@ -0,0 +1,189 @@
+use std::hash::{SipHasher, Hash, Hasher};
+use super::{ParserBase, Parser, ObjectSafeHash, ParseResult, ParserType};
+
+// Matches the items of a list, like `22,80,443`, with an item parser. The
+// items are separated by the separator string and their number must be
+// between `min` and `max`. The value is the whole list, the items are
+// returned separately.
+#[derive(Debug)]
+pub struct RepeatParser {
+    base: ParserBase,
+    item: Box<Parser>,
+    separator: String,
+    min: usize,
+    max: Option<usize>,
+}
+
+impl RepeatParser {
+    pub fn new<S: Into<String>>(item: Box<Parser>, separator: S) -> RepeatParser {
+        RepeatParser {
+            base: ParserBase::new(),
+            item: item,
+            separator: separator.into(),
+            min: 1,
+            max: None,
+        }
+    }
+
+    pub fn with_name<S, T>(name: S, item: Box<Parser>, separator: T) -> RepeatParser
+        where S: Into<String>,
+              T: Into<String>
+    {
+        let mut parser = RepeatParser::new(item, separator);
+        parser.set_name(Some(name.into()));
+        parser
+    }
+
+    pub fn item(&self) -> &Parser {
+        &*self.item
+    }
+
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    pub fn set_min(&mut self, min: usize) {
+        self.min = min;
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+
+    pub fn set_max(&mut self, max: Option<usize>) {
+        self.max = max;
+    }
+
+    fn is_full(&self, count: usize) -> bool {
+        self.max.map_or(false, |max| count >= max)
+    }
+}
+
+impl Clone for RepeatParser {
+    fn clone(&self) -> RepeatParser {
+        RepeatParser {
+            base: self.base.clone(),
+            item: self.item.boxed_clone(),
+            separator: self.separator.clone(),
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+impl ObjectSafeHash for RepeatParser {
+    fn hash_os(&self) -> u64 {
+        let mut hasher = SipHasher::new();
+        "parser:repeat".hash(&mut hasher);
+        self.base.hash(&mut hasher);
+        self.item.hash_os().hash(&mut hasher);
+        self.separator.hash(&mut hasher);
+        self.min.hash(&mut hasher);
+        self.max.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Parser for RepeatParser {
+    fn parse<'a, 'b>(&'a self, value: &'b str) -> Option<ParseResult<'a, 'b>> {
+        let mut items = Vec::new();
+        let mut end = 0;
+
+        while !self.is_full(items.len()) {
+            // a separator is consumed only if it's followed by an item
+            let start = if items.is_empty() {
+                0
+            } else if value[end..].starts_with(&self.separator[..]) {
+                end + self.separator.len()
+            } else {
+                break;
+            };
+            match self.item.parse(&value[start..]) {
+                Some(ref result) if !result.value().is_empty() => {
+                    items.push(result.value());
+                    end = start + result.value().len();
+                }
+                _ => break,
+            }
+        }
+
+        // the grammar accepts only a positive `min`, but a parser set up by
+        // hand can have 0, and an empty value still mustn't be matched
+        if items.is_empty() || items.len() < self.min {
+            None
+        } else {
+            Some(ParseResult::with_items(self, &value[..end], items))
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.base.name()
+    }
+
+    fn set_name(&mut self, name: Option<String>) {
+        self.base.set_name(name);
+    }
+
+    fn boxed_clone(&self) -> Box<Parser> {
+        Box::new(self.clone())
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Repeat {
+            item: Box::new(self.item.parser_type()),
+            separator: &self.separator,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use parsers::{IntParser, Parser, RepeatParser};
+
+    #[test]
+    fn test_given_repeat_parser_when_the_value_is_a_list_then_every_item_is_parsed() {
+        let parser = RepeatParser::with_name("ports", Box::new(IntParser::new()), ",");
+        let res = parser.parse("22,80,443 open").unwrap();
+        assert_eq!(res.parser().name(), Some("ports"));
+        assert_eq!(res.value(), "22,80,443");
+        assert_eq!(res.items(), Some(&["22", "80", "443"][..]));
+    }
+
+    #[test]
+    fn test_given_repeat_parser_when_no_item_follows_the_separator_then_it_is_not_consumed() {
+        let parser = RepeatParser::new(Box::new(IntParser::new()), ", ");
+        let res = parser.parse("22, 80, and more").unwrap();
+        assert_eq!(res.value(), "22, 80");
+        assert_eq!(res.items(), Some(&["22", "80"][..]));
+    }
+
+    #[test]
+    fn test_given_repeat_parser_when_max_is_reached_then_it_stops() {
+        let mut parser = RepeatParser::new(Box::new(IntParser::new()), ",");
+        parser.set_max(Some(2));
+        let res = parser.parse("22,80,443").unwrap();
+        assert_eq!(res.value(), "22,80");
+    }
+
+    #[test]
+    fn test_given_repeat_parser_when_there_are_less_items_than_min_then_it_does_not_match() {
+        let mut parser = RepeatParser::new(Box::new(IntParser::new()), ",");
+        assert!(parser.parse("ssh").is_none());
+        parser.set_min(3);
+        assert!(parser.parse("22,80").is_none());
+    }
+
+    #[test]
+    fn test_given_repeat_parser_when_min_is_zero_then_an_empty_list_still_does_not_match() {
+        let mut parser = RepeatParser::new(Box::new(IntParser::new()), ",");
+        parser.set_min(0);
+        assert!(parser.parse("ssh").is_none());
+        assert_eq!(parser.parse("22,ssh").map(|result| result.value()), Some("22"));
+    }
+}
@@ -2,12 +2,14 @@ use std::collections::BTreeSet;
 use std::iter::FromIterator;
 use std::hash::{SipHasher, Hash, Hasher};
 
-use parsers::{Parser, ObjectSafeHash, ParseResult, ParserBase, HasLengthConstraint};
+use parsers::{Parser, ObjectSafeHash, ParseResult, ParserBase, HasLengthConstraint, ParserType};
 
 #[derive(Clone, Debug, Hash)]
 pub struct SetParser {
     base: ParserBase,
     character_set: BTreeSet<u8>,
+    // the matching works on bytes, but the formatters need the characters
+    characters: String,
     min_length: Option<usize>,
     max_length: Option<usize>,
 }
@@ -17,6 +19,7 @@ impl SetParser {
         SetParser {
             base: ParserBase::with_name(name),
             character_set: SetParser::create_set_from_str(set),
+            characters: SetParser::sorted_characters(set),
             min_length: None,
             max_length: None,
         }
@@ -26,6 +29,7 @@ impl SetParser {
         SetParser {
             base: ParserBase::new(),
             character_set: SetParser::create_set_from_str(set),
+            characters: SetParser::sorted_characters(set),
             min_length: None,
             max_length: None,
         }
@@ -35,8 +39,19 @@ impl SetParser {
         SetParser::with_name(name.to_owned(), set)
     }
 
+    pub fn character_set(&self) -> &BTreeSet<u8> {
+        &self.character_set
+    }
+
+    // The distinct characters of the set in sorted order, so multibyte
+    // characters are kept intact.
+    pub fn characters(&self) -> &str {
+        &self.characters
+    }
+
     pub fn set_character_set(&mut self, set: &str) {
         self.character_set = SetParser::create_set_from_str(set);
+        self.characters = SetParser::sorted_characters(set);
     }
 
     fn create_set_from_str(set: &str) -> BTreeSet<u8> {
@@ -44,6 +59,11 @@ impl SetParser {
         BTreeSet::from_iter(vset)
     }
 
+    fn sorted_characters(set: &str) -> String {
+        let characters: BTreeSet<char> = set.chars().collect();
+        characters.into_iter().collect()
+    }
+
     fn calculate_match_length(&self, value: &str) -> usize {
         let mut match_len = 0;
 
@@ -96,6 +116,15 @@ impl Parser for SetParser {
     fn boxed_clone(&self) -> Box<Parser> {
         Box::new(self.clone())
     }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Set {
+            character_set: &self.character_set,
+            characters: &self.characters,
+            min_length: self.min_length,
+            max_length: self.max_length,
+        }
+    }
 }
 
 impl ObjectSafeHash for SetParser {
@@ -166,4 +195,10 @@ mod test {
         let p2 = SetParser::from_str("test", "01234");
         assert_eq!(p1.hash_os() == p2.hash_os(), false);
     }
+
+    #[test]
+    fn test_given_set_parser_when_it_has_multibyte_characters_then_they_are_kept_intact() {
+        let p = SetParser::from_str("test", "éaé");
+        assert_eq!(p.characters(), "aé");
+    }
 }
@@ -0,0 +1,89 @@
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+use super::Error;
+
+// A minimal in-memory representation of an XML document. Patterndb files are
+// small enough to be loaded at once and walking a tree is far simpler than
+// tracking the reader's state by hand.
+#[derive(Debug, Clone)]
+pub struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    pub fn new<S: Into<String>>(name: S) -> Element {
+        Element {
+            name: name.into(),
+            attributes: Vec::new(),
+            children: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    pub fn from_str(document: &str) -> Result<Element, Error> {
+        let config = ParserConfig::new().cdata_to_characters(true);
+        let reader = EventReader::new_with_config(document.as_bytes(), config);
+        let mut stack: Vec<Element> = Vec::new();
+
+        for event in reader {
+            match try!(event) {
+                XmlEvent::StartElement { name, attributes, .. } => {
+                    let mut element = Element::new(name.local_name);
+                    for attribute in attributes {
+                        element.attributes.push((attribute.name.local_name, attribute.value));
+                    }
+                    stack.push(element);
+                }
+                XmlEvent::EndElement { .. } => {
+                    let element = stack.pop().expect("Got an end element without its start");
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                XmlEvent::Characters(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.text.push_str(&text);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Err(Error::missing_element("document", "patterndb"))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|&&(ref key, _)| key == name).map(|&(_, ref value)| &value[..])
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> Box<Iterator<Item = &'a Element> + 'a> {
+        Box::new(self.children.iter().filter(move |child| child.name == name))
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children(name).next()
+    }
+
+    // patterndb allows both `<rule><pattern/></rule>` and
+    // `<rule><patterns><pattern/></patterns></rule>`, so every list like
+    // element has to be looked up in both places.
+    pub fn list<'a>(&'a self, container: &'a str, item: &'a str) -> Vec<&'a Element> {
+        let mut items: Vec<&Element> = self.children(item).collect();
+        for container in self.children(container) {
+            items.extend(container.children(item));
+        }
+        items
+    }
+}
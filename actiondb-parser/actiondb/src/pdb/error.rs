@@ -0,0 +1,94 @@
+use xml;
+
+use std::error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Xml(xml::reader::Error),
+    Emitter(xml::writer::Error),
+    MissingElement {
+        parent: String,
+        element: String,
+    },
+    MissingAttribute {
+        element: String,
+        attribute: String,
+    },
+    InvalidUuid(String),
+}
+
+impl Error {
+    pub fn missing_element(parent: &str, element: &str) -> Error {
+        Error::MissingElement {
+            parent: parent.to_owned(),
+            element: element.to_owned(),
+        }
+    }
+
+    pub fn missing_attribute(element: &str, attribute: &str) -> Error {
+        Error::MissingAttribute {
+            element: element.to_owned(),
+            attribute: attribute.to_owned(),
+        }
+    }
+}
+
+impl From<xml::reader::Error> for Error {
+    fn from(error: xml::reader::Error) -> Error {
+        Error::Xml(error)
+    }
+}
+
+impl From<xml::writer::Error> for Error {
+    fn from(error: xml::writer::Error) -> Error {
+        Error::Emitter(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            Error::Xml(ref error) => error.fmt(fmt),
+            Error::Emitter(ref error) => error.fmt(fmt),
+            Error::MissingElement{ref parent, ref element} => {
+                fmt.write_fmt(format_args!("A required element is missing from the patterndb \
+                                            file: parent={} element={}",
+                                           parent,
+                                           element))
+            }
+            Error::MissingAttribute{ref element, ref attribute} => {
+                fmt.write_fmt(format_args!("A required attribute is missing from the patterndb \
+                                            file: element={} attribute={}",
+                                           element,
+                                           attribute))
+            }
+            Error::InvalidUuid(ref uuid) => {
+                fmt.write_fmt(format_args!("A rule has an invalid id, it's not a UUID: id={}",
+                                           uuid))
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Xml(ref error) => error.description(),
+            Error::Emitter(ref error) => error.description(),
+            Error::MissingElement{..} => "A required element is missing from the patterndb file",
+            Error::MissingAttribute{..} => {
+                "A required attribute is missing from the patterndb file"
+            }
+            Error::InvalidUuid(..) => "A rule has an invalid id, it's not a UUID",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::Xml(ref error) => Some(error),
+            Error::Emitter(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
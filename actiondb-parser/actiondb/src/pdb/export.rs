@@ -0,0 +1,345 @@
+// Exports actiondb pattern files as patterndb XML documents. Constructs
+// without a patterndb equivalent are reported as `Untranslatable`.
+
+use uuid::Uuid;
+use xml::writer::{EmitterConfig, EventWriter, XmlEvent};
+
+use matcher::compiled_pattern::TokenType;
+use matcher::pattern::Pattern;
+use matcher::pattern::file::PatternFile;
+use matcher::pattern::testmessage::TestMessage;
+use parsers::{Parser, ParserType};
+
+use super::{hashed_uuid, Error, Untranslatable};
+
+use std::io::Write;
+use std::iter::Peekable;
+use std::slice::Iter;
+
+const PATTERNDB_VERSION: &'static str = "4";
+const PROVIDER: &'static str = "actiondb";
+
+pub struct Exporter {
+    ruleset_name: String,
+    ruleset_id: Option<Uuid>,
+    program: Option<String>,
+}
+
+impl Exporter {
+    pub fn new() -> Exporter {
+        Exporter::default()
+    }
+
+    pub fn set_ruleset_name(&mut self, name: String) {
+        self.ruleset_name = name;
+    }
+
+    // Without an explicit id the ruleset gets one derived from its name and
+    // the UUIDs of the exported patterns, so exporting the same file again
+    // gives the same document.
+    pub fn set_ruleset_id(&mut self, id: Option<Uuid>) {
+        self.ruleset_id = id;
+    }
+
+    // The program name which is used as the ruleset's pattern and as the
+    // program of the examples.
+    pub fn set_program(&mut self, program: Option<String>) {
+        self.program = program;
+    }
+
+    pub fn export(&self, file: &PatternFile) -> Result<Export, Error> {
+        let mut untranslatable = Vec::new();
+        let mut rules = Vec::new();
+
+        for pattern in file.patterns() {
+            if let Some(translated) = Exporter::translate_pattern(pattern, &mut untranslatable) {
+                Exporter::check_test_messages(pattern, &mut untranslatable);
+                rules.push((pattern, translated));
+            }
+        }
+
+        let ruleset_id = self.ruleset_id.unwrap_or_else(|| self.derive_ruleset_id(file));
+        let mut buffer = Vec::new();
+        {
+            let mut writer = EmitterConfig::new().perform_indent(true).create_writer(&mut buffer);
+            try!(self.write_document(&mut writer, &ruleset_id, &rules));
+        }
+
+        Ok(Export {
+            document: String::from_utf8(buffer).expect("The XML emitter produced invalid UTF-8"),
+            untranslatable: untranslatable,
+        })
+    }
+
+    fn translate_pattern(pattern: &Pattern,
+                         untranslatable: &mut Vec<Untranslatable>)
+                         -> Option<String> {
+        let uuid = pattern.uuid().hyphenated().to_string();
+        let mut translated = String::new();
+        let mut tokens = pattern.pattern().iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            let result = match *token {
+                TokenType::Literal(ref literal) => {
+                    translated.push_str(&escape_literal(literal));
+                    Ok(())
+                }
+                TokenType::Parser(ref parser) => {
+                    Exporter::translate_parser(&**parser,
+                                               &mut tokens,
+                                               &mut translated,
+                                               &uuid,
+                                               untranslatable)
+                }
+            };
+
+            if let Err(reason) = result {
+                untranslatable.push(Untranslatable::new(&uuid, &format!("{:?}", token), reason));
+                return None;
+            }
+        }
+
+        Some(translated)
+    }
+
+    fn translate_parser(parser: &Parser,
+                        tokens: &mut Peekable<Iter<TokenType>>,
+                        translated: &mut String,
+                        uuid: &str,
+                        untranslatable: &mut Vec<Untranslatable>)
+                        -> Result<(), String> {
+        let name = parser.name().unwrap_or("");
+
+        match parser.parser_type() {
+            ParserType::Int { min_length, max_length } => {
+                Exporter::check_length_constraints(parser, min_length, max_length, uuid, untranslatable);
+                translated.push_str(&format!("@NUMBER:{}@", name));
+                Ok(())
+            }
+            ParserType::Set { characters, min_length, max_length, .. } => {
+                if characters.contains('@') {
+                    return Err("patterndb parameters cannot contain the '@' character".to_owned());
+                }
+                Exporter::check_length_constraints(parser, min_length, max_length, uuid, untranslatable);
+                translated.push_str(&format!("@SET:{}:{}@", name, characters));
+                Ok(())
+            }
+            ParserType::Greedy { end_string: Some(end_string) } => {
+                if end_string.contains('@') {
+                    return Err("patterndb parameters cannot contain the '@' character".to_owned());
+                }
+                // ESTRING consumes its delimiter, GREEDY leaves it for the
+                // next literal
+                let rest = match tokens.next() {
+                    Some(&TokenType::Literal(ref literal)) if literal.starts_with(end_string) => {
+                        &literal[end_string.len()..]
+                    }
+                    _ => return Err("GREEDY is not followed by its end string".to_owned()),
+                };
+                translated.push_str(&format!("@ESTRING:{}:{}@", name, end_string));
+                translated.push_str(&escape_literal(rest));
+                Ok(())
+            }
+            ParserType::Greedy { end_string: None } => {
+                if tokens.peek().is_some() {
+                    Err("GREEDY without an end string can only be the last token in patterndb"
+                            .to_owned())
+                } else {
+                    translated.push_str(&format!("@ANYSTRING:{}@", name));
+                    Ok(())
+                }
+            }
+            ParserType::Lines { .. } => Err("patterndb doesn't have a LINES parser".to_owned()),
+            ParserType::Repeat { .. } => {
+                Err("patterndb doesn't have a REPEAT parser".to_owned())
+            }
+        }
+    }
+
+    fn check_length_constraints(parser: &Parser,
+                                min_length: Option<usize>,
+                                max_length: Option<usize>,
+                                uuid: &str,
+                                untranslatable: &mut Vec<Untranslatable>) {
+        if min_length.is_some() || max_length.is_some() {
+            let reason = "patterndb parsers have no length constraints, they are left out";
+            untranslatable.push(Untranslatable::new(uuid, &format!("{:?}", parser), reason));
+        }
+    }
+
+    fn check_test_messages(pattern: &Pattern, untranslatable: &mut Vec<Untranslatable>) {
+        let uuid = pattern.uuid().hyphenated().to_string();
+        for test_message in pattern.test_messages().unwrap_or(&[]) {
+            if !Exporter::is_exportable(test_message) {
+                let reason = "patterndb has no negative examples or examples which are claimed by \
+                              other rules, they are left out";
+                untranslatable.push(Untranslatable::new(&uuid, test_message.message(), reason));
+            } else if test_message.tags().is_some() {
+                let reason = "patterndb examples cannot check tags, they are left out";
+                untranslatable.push(Untranslatable::new(&uuid, test_message.message(), reason));
+            }
+        }
+    }
+
+    fn is_exportable(test_message: &TestMessage) -> bool {
+        !test_message.is_negative() && test_message.expected_uuid().is_none()
+    }
+
+    fn derive_ruleset_id(&self, file: &PatternFile) -> Uuid {
+        let uuids: Vec<&[u8]> = file.patterns()
+                                    .iter()
+                                    .map(|pattern| pattern.uuid().as_bytes())
+                                    .collect();
+        hashed_uuid(&(&self.ruleset_name, uuids))
+    }
+
+    fn write_document<W: Write>(&self,
+                                writer: &mut EventWriter<W>,
+                                ruleset_id: &Uuid,
+                                rules: &[(&Pattern, String)])
+                                -> Result<(), Error> {
+        let ruleset_id = ruleset_id.hyphenated().to_string();
+
+        try!(writer.write(XmlEvent::start_element("patterndb").attr("version", PATTERNDB_VERSION)));
+        try!(writer.write(XmlEvent::start_element("ruleset")
+                              .attr("name", &self.ruleset_name)
+                              .attr("id", &ruleset_id)));
+        if let Some(ref program) = self.program {
+            try!(writer.write(XmlEvent::start_element("patterns")));
+            try!(write_text_element(writer, "pattern", program));
+            try!(writer.write(XmlEvent::end_element()));
+        }
+        try!(writer.write(XmlEvent::start_element("rules")));
+        for &(pattern, ref translated) in rules {
+            try!(self.write_rule(writer, pattern, translated));
+        }
+        try!(writer.write(XmlEvent::end_element()));
+        try!(writer.write(XmlEvent::end_element()));
+        try!(writer.write(XmlEvent::end_element()));
+        Ok(())
+    }
+
+    fn write_rule<W: Write>(&self,
+                            writer: &mut EventWriter<W>,
+                            pattern: &Pattern,
+                            translated: &str)
+                            -> Result<(), Error> {
+        let uuid = pattern.uuid().hyphenated().to_string();
+        let mut rule = XmlEvent::start_element("rule").attr("provider", PROVIDER).attr("id", &uuid);
+        // the class of the metadata takes precedence over the name
+        let class = pattern.metadata().and_then(|metadata| metadata.class()).or(pattern.name());
+        if let Some(class) = class {
+            rule = rule.attr("class", class);
+        }
+
+        try!(writer.write(rule));
+        try!(writer.write(XmlEvent::start_element("patterns")));
+        try!(write_text_element(writer, "pattern", translated));
+        try!(writer.write(XmlEvent::end_element()));
+
+        let examples: Vec<&TestMessage> = pattern.test_messages()
+                                                 .unwrap_or(&[])
+                                                 .iter()
+                                                 .filter(|message| Exporter::is_exportable(message))
+                                                 .collect();
+        if !examples.is_empty() {
+            try!(writer.write(XmlEvent::start_element("examples")));
+            for test_message in examples {
+                try!(self.write_example(writer, test_message));
+            }
+            try!(writer.write(XmlEvent::end_element()));
+        }
+
+        if let Some(tags) = pattern.tags() {
+            try!(writer.write(XmlEvent::start_element("tags")));
+            for tag in tags {
+                try!(write_text_element(writer, "tag", tag));
+            }
+            try!(writer.write(XmlEvent::end_element()));
+        }
+
+        if let Some(values) = pattern.values() {
+            try!(writer.write(XmlEvent::start_element("values")));
+            for (name, value) in values {
+                try!(writer.write(XmlEvent::start_element("value").attr("name", name)));
+                try!(writer.write(XmlEvent::characters(value)));
+                try!(writer.write(XmlEvent::end_element()));
+            }
+            try!(writer.write(XmlEvent::end_element()));
+        }
+
+        try!(writer.write(XmlEvent::end_element()));
+        Ok(())
+    }
+
+    fn write_example<W: Write>(&self,
+                               writer: &mut EventWriter<W>,
+                               test_message: &TestMessage)
+                               -> Result<(), Error> {
+        try!(writer.write(XmlEvent::start_element("example")));
+
+        let mut message = XmlEvent::start_element("test_message");
+        if let Some(ref program) = self.program {
+            message = message.attr("program", program);
+        }
+        try!(writer.write(message));
+        try!(writer.write(XmlEvent::characters(test_message.message())));
+        try!(writer.write(XmlEvent::end_element()));
+
+        if !test_message.values().is_empty() {
+            try!(writer.write(XmlEvent::start_element("test_values")));
+            for (name, value) in test_message.values() {
+                try!(writer.write(XmlEvent::start_element("test_value").attr("name", name)));
+                try!(writer.write(XmlEvent::characters(value)));
+                try!(writer.write(XmlEvent::end_element()));
+            }
+            try!(writer.write(XmlEvent::end_element()));
+        }
+
+        try!(writer.write(XmlEvent::end_element()));
+        Ok(())
+    }
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Exporter {
+            ruleset_name: "actiondb".to_owned(),
+            ruleset_id: None,
+            program: None,
+        }
+    }
+}
+
+pub struct Export {
+    document: String,
+    untranslatable: Vec<Untranslatable>,
+}
+
+impl Export {
+    pub fn document(&self) -> &str {
+        &self.document
+    }
+
+    pub fn untranslatable(&self) -> &[Untranslatable] {
+        &self.untranslatable
+    }
+
+    pub fn into_document(self) -> String {
+        self.document
+    }
+}
+
+fn write_text_element<W: Write>(writer: &mut EventWriter<W>,
+                                 name: &str,
+                                 text: &str)
+                                 -> Result<(), Error> {
+    try!(writer.write(XmlEvent::start_element(name)));
+    try!(writer.write(XmlEvent::characters(text)));
+    try!(writer.write(XmlEvent::end_element()));
+    Ok(())
+}
+
+fn escape_literal(literal: &str) -> String {
+    literal.replace("@", "@@")
+}
@@ -0,0 +1,304 @@
+use uuid::Uuid;
+
+use grammar;
+use matcher::{Matcher, MatcherFactory};
+use matcher::compiled_pattern::CompiledPattern;
+use matcher::pattern::Pattern;
+use matcher::pattern::file::PatternFile;
+use matcher::pattern::testmessage::TestMessage;
+use matcher::trie::factory::TrieMatcherFactory;
+
+use super::{Element, Error, Untranslatable};
+use super::{hashed_uuid, translate};
+
+use std::collections::BTreeMap;
+
+pub struct Importer {
+    prepend_program: bool,
+}
+
+impl Importer {
+    pub fn new() -> Importer {
+        Importer::default()
+    }
+
+    // patterndb matches the program name against the ruleset's patterns and
+    // the message against the rule's patterns. actiondb has only the latter,
+    // so the program can be prepended to the patterns and test messages as
+    // `program: message`.
+    pub fn set_prepend_program(&mut self, prepend_program: bool) {
+        self.prepend_program = prepend_program;
+    }
+
+    pub fn import(&self, document: &str) -> Result<Import, Error> {
+        let root = try!(Element::from_str(document));
+        let mut import = Import::default();
+
+        for ruleset in root.children("ruleset") {
+            try!(self.import_ruleset(ruleset, &mut import));
+        }
+
+        Ok(import)
+    }
+
+    fn import_ruleset(&self, ruleset: &Element, import: &mut Import) -> Result<(), Error> {
+        let programs: Vec<&str> = ruleset.list("patterns", "pattern")
+                                         .into_iter()
+                                         .map(|program| program.text().trim())
+                                         .collect();
+
+        for rule in ruleset.list("rules", "rule") {
+            try!(self.import_rule(rule, &programs, import));
+        }
+        Ok(())
+    }
+
+    fn import_rule(&self,
+                   rule: &Element,
+                   programs: &[&str],
+                   import: &mut Import)
+                   -> Result<(), Error> {
+        let id = try!(rule.attribute("id").ok_or(Error::missing_attribute("rule", "id")));
+        let uuid = try!(Uuid::parse_str(id).map_err(|_| Error::InvalidUuid(id.to_owned())));
+        let name = rule.attribute("class").map(|class| class.to_owned());
+        let values = try!(Importer::import_values(rule, id, import));
+        let tags = Importer::import_tags(rule);
+        let compiled_patterns = self.import_patterns(rule, id, programs, import);
+
+        if compiled_patterns.is_empty() {
+            return Ok(());
+        }
+
+        let mut patterns = Vec::new();
+        for (i, compiled_pattern) in compiled_patterns.into_iter().enumerate() {
+            let uuid = if i == 0 {
+                uuid
+            } else {
+                Importer::derive_uuid(&uuid, i)
+            };
+            let pattern = Pattern::new(name.clone(),
+                                       uuid,
+                                       compiled_pattern,
+                                       None,
+                                       values.clone(),
+                                       tags.clone());
+            match pattern {
+                Ok(pattern) => patterns.push(pattern),
+                Err(error) => {
+                    let reason = format!("the values of the rule are invalid: {}", error);
+                    import.untranslatable.push(Untranslatable::new(id, "<values>", reason));
+                }
+            }
+        }
+
+        if patterns.is_empty() {
+            return Ok(());
+        }
+
+        let examples = try!(self.import_examples(rule));
+        let test_messages = Importer::assign_examples(&patterns, examples, id, import);
+
+        for (mut pattern, test_messages) in patterns.into_iter().zip(test_messages) {
+            if !test_messages.is_empty() {
+                pattern.set_test_messages(Some(test_messages));
+            }
+            import.file.patterns.push(pattern);
+        }
+        Ok(())
+    }
+
+    // The patterns of a rule after the first one get UUIDs derived from the
+    // rule's id and their position, so importing the same file again gives
+    // the same UUIDs.
+    fn derive_uuid(uuid: &Uuid, index: usize) -> Uuid {
+        hashed_uuid(&(uuid.as_bytes(), index as u64))
+    }
+
+    fn import_patterns(&self,
+                       rule: &Element,
+                       id: &str,
+                       programs: &[&str],
+                       import: &mut Import)
+                       -> Vec<CompiledPattern> {
+        let mut compiled_patterns = Vec::new();
+
+        for pattern in rule.list("patterns", "pattern") {
+            let pattern = pattern.text().trim();
+            let mut approximated = Vec::new();
+            for prefixed_pattern in self.prefix_with_programs(pattern, programs) {
+                let mut approximated_parsers = Vec::new();
+                let compiled_pattern =
+                    translate::translate(&prefixed_pattern, &mut approximated_parsers)
+                        .and_then(|translated| Importer::compile(&translated));
+
+                match compiled_pattern {
+                    Ok(compiled_pattern) => {
+                        compiled_patterns.push(compiled_pattern);
+                        approximated.extend(approximated_parsers);
+                    }
+                    Err(reason) => {
+                        import.untranslatable.push(Untranslatable::new(id, pattern, reason))
+                    }
+                }
+            }
+
+            // the patterns are kept, but they can match more than in patterndb
+            approximated.sort();
+            approximated.dedup();
+            for parser in approximated {
+                let reason = "the parser is approximated by a SET, it matches values which \
+                              patterndb rejects";
+                import.untranslatable.push(Untranslatable::new(id, &parser, reason));
+            }
+        }
+        compiled_patterns
+    }
+
+    fn compile(translated: &str) -> Result<CompiledPattern, String> {
+        grammar::parser::pattern(translated).map_err(|err| {
+            format!("the translated pattern is invalid: pattern={} error={}",
+                    translated,
+                    err)
+        })
+    }
+
+    fn prefix_with_programs(&self, pattern: &str, programs: &[&str]) -> Vec<String> {
+        if self.prepend_program && !programs.is_empty() {
+            programs.iter().map(|program| format!("{}: {}", program, pattern)).collect()
+        } else {
+            vec![pattern.to_owned()]
+        }
+    }
+
+    fn import_values(rule: &Element,
+                     id: &str,
+                     import: &mut Import)
+                     -> Result<Option<BTreeMap<String, String>>, Error> {
+        let mut values = BTreeMap::new();
+
+        for value in rule.list("values", "value") {
+            let name = try!(value.attribute("name").ok_or(Error::missing_attribute("value", "name")));
+            if value.text().contains('$') {
+                let construct = format!("<value name=\"{}\">{}</value>", name, value.text());
+                let reason = "values with template functions or macros are not supported";
+                import.untranslatable.push(Untranslatable::new(id, &construct, reason));
+            } else {
+                values.insert(name.to_owned(), value.text().to_owned());
+            }
+        }
+
+        if values.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(values))
+        }
+    }
+
+    fn import_tags(rule: &Element) -> Option<Vec<String>> {
+        let tags: Vec<String> = rule.list("tags", "tag")
+                                    .iter()
+                                    .map(|tag| tag.text().trim().to_owned())
+                                    .collect();
+        if tags.is_empty() {
+            None
+        } else {
+            Some(tags)
+        }
+    }
+
+    fn import_examples(&self, rule: &Element) -> Result<Vec<TestMessage>, Error> {
+        let mut examples = Vec::new();
+
+        for example in rule.list("examples", "example") {
+            let test_message = try!(example.child("test_message")
+                                           .ok_or(Error::missing_element("example",
+                                                                         "test_message")));
+            let message = match test_message.attribute("program") {
+                Some(program) if self.prepend_program => {
+                    format!("{}: {}", program, test_message.text())
+                }
+                _ => test_message.text().to_owned(),
+            };
+
+            let mut values = BTreeMap::new();
+            for value in example.list("test_values", "test_value") {
+                let name = try!(value.attribute("name")
+                                     .ok_or(Error::missing_attribute("test_value", "name")));
+                values.insert(name.to_owned(), value.text().to_owned());
+            }
+
+            examples.push(TestMessage::new(message, values, None));
+        }
+        Ok(examples)
+    }
+
+    // When a rule has more than one pattern every example is assigned to the
+    // pattern which matches it, otherwise the validation of the imported
+    // patterns would fail with MatchedToOtherPattern errors.
+    fn assign_examples(patterns: &[Pattern],
+                       examples: Vec<TestMessage>,
+                       id: &str,
+                       import: &mut Import)
+                       -> Vec<Vec<TestMessage>> {
+        let mut assigned: Vec<Vec<TestMessage>> = patterns.iter().map(|_| Vec::new()).collect();
+
+        if patterns.len() == 1 {
+            assigned[0] = examples;
+            return assigned;
+        }
+
+        let mut matcher = TrieMatcherFactory::new_matcher();
+        for pattern in patterns {
+            matcher.add_pattern(pattern.clone());
+        }
+
+        for example in examples {
+            let position = matcher.parse(example.message()).and_then(|result| {
+                patterns.iter().position(|pattern| pattern.uuid() == result.pattern().uuid())
+            });
+
+            match position {
+                Some(position) => assigned[position].push(example),
+                None => {
+                    let reason = "the example does not match any of the rule's translated patterns";
+                    import.untranslatable.push(Untranslatable::new(id, example.message(), reason));
+                }
+            }
+        }
+        assigned
+    }
+}
+
+impl Default for Importer {
+    fn default() -> Self {
+        Importer { prepend_program: false }
+    }
+}
+
+pub struct Import {
+    file: PatternFile,
+    untranslatable: Vec<Untranslatable>,
+}
+
+impl Import {
+    pub fn file(&self) -> &PatternFile {
+        &self.file
+    }
+
+    pub fn untranslatable(&self) -> &[Untranslatable] {
+        &self.untranslatable
+    }
+
+    pub fn into_file(self) -> PatternFile {
+        self.file
+    }
+}
+
+impl Default for Import {
+    fn default() -> Self {
+        Import {
+            file: PatternFile { patterns: Vec::new() },
+            untranslatable: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+pub use self::error::Error;
+pub use self::import::{Import, Importer};
+pub use self::export::{Export, Exporter};
+
+use self::element::Element;
+
+use uuid::Uuid;
+
+use std::fmt;
+use std::hash::{Hash, Hasher, SipHasher};
+
+#[cfg(test)]
+mod test;
+mod element;
+mod error;
+mod export;
+mod import;
+mod translate;
+
+// A construct which has no equivalent in the other format, so it was left
+// out from the result.
+#[derive(Debug, Clone)]
+pub struct Untranslatable {
+    rule_id: String,
+    construct: String,
+    reason: String,
+}
+
+impl Untranslatable {
+    pub fn new<S: Into<String>>(rule_id: &str, construct: &str, reason: S) -> Untranslatable {
+        Untranslatable {
+            rule_id: rule_id.to_owned(),
+            construct: construct.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn rule_id(&self) -> &str {
+        &self.rule_id
+    }
+
+    pub fn construct(&self) -> &str {
+        &self.construct
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for Untranslatable {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.write_fmt(format_args!("Failed to translate a patterndb construct: rule_id={} \
+                                    construct='{}' reason={}",
+                                   self.rule_id,
+                                   self.construct,
+                                   self.reason))
+    }
+}
+
+// Creates a UUID from the hash of `seed`, so the same input always gets the
+// same UUID. It's marked as a version 8 (custom) UUID.
+fn hashed_uuid<H: Hash>(seed: &H) -> Uuid {
+    let mut bytes = Vec::with_capacity(16);
+    for half in 0..2u8 {
+        let mut hasher = SipHasher::new();
+        seed.hash(&mut hasher);
+        half.hash(&mut hasher);
+        let hash = hasher.finish();
+        for shift in 0..8 {
+            bytes.push((hash >> (shift * 8)) as u8);
+        }
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Uuid::from_bytes(&bytes).expect("Failed to create a UUID from 16 bytes")
+}
@@ -0,0 +1,351 @@
+use super::{Exporter, Importer};
+use super::translate;
+
+use grammar::parser;
+use matcher::pattern::Pattern;
+use matcher::pattern::file::PatternFile;
+use matcher::pattern::metadata::Metadata;
+use matcher::pattern::testmessage::TestMessage;
+
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+const SSH_PATTERNDB: &'static str = r#"<?xml version='1.0' encoding='UTF-8'?>
+<patterndb version="4" pub_date="2010-10-17">
+  <ruleset name="ssh" id="4f4a4c4e-6d88-4ff2-a6a0-2e0c0e0f4e4a">
+    <patterns>
+      <pattern>sshd</pattern>
+    </patterns>
+    <rules>
+      <rule provider="test" id="4dd5a329-da83-4876-a431-ddcb59c2858c" class="system">
+        <patterns>
+          <pattern>Accepted @ESTRING:usracct.authmethod: @for @ESTRING:usracct.username: @from @IPv4:usracct.device@ port @NUMBER:usracct.service@ ssh2</pattern>
+        </patterns>
+        <examples>
+          <example>
+            <test_message program="sshd">Accepted password for bazsi from 127.0.0.1 port 48650 ssh2</test_message>
+            <test_values>
+              <test_value name="usracct.username">bazsi</test_value>
+              <test_value name="usracct.authmethod">password</test_value>
+              <test_value name="usracct.device">127.0.0.1</test_value>
+              <test_value name="usracct.service">48650</test_value>
+            </test_values>
+          </example>
+        </examples>
+        <tags>
+          <tag>usracct</tag>
+          <tag>secevt</tag>
+        </tags>
+        <values>
+          <value name="usracct.type">login</value>
+          <value name="usracct.sessionid">$PID</value>
+        </values>
+      </rule>
+      <rule provider="test" id="9a49c47d-29e9-4072-be84-3b76c6814743" class="system">
+        <patterns>
+          <pattern>Received disconnect from @IPv4:ipaddr@: @NUMBER:code@: disconnected by user</pattern>
+          <pattern>Connection closed by @IPv4:ipaddr@</pattern>
+        </patterns>
+        <examples>
+          <example>
+            <test_message program="sshd">Connection closed by 1.2.3.4</test_message>
+          </example>
+          <example>
+            <test_message program="sshd">Received disconnect from 1.2.3.4: 11: disconnected by user</test_message>
+          </example>
+        </examples>
+      </rule>
+      <rule provider="test" id="fa8bdbcb-e0fd-4da1-9fa4-15ecfec28ad2" class="system">
+        <pattern>Invalid user @PCRE:user:[a-z]+@ from @IPv4:ipaddr@</pattern>
+      </rule>
+    </rules>
+  </ruleset>
+</patterndb>
+"#;
+
+fn translate(pattern: &str) -> Result<String, String> {
+    translate::translate(pattern, &mut Vec::new())
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_it_is_translated_then_the_parsers_are_converted_to_actiondb_parsers
+    () {
+    assert_eq!(translate("Accepted @ESTRING:method: @for @NUMBER:port@").unwrap(),
+               "Accepted %{GREEDY:method} for %{INT:port}");
+    assert_eq!(translate("@ANYSTRING@").unwrap(), "%{GREEDY}");
+    assert_eq!(translate("from @IPv4:ip@").unwrap(),
+               "from %{SET(\"0123456789.\"):ip}");
+    assert_eq!(translate("@QSTRING:quoted:\"@").unwrap(),
+               "\"%{GREEDY:quoted}\"");
+    assert_eq!(translate("@QSTRING:quoted:<>@").unwrap(),
+               "<%{GREEDY:quoted}>");
+    assert_eq!(translate("@SET:spaces: \t@").unwrap(), "%{SET(\" \t\"):spaces}");
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_it_contains_escaped_at_signs_then_they_are_unescaped() {
+    assert_eq!(translate("user@@host @NUMBER:n@").unwrap(), "user@host %{INT:n}");
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_it_contains_the_actiondb_parser_begin_then_it_is_escaped() {
+    assert_eq!(translate("literal %{ @NUMBER:n@").unwrap(), r#"literal \%\{ %{INT:n}"#);
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_it_contains_an_estring_with_colon_delimiter_then_it_is_translated
+    () {
+    assert_eq!(translate("@ESTRING:program::@ message").unwrap(),
+               "%{GREEDY:program}: message");
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_it_contains_unsupported_parsers_then_it_cannot_be_translated
+    () {
+    let _ = translate("@PCRE:user:[a-z]+@").err().expect("PCRE parsers should not be translated");
+    let _ = translate("@ESTRING:user:@").err().expect("ESTRING needs an end delimiter");
+    let _ = translate("@NUMBER:invalid-name@").err().expect("Parser names must be identifiers");
+    let _ = translate("@NUMBER:unterminated").err().expect("Parsers must be terminated");
+    let _ = translate("@SET:quote:\"@").err().expect("SET cannot contain quotation marks");
+}
+
+#[test]
+fn test_given_patterndb_file_when_it_is_imported_then_rules_are_converted_to_patterns() {
+    let import = Importer::new().import(SSH_PATTERNDB).expect("Failed to import a patterndb file");
+    let patterns = import.file().patterns();
+    let uuid = Uuid::parse_str("4dd5a329-da83-4876-a431-ddcb59c2858c").unwrap();
+    let pattern = patterns.iter()
+                          .find(|pattern| pattern.uuid() == &uuid)
+                          .expect("Failed to find an imported rule by its id");
+
+    assert_eq!(patterns.len(), 3);
+    assert_eq!(pattern.name(), Some("system"));
+    assert_eq!(pattern.tags(), Some(&["usracct".to_owned(), "secevt".to_owned()][..]));
+    assert_eq!(pattern.values().unwrap().get("usracct.type").map(|x| &x[..]),
+               Some("login"));
+    assert_eq!(pattern.values().unwrap().get("usracct.sessionid"), None);
+}
+
+#[test]
+fn test_given_patterndb_file_when_it_contains_untranslatable_constructs_then_they_are_reported() {
+    let import = Importer::new().import(SSH_PATTERNDB).expect("Failed to import a patterndb file");
+    let untranslatable = import.untranslatable();
+
+    assert_eq!(untranslatable.len(), 4);
+    assert_eq!(untranslatable[0].rule_id(), "4dd5a329-da83-4876-a431-ddcb59c2858c");
+    assert_eq!(untranslatable[1].rule_id(), "4dd5a329-da83-4876-a431-ddcb59c2858c");
+    assert_eq!(untranslatable[1].construct(), "@IPv4:usracct.device@");
+    assert_eq!(untranslatable[2].rule_id(), "9a49c47d-29e9-4072-be84-3b76c6814743");
+    assert_eq!(untranslatable[2].construct(), "@IPv4:ipaddr@");
+    assert_eq!(untranslatable[3].rule_id(), "fa8bdbcb-e0fd-4da1-9fa4-15ecfec28ad2");
+}
+
+#[test]
+fn test_given_patterndb_pattern_when_its_parsers_are_approximated_then_they_are_reported() {
+    let mut approximated = Vec::new();
+    let translated = translate::translate("@HOSTNAME:host@ @NUMBER:port@ @EMAIL:to@",
+                                          &mut approximated)
+                         .expect("Failed to translate a patterndb pattern");
+
+    assert!(translated.contains("%{INT:port}"));
+    assert_eq!(approximated, vec!["@HOSTNAME:host@".to_owned(), "@EMAIL:to@".to_owned()]);
+}
+
+#[test]
+fn test_given_patterndb_rule_with_multiple_patterns_when_it_is_imported_then_examples_are_assigned_to_the_matching_pattern
+    () {
+    let mut import = Importer::new()
+                         .import(SSH_PATTERNDB)
+                         .expect("Failed to import a patterndb file")
+                         .into_file();
+    let uuid = Uuid::parse_str("9a49c47d-29e9-4072-be84-3b76c6814743").unwrap();
+
+    for pattern in import.patterns.iter_mut() {
+        if pattern.uuid() == &uuid {
+            let message = pattern.pop_test_message().expect("Failed to assign an example");
+            assert_eq!(message.message(),
+                       "Received disconnect from 1.2.3.4: 11: disconnected by user");
+            assert_eq!(pattern.pop_test_message().is_none(), true);
+        } else if pattern.name() == Some("system") && pattern.values().is_none() {
+            let message = pattern.pop_test_message().expect("Failed to assign an example");
+            assert_eq!(message.message(), "Connection closed by 1.2.3.4");
+        }
+    }
+}
+
+#[test]
+fn test_given_patterndb_rule_with_multiple_patterns_when_it_is_imported_twice_then_the_uuids_are_the_same
+    () {
+    let uuids = |document: &str| -> Vec<Uuid> {
+        Importer::new()
+            .import(document)
+            .expect("Failed to import a patterndb file")
+            .into_file()
+            .patterns()
+            .iter()
+            .map(|pattern| pattern.uuid().clone())
+            .collect()
+    };
+    let first = uuids(SSH_PATTERNDB);
+    let second = uuids(SSH_PATTERNDB);
+    let rule_uuid = Uuid::parse_str("9a49c47d-29e9-4072-be84-3b76c6814743").unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(first.iter().filter(|uuid| **uuid == rule_uuid).count(), 1);
+    let mut unique = first.clone();
+    unique.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    unique.dedup();
+    assert_eq!(unique.len(), first.len());
+}
+
+#[test]
+fn test_given_patterndb_file_when_programs_are_prepended_then_patterns_and_examples_contain_them
+    () {
+    let mut importer = Importer::new();
+    importer.set_prepend_program(true);
+    let mut file = importer.import(SSH_PATTERNDB)
+                           .expect("Failed to import a patterndb file")
+                           .into_file();
+    let uuid = Uuid::parse_str("4dd5a329-da83-4876-a431-ddcb59c2858c").unwrap();
+    let pattern = file.patterns
+                      .iter_mut()
+                      .find(|pattern| pattern.uuid() == &uuid)
+                      .expect("Failed to find an imported rule by its id");
+
+    let message = pattern.pop_test_message().expect("Failed to import an example");
+    assert_eq!(message.message(),
+               "sshd: Accepted password for bazsi from 127.0.0.1 port 48650 ssh2");
+}
+
+#[test]
+fn test_given_invalid_xml_when_it_is_imported_then_we_get_an_error() {
+    let _ = Importer::new()
+                .import("<patterndb><ruleset>")
+                .err()
+                .expect("We imported an invalid XML document");
+}
+
+#[test]
+fn test_given_patterndb_rule_when_its_id_is_not_a_uuid_then_we_get_an_error() {
+    let document = r#"<patterndb><ruleset><rules><rule id="not-a-uuid"><pattern>foo</pattern></rule></rules></ruleset></patterndb>"#;
+    let _ = Importer::new()
+                .import(document)
+                .err()
+                .expect("We imported a rule with an invalid id");
+}
+
+fn pattern_file_with(pattern: &str) -> PatternFile {
+    let uuid = Uuid::parse_str("9a49c47d-29e9-4072-be84-3b76c6814743").unwrap();
+    let mut pattern_ = Pattern::with_uuid(uuid);
+    pattern_.set_pattern(parser::pattern(pattern).expect("Failed to parse a pattern"));
+    PatternFile { patterns: vec![pattern_] }
+}
+
+#[test]
+fn test_given_pattern_file_when_it_is_exported_then_the_parsers_are_converted_to_patterndb_parsers
+    () {
+    let file = pattern_file_with("user %{GREEDY:user} logged in from %{INT:port} via \
+                                  %{GREEDY:via}");
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert!(export.untranslatable().is_empty());
+    assert!(export.document()
+                  .contains("<pattern>user @ESTRING:user: logged in from @@NUMBER:port@ via \
+                             @ANYSTRING:via@</pattern>"));
+}
+
+#[test]
+fn test_given_pattern_file_when_it_is_exported_twice_then_the_ruleset_id_is_the_same() {
+    let ruleset_id = |exporter: &Exporter, file: &PatternFile| -> String {
+        let export = exporter.export(file).expect("Failed to export a pattern file");
+        let start = export.document().find("id=\"").expect("The ruleset has no id") + 4;
+        export.document()[start..start + 36].to_owned()
+    };
+    let file = pattern_file_with("user %{GREEDY:user} logged in");
+    let first = ruleset_id(&Exporter::new(), &file);
+    assert_eq!(first, ruleset_id(&Exporter::new(), &file));
+
+    let mut exporter = Exporter::new();
+    exporter.set_ruleset_name("sshd".to_owned());
+    assert!(first != ruleset_id(&exporter, &file));
+}
+
+#[test]
+fn test_given_exporter_when_the_ruleset_id_is_set_then_it_is_used() {
+    let file = pattern_file_with("user %{GREEDY:user} logged in");
+    let mut exporter = Exporter::new();
+    let uuid = Uuid::parse_str("4f4a4c4e-6d88-4ff2-a6a0-2e0c0e0f4e4a").unwrap();
+    exporter.set_ruleset_id(Some(uuid));
+    let export = exporter.export(&file).expect("Failed to export a pattern file");
+    assert!(export.document().contains("id=\"4f4a4c4e-6d88-4ff2-a6a0-2e0c0e0f4e4a\""));
+}
+
+#[test]
+fn test_given_pattern_file_when_it_contains_at_signs_then_they_are_escaped() {
+    let file = pattern_file_with("mail to root@localhost");
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert!(export.document().contains("<pattern>mail to root@@localhost</pattern>"));
+}
+
+#[test]
+fn test_given_pattern_file_when_it_contains_untranslatable_patterns_then_they_are_reported_and_left_out
+    () {
+    let file = pattern_file_with("%{GREEDY:a}%{INT:b}");
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert_eq!(export.untranslatable().len(), 1);
+    assert!(!export.document().contains("<rule "));
+}
+
+#[test]
+fn test_given_pattern_file_when_it_contains_length_constraints_then_they_are_reported() {
+    let file = pattern_file_with("port %{INT(min_len=2):port}");
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert_eq!(export.untranslatable().len(), 1);
+    assert!(export.document().contains("<pattern>port @NUMBER:port@</pattern>"));
+}
+
+#[test]
+fn test_given_pattern_file_when_it_contains_a_non_ascii_set_then_its_characters_are_kept() {
+    let file = pattern_file_with("name %{SET(\"éá\"):name}");
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert!(export.document().contains("<pattern>name @SET:name:áé@</pattern>"));
+}
+
+#[test]
+fn test_given_patterndb_file_when_it_is_imported_and_exported_then_we_can_import_it_again() {
+    let file = Importer::new()
+                   .import(SSH_PATTERNDB)
+                   .expect("Failed to import a patterndb file")
+                   .into_file();
+    let mut exporter = Exporter::new();
+    exporter.set_program(Some("sshd".to_owned()));
+    let export = exporter.export(&file).expect("Failed to export a pattern file");
+    let reimported = Importer::new()
+                         .import(export.document())
+                         .expect("Failed to import an exported patterndb file")
+                         .into_file();
+    assert_eq!(file.patterns().len(), reimported.patterns().len());
+}
+
+#[test]
+fn test_given_pattern_with_test_messages_when_it_is_exported_then_examples_are_generated() {
+    let mut file = pattern_file_with("user %{GREEDY:user} logged in");
+    let mut values = BTreeMap::new();
+    values.insert("user".to_owned(), "bazsi".to_owned());
+    let message = TestMessage::new("user bazsi logged in".to_owned(), values, None);
+    file.patterns[0].set_test_messages(Some(vec![message]));
+
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert!(export.document().contains("<test_message>user bazsi logged in</test_message>"));
+    assert!(export.document().contains("<test_value name=\"user\">bazsi</test_value>"));
+}
+
+#[test]
+fn test_given_pattern_with_a_metadata_class_when_it_is_exported_then_it_is_the_class_of_the_rule() {
+    let mut file = pattern_file_with("user %{GREEDY:user} logged in");
+    let mut metadata = Metadata::new();
+    metadata.set_class(Some("violation".to_owned()));
+    file.patterns[0].set_metadata(Some(metadata));
+
+    let export = Exporter::new().export(&file).expect("Failed to export a pattern file");
+    assert!(export.document().contains("class=\"violation\""));
+}
@@ -0,0 +1,135 @@
+// Translates patterndb pattern strings (like `Accepted @ESTRING:method: @for`)
+// into the actiondb grammar (like `Accepted %{GREEDY:method} for`).
+
+use grammar;
+
+const ALNUM: &'static str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &'static str = "0123456789";
+const HEX_DIGITS: &'static str = "0123456789abcdefABCDEF";
+
+const PARSER_DELIMITER: char = '@';
+
+// These parsers validate their values in patterndb, but their translations
+// are SETs, which accept any sequence of the characters.
+const APPROXIMATED_PARSERS: &'static [&'static str] = &["HOSTNAME", "EMAIL", "IPv4", "IPv6",
+                                                         "IPvANY", "MACADDR", "FLOAT", "DOUBLE"];
+
+// The approximately translated parsers (like `@IPv4:ip@`) are appended to
+// `approximated`.
+pub fn translate(pattern: &str, approximated: &mut Vec<String>) -> Result<String, String> {
+    let mut translated = String::new();
+    let mut rest = pattern;
+
+    while let Some(pos) = rest.find(PARSER_DELIMITER) {
+        push_literal(&mut translated, &rest[..pos]);
+        rest = &rest[pos + 1..];
+
+        if rest.starts_with(PARSER_DELIMITER) {
+            translated.push(PARSER_DELIMITER);
+            rest = &rest[1..];
+            continue;
+        }
+
+        match rest.find(PARSER_DELIMITER) {
+            Some(end) => {
+                let parser = try!(translate_parser(&rest[..end], approximated));
+                translated.push_str(&parser);
+                rest = &rest[end + 1..];
+            }
+            None => return Err(format!("unterminated parser: @{}", rest)),
+        }
+    }
+    push_literal(&mut translated, rest);
+
+    Ok(translated)
+}
+
+fn push_literal(translated: &mut String, literal: &str) {
+    translated.push_str(&grammar::escape_literal(literal));
+}
+
+fn translate_parser(parser: &str, approximated: &mut Vec<String>) -> Result<String, String> {
+    let mut parts = parser.splitn(3, ':');
+    let parser_type = parts.next().unwrap_or("");
+    let name = parts.next().unwrap_or("");
+    let param = parts.next().unwrap_or("");
+
+    try!(check_name(name));
+
+    if APPROXIMATED_PARSERS.contains(&parser_type) {
+        approximated.push(format!("@{}@", parser));
+    }
+
+    match parser_type {
+        "NUMBER" => Ok(format!("%{{INT{}}}", format_name(name))),
+        "ANYSTRING" => Ok(format!("%{{GREEDY{}}}", format_name(name))),
+        "ESTRING" => {
+            if param.is_empty() {
+                Err(format!("ESTRING without an end delimiter: @{}@", parser))
+            } else {
+                let mut translated = format!("%{{GREEDY{}}}", format_name(name));
+                push_literal(&mut translated, param);
+                Ok(translated)
+            }
+        }
+        "QSTRING" => {
+            let mut quotes = param.chars();
+            match quotes.next() {
+                Some(open) => {
+                    let close = quotes.next().unwrap_or(open);
+                    let mut translated = String::new();
+                    push_literal(&mut translated, &open.to_string());
+                    translated.push_str(&format!("%{{GREEDY{}}}", format_name(name)));
+                    push_literal(&mut translated, &close.to_string());
+                    Ok(translated)
+                }
+                None => Err(format!("QSTRING without quote characters: @{}@", parser)),
+            }
+        }
+        "STRING" => set(&format!("{}{}", ALNUM, param), name),
+        "SET" => set(param, name),
+        "HOSTNAME" => set(&format!("{}-.", ALNUM), name),
+        "EMAIL" => set(&format!("{}-._+@", ALNUM), name),
+        "IPv4" => set(&format!("{}.", DIGITS), name),
+        "IPv6" => set(&format!("{}:.", HEX_DIGITS), name),
+        "IPvANY" => set(&format!("{}:.", HEX_DIGITS), name),
+        "MACADDR" => set(&format!("{}:", HEX_DIGITS), name),
+        "FLOAT" | "DOUBLE" => set(&format!("{}.-+eE", DIGITS), name),
+        "" => Err(format!("missing parser type: @{}@", parser)),
+        _ => Err(format!("unsupported patterndb parser: {}", parser_type)),
+    }
+}
+
+fn set(characters: &str, name: &str) -> Result<String, String> {
+    if characters.is_empty() {
+        Err("SET parser without characters".to_owned())
+    } else if characters.contains('"') {
+        Err(format!("the character set cannot contain a quotation mark: {}", characters))
+    } else {
+        Ok(format!("%{{SET(\"{}\"){}}}", characters, format_name(name)))
+    }
+}
+
+fn format_name(name: &str) -> String {
+    if name.is_empty() {
+        String::new()
+    } else {
+        format!(":{}", name)
+    }
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let is_valid = name.chars().enumerate().all(|(i, c)| {
+        match c {
+            'a'...'z' | 'A'...'Z' | '_' | '.' => true,
+            '0'...'9' => i > 0,
+            _ => false,
+        }
+    });
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!("the parser name is not a valid actiondb identifier: {}", name))
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::preprocess::{Preprocessor, Step};
+
+#[cfg(test)]
+mod test;
+mod preprocess;
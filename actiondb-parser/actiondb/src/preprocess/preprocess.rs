@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+
+const ESCAPE: char = '\u{1b}';
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+// A transformation of the messages which is applied before they are parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    // removes the leading and trailing whitespaces and byte order marks
+    Trim,
+    // removes the ANSI escape sequences, the byte order marks and the
+    // control characters except tabs and newlines
+    StripControl,
+    // replaces every run of whitespaces (including newlines) with one space
+    NormalizeWhitespace,
+    // keeps only the first line without its line ending
+    FirstLine,
+}
+
+impl Step {
+    pub fn from_str(step: &str) -> Option<Step> {
+        match step {
+            "trim" => Some(Step::Trim),
+            "strip-control" => Some(Step::StripControl),
+            "normalize-whitespace" => Some(Step::NormalizeWhitespace),
+            "first-line" => Some(Step::FirstLine),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Step::Trim => "trim",
+            Step::StripControl => "strip-control",
+            Step::NormalizeWhitespace => "normalize-whitespace",
+            Step::FirstLine => "first-line",
+        }
+    }
+
+    // The message is borrowed as long as it's not modified or it's only
+    // shortened, so the parsed values refer to the original text.
+    pub fn apply<'a>(&self, message: Cow<'a, str>) -> Cow<'a, str> {
+        match *self {
+            Step::Trim => trim(message),
+            Step::StripControl => strip_control(message),
+            Step::NormalizeWhitespace => normalize_whitespace(message),
+            Step::FirstLine => first_line(message),
+        }
+    }
+}
+
+// Applies its steps to the messages in the order they were added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Preprocessor {
+    steps: Vec<Step>,
+}
+
+impl Preprocessor {
+    pub fn new() -> Preprocessor {
+        Preprocessor::default()
+    }
+
+    // Parses a comma separated list of steps, like `strip-control,trim`.
+    pub fn from_list(steps: &str) -> Result<Preprocessor, String> {
+        let mut preprocessor = Preprocessor::new();
+        let steps = steps.split(',').map(|step| step.trim()).filter(|step| !step.is_empty());
+        for step in steps {
+            let step = try!(Step::from_str(step).ok_or(step.to_owned()));
+            preprocessor.add_step(step);
+        }
+        Ok(preprocessor)
+    }
+
+    pub fn add_step(&mut self, step: Step) {
+        self.steps.push(step);
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn process<'a>(&self, message: &'a str) -> Cow<'a, str> {
+        self.steps.iter().fold(Cow::Borrowed(message), |message, step| step.apply(message))
+    }
+}
+
+fn slice<'a>(message: Cow<'a, str>, start: usize, end: usize) -> Cow<'a, str> {
+    if start == 0 && end == message.len() {
+        return message;
+    }
+    match message {
+        Cow::Borrowed(message) => Cow::Borrowed(&message[start..end]),
+        Cow::Owned(message) => Cow::Owned(message[start..end].to_owned()),
+    }
+}
+
+fn is_trimmed(c: char) -> bool {
+    c.is_whitespace() || c == BYTE_ORDER_MARK
+}
+
+fn trim<'a>(message: Cow<'a, str>) -> Cow<'a, str> {
+    let start = message.len() - message.trim_left_matches(is_trimmed).len();
+    let end = message.trim_right_matches(is_trimmed).len();
+    if start >= end {
+        slice(message, 0, 0)
+    } else {
+        slice(message, start, end)
+    }
+}
+
+fn is_stripped(c: char) -> bool {
+    c == BYTE_ORDER_MARK || (c.is_control() && c != '\t' && c != '\n')
+}
+
+fn strip_control<'a>(message: Cow<'a, str>) -> Cow<'a, str> {
+    if !message.chars().any(is_stripped) {
+        return message;
+    }
+
+    let mut result = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(c) = chars.next() {
+        if c == ESCAPE {
+            // a control sequence, like `\x1b[1;31m`, ends with a character
+            // between `@` and `~`, the other escape sequences are two
+            // characters long
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c >= '@' && c <= '~' {
+                        break;
+                    }
+                }
+            }
+        } else if !is_stripped(c) {
+            result.push(c);
+        }
+    }
+    Cow::Owned(result)
+}
+
+fn normalize_whitespace<'a>(message: Cow<'a, str>) -> Cow<'a, str> {
+    let mut previous_whitespace = false;
+    let is_normalized = message.chars().all(|c| {
+        let is_normalized = !c.is_whitespace() || (c == ' ' && !previous_whitespace);
+        previous_whitespace = c.is_whitespace();
+        is_normalized
+    });
+    if is_normalized {
+        return message;
+    }
+
+    let mut result = String::with_capacity(message.len());
+    let mut previous_whitespace = false;
+    for c in message.chars() {
+        if !c.is_whitespace() {
+            result.push(c);
+        } else if !previous_whitespace {
+            result.push(' ');
+        }
+        previous_whitespace = c.is_whitespace();
+    }
+    Cow::Owned(result)
+}
+
+fn first_line<'a>(message: Cow<'a, str>) -> Cow<'a, str> {
+    let end = match message.find('\n') {
+        Some(end) if message[..end].ends_with('\r') => end - 1,
+        Some(end) => end,
+        None => return message,
+    };
+    slice(message, 0, end)
+}
@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+
+use matcher::{Matcher, MatcherFactory};
+use matcher::pattern::Pattern;
+use matcher::trie::factory::TrieMatcherFactory;
+use super::{Preprocessor, Step};
+
+fn preprocessor(steps: &str) -> Preprocessor {
+    Preprocessor::from_list(steps).expect("Failed to parse valid preprocessing steps")
+}
+
+fn is_borrowed(message: &Cow<str>) -> bool {
+    match *message {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
+    }
+}
+
+#[test]
+fn test_given_list_of_steps_when_it_is_parsed_then_the_steps_are_kept_in_order() {
+    let preprocessor = preprocessor("strip-control, trim,first-line");
+    assert_eq!(preprocessor.steps(),
+               &[Step::StripControl, Step::Trim, Step::FirstLine][..]);
+}
+
+#[test]
+fn test_given_list_of_steps_when_it_contains_an_unknown_step_then_it_is_returned_as_an_error() {
+    assert_eq!(Preprocessor::from_list("trim,lowercase"),
+               Err("lowercase".to_owned()));
+}
+
+#[test]
+fn test_given_preprocessor_without_steps_when_a_message_is_processed_then_it_is_not_changed() {
+    let message = Preprocessor::new().process(" foo\r\n");
+    assert_eq!(message, " foo\r\n");
+    assert!(is_borrowed(&message));
+}
+
+#[test]
+fn test_given_trim_step_when_a_message_is_processed_then_whitespaces_and_byte_order_marks_are_removed
+    () {
+    let message = preprocessor("trim").process("\u{feff} foo bar\r\n");
+    assert_eq!(message, "foo bar");
+    assert!(is_borrowed(&message));
+    assert_eq!(preprocessor("trim").process(" \t "), "");
+}
+
+#[test]
+fn test_given_strip_control_step_when_a_message_contains_ansi_colours_then_they_are_removed() {
+    let message = preprocessor("strip-control").process("\u{1b}[1;31mERROR\u{1b}[0m disk\r is \
+                                                         full\u{7}\tnow");
+    assert_eq!(message, "ERROR disk is full\tnow");
+}
+
+#[test]
+fn test_given_strip_control_step_when_a_message_is_clean_then_it_is_borrowed() {
+    let message = preprocessor("strip-control").process("disk is full\n");
+    assert_eq!(message, "disk is full\n");
+    assert!(is_borrowed(&message));
+}
+
+#[test]
+fn test_given_normalize_whitespace_step_when_a_message_is_processed_then_runs_of_whitespaces_become_one_space
+    () {
+    let message = preprocessor("normalize-whitespace").process("user  bob\tlogged\r\nin");
+    assert_eq!(message, "user bob logged in");
+    assert!(is_borrowed(&preprocessor("normalize-whitespace").process("user bob logged in")));
+}
+
+#[test]
+fn test_given_first_line_step_when_a_message_has_more_lines_then_only_the_first_one_is_kept() {
+    let message = preprocessor("first-line").process("java.lang.NullPointerException\r\n\tat \
+                                                      Foo.bar(Foo.java:42)");
+    assert_eq!(message, "java.lang.NullPointerException");
+    assert!(is_borrowed(&message));
+}
+
+#[test]
+fn test_given_preprocessed_message_when_it_is_parsed_then_the_values_refer_to_the_original_text() {
+    let json = r#"{"uuid": "6d2cba0c-e241-464a-89c3-8035cac8f73e",
+                   "pattern": "user %{GREEDY:user} logged in"}"#;
+    let mut matcher = TrieMatcherFactory::new_matcher();
+    matcher.add_pattern(Pattern::from_json(json).expect("Failed to deserialize a pattern"));
+    let original = "  user bob logged in\r\n";
+    let message = preprocessor("trim,first-line").process(original);
+    let result = matcher.parse(&message).expect("Failed to parse a preprocessed message");
+    let user = result.values().get("user").expect("The user wasn't parsed");
+    let offset = user.as_ptr() as usize - original.as_ptr() as usize;
+    assert_eq!(&original[offset..offset + user.len()], "bob");
+}
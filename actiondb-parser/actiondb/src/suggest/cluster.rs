@@ -0,0 +1,164 @@
+use std::collections::BTreeSet;
+
+// The characters of the values which can be parsed with a `SET` parser
+// instead of a `GREEDY` one, like IP addresses and hexadecimal numbers.
+const SET_CHARACTERS: &'static str = "0123456789abcdefABCDEF.:-";
+const VARIABLE_PLACEHOLDER: &'static str = "<*>";
+
+// A group of messages with the same token structure. The tokens which were
+// the same in every message are literals, the others are variables.
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    tokens: Vec<Token>,
+    count: usize,
+    samples: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    Literal(String),
+    Variable(Variable),
+}
+
+// A summary of the values which were seen at a variable position. It's used
+// to choose the parser of the position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variable {
+    numeric: bool,
+    characters: Option<BTreeSet<char>>,
+}
+
+impl Variable {
+    fn new(value: &str) -> Variable {
+        let mut variable = Variable {
+            numeric: true,
+            characters: Some(BTreeSet::new()),
+        };
+        variable.add(value);
+        variable
+    }
+
+    fn add(&mut self, value: &str) {
+        self.numeric = self.numeric && is_numeric(value);
+        let fits_a_set = !value.is_empty() && value.chars().all(|c| SET_CHARACTERS.contains(c));
+        if fits_a_set {
+            if let Some(ref mut characters) = self.characters {
+                characters.extend(value.chars());
+            }
+        } else {
+            self.characters = None;
+        }
+    }
+
+    // Every value consisted of digits only.
+    pub fn is_numeric(&self) -> bool {
+        self.numeric
+    }
+
+    // The characters of the values if every one of them fits a `SET` parser.
+    pub fn characters(&self) -> Option<&BTreeSet<char>> {
+        self.characters.as_ref()
+    }
+}
+
+fn is_numeric(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c >= '0' && c <= '9')
+}
+
+pub fn tokenize(message: &str) -> Vec<&str> {
+    message.split(' ').collect()
+}
+
+impl Cluster {
+    pub fn new(tokens: &[&str], message: &str) -> Cluster {
+        // numbers are variables even if they are seen only once
+        let tokens = tokens.iter()
+                           .map(|token| {
+                               if is_numeric(token) {
+                                   Token::Variable(Variable::new(token))
+                               } else {
+                                   Token::Literal((*token).to_owned())
+                               }
+                           })
+                           .collect();
+        Cluster {
+            tokens: tokens,
+            count: 1,
+            samples: vec![message.to_owned()],
+        }
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    // The number of messages in the cluster.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // The first few messages of the cluster.
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+
+    // The token structure of the messages where the variables are replaced
+    // with `<*>`.
+    pub fn template(&self) -> String {
+        let tokens: Vec<&str> = self.tokens
+                                    .iter()
+                                    .map(|token| {
+                                        match *token {
+                                            Token::Literal(ref literal) => literal.as_str(),
+                                            Token::Variable(_) => VARIABLE_PLACEHOLDER,
+                                        }
+                                    })
+                                    .collect();
+        tokens.join(" ")
+    }
+
+    // The ratio of the literal tokens which are equal to the tokens of the
+    // message at the same position. The variables don't count, like in Drain.
+    pub fn similarity(&self, tokens: &[&str]) -> f64 {
+        if self.tokens.len() != tokens.len() || tokens.is_empty() {
+            return 0.0;
+        }
+        let equal = self.tokens
+                        .iter()
+                        .zip(tokens)
+                        .filter(|&(token, other)| {
+                            match *token {
+                                Token::Literal(ref literal) => literal == other,
+                                Token::Variable(_) => false,
+                            }
+                        })
+                        .count();
+        equal as f64 / tokens.len() as f64
+    }
+
+    // Adds a message to the cluster. The literals which differ from the
+    // tokens of the message become variables.
+    pub fn add(&mut self, tokens: &[&str], message: &str, max_samples: usize) {
+        for (token, other) in self.tokens.iter_mut().zip(tokens) {
+            let variable = match *token {
+                Token::Literal(ref literal) if literal != other => {
+                    let mut variable = Variable::new(literal);
+                    variable.add(other);
+                    Some(variable)
+                }
+                Token::Literal(_) => None,
+                Token::Variable(ref mut variable) => {
+                    variable.add(other);
+                    None
+                }
+            };
+            if let Some(variable) = variable {
+                *token = Token::Variable(variable);
+            }
+        }
+        self.count += 1;
+        if self.samples.len() < max_samples {
+            self.samples.push(message.to_owned());
+        }
+    }
+}
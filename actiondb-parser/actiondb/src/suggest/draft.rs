@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use grammar;
+
+use super::cluster::{self, Cluster, Token, Variable};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const NAME_PREFIX: &'static str = "SUGGESTED_";
+
+// A pattern suggested from a cluster in the format of the pattern files.
+pub struct Draft {
+    uuid: Uuid,
+    name: String,
+    pattern: String,
+    count: usize,
+    test_messages: Vec<(String, BTreeMap<String, String>)>,
+}
+
+impl Draft {
+    pub fn new(index: usize, cluster: &Cluster) -> Draft {
+        let names = variable_names(cluster.tokens());
+        let mut pattern = Vec::new();
+
+        for (position, token) in cluster.tokens().iter().enumerate() {
+            match *token {
+                Token::Literal(ref literal) => pattern.push(grammar::escape_literal(literal)),
+                Token::Variable(ref variable) => {
+                    pattern.push(parser(variable, &names[&position]))
+                }
+            }
+        }
+
+        let test_messages = cluster.samples()
+                                   .iter()
+                                   .map(|sample| {
+                                       let tokens = cluster::tokenize(sample);
+                                       let values = names.iter()
+                                                         .map(|(position, name)| {
+                                                             (name.clone(),
+                                                              tokens[*position].to_owned())
+                                                         })
+                                                         .collect();
+                                       (sample.clone(), values)
+                                   })
+                                   .collect();
+
+        Draft {
+            uuid: Uuid::new_v4(),
+            name: format!("{}{}", NAME_PREFIX, index),
+            pattern: pattern.join(" "),
+            count: cluster.count(),
+            test_messages: test_messages,
+        }
+    }
+
+    pub fn write_yaml(&self, buffer: &mut String) {
+        let _ = writeln!(buffer, "  # occurrences: {}", self.count);
+        let _ = writeln!(buffer, "  - uuid: {}", self.uuid.hyphenated());
+        let _ = writeln!(buffer, "    name: {}", self.name);
+        let _ = writeln!(buffer, "    pattern: {}", quote(&self.pattern));
+        if self.test_messages.is_empty() {
+            return;
+        }
+        let _ = writeln!(buffer, "    test_messages:");
+        for &(ref message, ref values) in &self.test_messages {
+            let _ = writeln!(buffer, "      - message: {}", quote(message));
+            if !values.is_empty() {
+                let _ = writeln!(buffer, "        values:");
+                for (name, value) in values {
+                    let _ = writeln!(buffer, "          {}: {}", name, quote(value));
+                }
+            }
+        }
+    }
+}
+
+// The variables are named after the word before them, like `port` in
+// `port 22`. Otherwise they are named after their position.
+fn variable_names(tokens: &[Token]) -> BTreeMap<usize, String> {
+    let mut names = BTreeMap::new();
+    let mut used = BTreeSet::new();
+
+    for (position, token) in tokens.iter().enumerate() {
+        if let Token::Variable(_) = *token {
+            let word = match position.checked_sub(1).map(|previous| &tokens[previous]) {
+                Some(&Token::Literal(ref literal)) if is_word(literal) => literal.to_lowercase(),
+                _ => format!("field{}", position),
+            };
+            let mut name = word.clone();
+            let mut suffix = 1;
+            while used.contains(&name) {
+                suffix += 1;
+                name = format!("{}_{}", word, suffix);
+            }
+            used.insert(name.clone());
+            names.insert(position, name);
+        }
+    }
+    names
+}
+
+fn is_word(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z'))
+}
+
+fn parser(variable: &Variable, name: &str) -> String {
+    if variable.is_numeric() {
+        format!("%{{INT:{}}}", name)
+    } else if let Some(characters) = variable.characters() {
+        let characters: String = characters.iter().cloned().collect();
+        format!("%{{SET(\"{}\"):{}}}", characters, name)
+    } else {
+        format!("%{{GREEDY:{}}}", name)
+    }
+}
+
+// Writes the string as a double quoted YAML scalar.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' || c == '\x7f' => {
+                let _ = write!(quoted, "\\x{:02X}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
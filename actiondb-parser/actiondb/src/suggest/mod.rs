@@ -0,0 +1,8 @@
+pub use self::cluster::{Cluster, Token, Variable};
+pub use self::suggester::Suggester;
+
+#[cfg(test)]
+mod test;
+mod cluster;
+mod draft;
+mod suggester;
@@ -0,0 +1,124 @@
+use super::cluster::{self, Cluster};
+use super::draft::Draft;
+
+use std::collections::BTreeMap;
+
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.5;
+const DEFAULT_MAX_SAMPLES: usize = 3;
+const DEFAULT_MIN_COUNT: usize = 1;
+
+// Clusters messages by their token structure in the style of Drain: the
+// messages are grouped by their number of tokens and their first token, and
+// in a group a message joins the most similar cluster if it's similar enough.
+// Every cluster can be turned into a draft pattern.
+pub struct Suggester {
+    similarity_threshold: f64,
+    max_samples: usize,
+    min_count: usize,
+    messages: usize,
+    groups: BTreeMap<(usize, String), Vec<usize>>,
+    clusters: Vec<Cluster>,
+}
+
+impl Suggester {
+    pub fn new() -> Suggester {
+        Suggester::default()
+    }
+
+    // The minimum ratio of the equal literal tokens which is required to add
+    // a message to a cluster.
+    pub fn set_similarity_threshold(&mut self, similarity_threshold: f64) {
+        self.similarity_threshold = similarity_threshold;
+    }
+
+    // The maximum number of messages which are kept as test messages.
+    pub fn set_max_samples(&mut self, max_samples: usize) {
+        self.max_samples = max_samples;
+    }
+
+    // The clusters with less messages are left out from the suggestions.
+    pub fn set_min_count(&mut self, min_count: usize) {
+        self.min_count = min_count;
+    }
+
+    pub fn add_message(&mut self, message: &str) {
+        let tokens = cluster::tokenize(message);
+        self.messages += 1;
+
+        let key = (tokens.len(), Suggester::group_name(tokens[0]));
+        let clusters = &mut self.clusters;
+        let group = self.groups.entry(key).or_insert_with(Vec::new);
+
+        let mut best: Option<(usize, f64)> = None;
+        for &index in group.iter() {
+            let similarity = clusters[index].similarity(&tokens);
+            if similarity >= self.similarity_threshold &&
+               best.map_or(true, |(_, best_similarity)| similarity > best_similarity) {
+                best = Some((index, similarity));
+            }
+        }
+
+        match best {
+            Some((index, _)) => clusters[index].add(&tokens, message, self.max_samples),
+            None => {
+                group.push(clusters.len());
+                clusters.push(Cluster::new(&tokens, message));
+            }
+        }
+    }
+
+    // Numbers don't separate the groups, like in Drain.
+    fn group_name(first_token: &str) -> String {
+        if first_token.chars().any(|c| c >= '0' && c <= '9') {
+            "*".to_owned()
+        } else {
+            first_token.to_owned()
+        }
+    }
+
+    // The number of messages which were added.
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    // The clusters with at least `min_count` messages, the biggest first.
+    pub fn clusters(&self) -> Vec<&Cluster> {
+        let mut clusters: Vec<&Cluster> = self.clusters
+                                              .iter()
+                                              .filter(|cluster| cluster.count() >= self.min_count)
+                                              .collect();
+        // the sort is stable, so the clusters with the same size remain in
+        // the order of their appearance
+        clusters.sort_by(|a, b| b.count().cmp(&a.count()));
+        clusters
+    }
+
+    // Renders the clusters as draft patterns in a YAML pattern file. The
+    // occurrence counts are written as comments.
+    pub fn to_yaml(&self) -> String {
+        let mut buffer = format!("# Draft patterns suggested from {} messages. Review them before \
+                                  use.\npatterns:\n",
+                                 self.messages);
+        let clusters = self.clusters();
+        if clusters.is_empty() {
+            buffer.push_str("  []\n");
+        }
+        for (index, cluster) in clusters.into_iter().enumerate() {
+            Draft::new(index + 1, cluster).write_yaml(&mut buffer);
+        }
+        buffer
+    }
+}
+
+impl Default for Suggester {
+    fn default() -> Self {
+        Suggester {
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            max_samples: DEFAULT_MAX_SAMPLES,
+            min_count: DEFAULT_MIN_COUNT,
+            messages: 0,
+            groups: BTreeMap::new(),
+            clusters: Vec::new(),
+        }
+    }
+}
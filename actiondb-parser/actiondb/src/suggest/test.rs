@@ -0,0 +1,66 @@
+use super::{Suggester, Token};
+
+#[test]
+fn test_given_suggester_when_messages_differ_only_in_some_tokens_then_they_form_one_cluster() {
+    let mut suggester = Suggester::new();
+    suggester.add_message("Accepted password for bob from 10.0.0.1 port 2222 ssh2");
+    suggester.add_message("Accepted password for alice from 10.0.0.2 port 3333 ssh2");
+    suggester.add_message("Connection closed by 10.0.0.1");
+
+    let clusters = suggester.clusters();
+    assert_eq!(suggester.messages(), 3);
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].count(), 2);
+    assert_eq!(clusters[0].tokens()[0], Token::Literal("Accepted".to_owned()));
+    assert_eq!(clusters[0].template(),
+               "Accepted password for <*> from <*> port <*> ssh2");
+    match clusters[0].tokens()[3] {
+        Token::Variable(ref variable) => assert!(!variable.is_numeric()),
+        ref token => panic!("Expected a variable, got {:?}", token),
+    }
+}
+
+#[test]
+fn test_given_suggester_when_messages_are_not_similar_enough_then_they_form_separate_clusters() {
+    let mut suggester = Suggester::new();
+    suggester.add_message("session opened for user bob");
+    suggester.add_message("session closed by remote host");
+
+    assert_eq!(suggester.clusters().len(), 2);
+}
+
+#[test]
+fn test_given_suggester_when_min_count_is_set_then_the_small_clusters_are_left_out() {
+    let mut suggester = Suggester::new();
+    suggester.set_min_count(2);
+    suggester.add_message("user bob logged in");
+    suggester.add_message("user alice logged in");
+    suggester.add_message("disk is full");
+
+    assert_eq!(suggester.clusters().len(), 1);
+}
+
+#[test]
+fn test_given_suggester_when_it_is_rendered_as_yaml_then_the_parsers_are_chosen_by_the_values() {
+    let mut suggester = Suggester::new();
+    suggester.set_max_samples(1);
+    suggester.add_message("Accepted password for bob from 10.0.0.1 port 2222 ssh2");
+    suggester.add_message("Accepted password for alice from 10.0.0.2 port 3333 ssh2");
+
+    let yaml = suggester.to_yaml();
+    assert!(yaml.contains("  # occurrences: 2\n"));
+    assert!(yaml.contains("    pattern: \"Accepted password for %{GREEDY:for} from \
+                           %{SET(\\\".012\\\"):from} port %{INT:port} ssh2\"\n"));
+    assert!(yaml.contains("      - message: \"Accepted password for bob from 10.0.0.1 port 2222 \
+                           ssh2\"\n"));
+    assert!(yaml.contains("          port: \"2222\"\n"));
+    assert!(!yaml.contains("alice"));
+}
+
+#[test]
+fn test_given_suggester_when_a_message_contains_a_parser_start_then_it_is_escaped() {
+    let mut suggester = Suggester::new();
+    suggester.add_message("literal %{ in message");
+
+    assert!(suggester.to_yaml().contains("pattern: \"literal \\\\%\\\\{ in message\""));
+}
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::slice;
 
 #[derive(Clone, Debug)]
 pub struct SortedVec<T> {
@@ -47,6 +48,10 @@ impl <T: Ord> SortedVec<T> {
         self.array.is_empty()
     }
 
+    pub fn iter(&self) -> slice::Iter<T> {
+        self.array.iter()
+    }
+
     pub fn binary_search_by<F>(&self, f: F) -> Result<usize, usize>
         where F: FnMut(&T) -> Ordering
     {
@@ -55,3 +55,11 @@ fn test_given_not_existing_yaml_pattern_file_when_it_is_opened_we_get_io_error()
                    .err()
                    .expect("Successfully opened a non-existing YAML file");
 }
+
+#[test]
+fn test_given_valid_patterndb_file_when_it_is_loaded_then_its_rules_are_imported_as_patterns() {
+    let file = PatternLoader::load_file("tests/file/ssh_ok.xml")
+                   .ok()
+                   .expect("Failed to import a patterndb XML file");
+    assert_eq!(file.patterns().len(), 3);
+}
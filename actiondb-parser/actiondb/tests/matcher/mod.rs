@@ -1,8 +1,14 @@
 extern crate actiondb;
 
-use actiondb::matcher::PatternLoader;
+use actiondb::Matcher;
+use actiondb::matcher::{BuildError, DuplicatePolicy, LoadOptions, PatternLoader,
+                        TestMessagePolicy};
+use actiondb::matcher::pattern::metadata::Severity;
+use actiondb::matcher::testing::TestRunner;
 use actiondb::matcher::trie::factory::TrieMatcherFactory;
 
+use std::iter;
+
 #[test]
 fn test_given_json_file_when_its_syntax_is_ok_then_matcher_can_be_built_from_it() {
     let pattern_file_path = "tests/matcher/ssh_ok.json";
@@ -90,3 +96,406 @@ fn test_given_json_file_when_we_check_the_test_messages_then_the_resulting_patte
                    .expect("The UUID of the resulting pattern should be the same as the freshly \
                             inserted one");
 }
+
+#[test]
+fn test_given_patterndb_file_when_its_examples_are_valid_then_matcher_can_be_built_from_it() {
+    let pattern_file_path = "tests/file/ssh_ok.xml";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path);
+    println!("matcher: {:?}", &matcher);
+    let _ = matcher.ok().expect("Failed to create a Matcher from a valid patterndb file");
+}
+
+#[test]
+fn test_given_directory_when_it_is_loaded_then_the_patterns_of_every_pattern_file_are_merged() {
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_ok")
+                      .ok()
+                      .expect("Failed to create a Matcher from a directory of pattern files");
+    let _ = matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .expect("Failed to parse a message with a pattern from a JSON file");
+    let _ = matcher.parse("CRON[1234]: pam_unix(cron:session): session opened for user root")
+                   .expect("Failed to parse a message with a pattern from a YAML file");
+}
+
+#[test]
+fn test_given_glob_pattern_when_it_is_loaded_then_only_the_matching_files_are_used() {
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_ok/*.yaml")
+                      .ok()
+                      .expect("Failed to create a Matcher from a glob pattern");
+    assert!(matcher.parse("CRON[1234]: pam_unix(cron:session): session opened for user root")
+                   .is_some());
+    assert!(matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .is_none());
+}
+
+#[test]
+fn test_given_glob_pattern_when_no_files_match_it_then_we_get_an_error() {
+    match PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_ok/*.nothing") {
+        Err(BuildError::NoPatternFiles(_)) => {}
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_given_directory_when_two_files_contain_the_same_uuid_then_both_files_are_reported() {
+    match PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_duplicate_uuid") {
+        Err(BuildError::DuplicateUuid { uuid, first_file, second_file }) => {
+            assert_eq!(uuid, "c11c806a-766d-4a09-9f24-7de1fe02e51e");
+            assert_eq!(first_file, "tests/matcher/dir_duplicate_uuid/a.json");
+            assert_eq!(second_file, "tests/matcher/dir_duplicate_uuid/b.json");
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_given_directory_when_two_patterns_compile_to_the_same_tokens_then_the_last_one_is_used() {
+    let pattern_file_path = "tests/matcher/dir_identical_patterns";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                      .ok()
+                      .expect("Failed to create a Matcher when two patterns are identical");
+    let result = matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected \
+                                by user")
+                        .expect("Failed to parse a message");
+    assert_eq!(result.pattern().uuid().hyphenated().to_string(),
+               "fa8bdbcb-e0fd-4da1-9fa4-15ecfec28ad2");
+}
+
+#[test]
+fn test_given_file_when_its_name_contains_glob_characters_then_it_is_loaded_as_a_file() {
+    let pattern_file_path = "tests/matcher/glob_characters[1].json";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                      .ok()
+                      .expect("Failed to create a Matcher when the file name contains a '['");
+    assert!(matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .is_some());
+}
+
+#[test]
+fn test_given_directory_when_it_contains_patterndb_files_then_they_are_loaded() {
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_pdb")
+                      .ok()
+                      .expect("Failed to create a Matcher from a directory of patterndb files");
+    assert!(matcher.parse("Accepted publickey for zts from 1.2.3.4 port 22 ssh2").is_some());
+    // the extensions are matched case-insensitively
+    assert!(matcher.parse("(root) CMD (run-parts /etc/cron.hourly)").is_some());
+}
+
+#[test]
+fn test_given_directory_when_duplicates_are_ignored_then_the_first_pattern_is_kept() {
+    let matcher = PatternLoader::from_file_ignore_errors::<TrieMatcherFactory>(
+                      "tests/matcher/dir_duplicate_uuid")
+                      .ok()
+                      .expect("Failed to create a Matcher when the errors are ignored");
+    assert!(matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .is_some());
+    assert!(matcher.parse("other sshd[26478]: Received disconnect from 10.0.0.1: 11: \
+                           disconnected by user")
+                   .is_none());
+}
+
+fn duplicate_policy(policy: DuplicatePolicy) -> LoadOptions {
+    let mut options = LoadOptions::new();
+    options.set_duplicate_policy(policy);
+    options
+}
+
+const DUPLICATE_UUID_FILES: &'static [&'static str] =
+    &["tests/matcher/dir_duplicate_uuid/a.json",
+      "tests/matcher/dir_duplicate_uuid/b.json"];
+
+#[test]
+fn test_given_more_pattern_files_when_they_are_loaded_then_their_patterns_are_merged() {
+    let paths = &["tests/matcher/dir_ok/sshd.json", "tests/matcher/dir_ok"];
+    let options = duplicate_policy(DuplicatePolicy::FirstWins);
+    let matcher = PatternLoader::from_files::<TrieMatcherFactory>(paths, &options)
+                      .ok()
+                      .expect("Failed to create a Matcher from more pattern files");
+    assert!(matcher.parse("CRON[1234]: pam_unix(cron:session): session opened for user root")
+                   .is_some());
+}
+
+#[test]
+fn test_given_more_pattern_files_when_duplicates_are_errors_then_both_files_are_reported() {
+    let options = duplicate_policy(DuplicatePolicy::Error);
+    match PatternLoader::from_files::<TrieMatcherFactory>(DUPLICATE_UUID_FILES, &options) {
+        Err(BuildError::DuplicateUuid { first_file, second_file, .. }) => {
+            assert_eq!(first_file, "tests/matcher/dir_duplicate_uuid/a.json");
+            assert_eq!(second_file, "tests/matcher/dir_duplicate_uuid/b.json");
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_given_more_pattern_files_when_the_first_duplicate_wins_then_the_first_pattern_is_kept() {
+    let options = duplicate_policy(DuplicatePolicy::FirstWins);
+    let matcher = PatternLoader::from_files::<TrieMatcherFactory>(DUPLICATE_UUID_FILES, &options)
+                      .ok()
+                      .expect("Failed to create a Matcher when the first duplicate wins");
+    assert!(matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .is_some());
+    assert!(matcher.parse("other sshd[26478]: Received disconnect from 10.0.0.1: 11: \
+                           disconnected by user")
+                   .is_none());
+}
+
+#[test]
+fn test_given_more_pattern_files_when_the_last_duplicate_wins_then_the_last_pattern_is_kept() {
+    let options = duplicate_policy(DuplicatePolicy::LastWins);
+    let matcher = PatternLoader::from_files::<TrieMatcherFactory>(DUPLICATE_UUID_FILES, &options)
+                      .ok()
+                      .expect("Failed to create a Matcher when the last duplicate wins");
+    assert!(matcher.parse("sshd[26478]: Received disconnect from 10.0.0.1: 11: disconnected by \
+                           user")
+                   .is_none());
+    assert!(matcher.parse("other sshd[26478]: Received disconnect from 10.0.0.1: 11: \
+                           disconnected by user")
+                   .is_some());
+}
+
+#[test]
+fn test_given_more_pattern_files_when_a_duplicate_wins_then_its_file_is_returned_as_its_source() {
+    let options = duplicate_policy(DuplicatePolicy::LastWins);
+    let (_, loaded) = PatternLoader::from_files_with_sources::<TrieMatcherFactory>(
+                          DUPLICATE_UUID_FILES, &options)
+                          .ok()
+                          .expect("Failed to create a Matcher when the last duplicate wins");
+    let duplicate = loaded.iter()
+                          .find(|pattern| {
+                              pattern.uuid().hyphenated().to_string() ==
+                              "c11c806a-766d-4a09-9f24-7de1fe02e51e"
+                          })
+                          .expect("The duplicated pattern is not loaded");
+    assert_eq!(duplicate.path(), "tests/matcher/dir_duplicate_uuid/b.json");
+    assert_eq!(loaded.iter().filter(|pattern| pattern.uuid() == duplicate.uuid()).count(), 1);
+}
+
+fn load_with_test_message_policy(policy: TestMessagePolicy) -> Result<(), BuildError> {
+    let mut options = LoadOptions::new();
+    options.set_test_message_policy(policy);
+    let paths = &["tests/matcher/ssh_when_an_expected_value_is_not_found_we_fail.json"];
+    PatternLoader::from_files::<TrieMatcherFactory>(paths, &options).map(|_| ())
+}
+
+#[test]
+fn test_given_failing_test_message_when_test_messages_are_strict_then_matcher_cannot_be_built() {
+    let _ = load_with_test_message_policy(TestMessagePolicy::Strict)
+                .err()
+                .expect("Built a Matcher with a failing test message");
+}
+
+#[test]
+fn test_given_failing_test_message_when_test_messages_only_warn_then_matcher_can_be_built() {
+    load_with_test_message_policy(TestMessagePolicy::Warn)
+        .ok()
+        .expect("Failed to build a Matcher when failing test messages only warn");
+    load_with_test_message_policy(TestMessagePolicy::Skip)
+        .ok()
+        .expect("Failed to build a Matcher when test messages are skipped");
+}
+
+#[test]
+fn test_given_directory_when_a_file_is_invalid_then_the_error_is_annotated_with_its_name() {
+    let error = PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/dir_wrong")
+                    .err()
+                    .expect("Failed to get an error when a directory contains an invalid file");
+    assert_eq!(error.file(), Some("tests/matcher/dir_wrong/b.json"));
+}
+
+#[test]
+fn test_given_yaml_file_when_a_pattern_is_invalid_then_the_error_points_to_its_location() {
+    let pattern_file_path = "tests/matcher/ssh_invalid_pattern.yaml";
+    let error = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                    .err()
+                    .expect("Failed to get an error when a pattern is invalid");
+    let location = error.location().expect("The location of the error is not known");
+    assert_eq!(location.path(), pattern_file_path);
+    assert_eq!(location.line(), Some(4));
+    assert_eq!(location.uuid(), Some("9a49c47d-29e9-4072-be84-3b76c6814743"));
+    assert_eq!(location.name(), Some("SSH_DISCONNECT"));
+    assert_eq!(location.offset(), Some(58));
+    let caret = location.caret().expect("Failed to render the caret");
+    let lines = caret.lines().collect::<Vec<&str>>();
+    assert_eq!(lines[0],
+               r#"    pattern: "sshd[%{INT:pid}]: Received disconnect from %{GREEDY:ipaddr: %{INT:dunno}""#);
+    let padding = iter::repeat(' ').take(14 + 58).collect::<String>();
+    assert_eq!(lines[1], format!("{}^", padding));
+}
+
+#[test]
+fn test_given_json_file_when_a_test_message_fails_then_the_error_points_to_its_pattern() {
+    let pattern_file_path = "tests/matcher/ssh_when_an_expected_value_is_not_found_we_fail.json";
+    let error = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                    .err()
+                    .expect("Failed to get an error when a test message fails");
+    let location = error.location().expect("The location of the error is not known");
+    assert_eq!(location.path(), pattern_file_path);
+    assert!(location.uuid().is_some());
+    assert!(location.line().is_some());
+}
+
+#[test]
+fn test_given_json_file_when_a_negative_test_message_is_claimed_by_an_other_pattern_then_matcher_can_be_built
+    () {
+    let pattern_file_path = "tests/matcher/negative_test_message_ok.json";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path);
+    println!("matcher: {:?}", &matcher);
+    let _ = matcher.ok().expect("Failed to create a Matcher when a negative test message is not \
+                                 matched by its pattern");
+}
+
+#[test]
+fn test_given_json_file_when_a_negative_test_message_matches_its_pattern_then_we_fail() {
+    let pattern_file_path = "tests/matcher/negative_test_message_fails.json";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path);
+    println!("matcher: {:?}", &matcher);
+    let _ = matcher.err().expect("A negative test message matched its pattern but we created the \
+                                  Matcher object");
+}
+
+#[test]
+fn test_given_json_file_when_a_test_message_is_claimed_by_the_expected_pattern_then_matcher_can_be_built
+    () {
+    let pattern_file_path = "tests/matcher/expected_uuid_ok.json";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path);
+    println!("matcher: {:?}", &matcher);
+    let _ = matcher.ok().expect("Failed to create a Matcher when a test message is claimed by \
+                                 its expected pattern");
+}
+
+#[test]
+fn test_given_json_file_when_a_test_message_is_not_claimed_by_the_expected_pattern_then_we_fail() {
+    let pattern_file_path = "tests/matcher/expected_uuid_fails.json";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path);
+    println!("matcher: {:?}", &matcher);
+    let _ = matcher.err().expect("A test message was claimed by an unexpected pattern but we \
+                                  created the Matcher object");
+}
+
+#[test]
+fn test_given_json_file_when_a_test_message_is_claimed_by_an_other_pattern_then_its_owner_is_reported
+    () {
+    let pattern_file_path = "tests/matcher/expected_uuid_fails.json";
+    let error = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                    .err()
+                    .expect("A test message was claimed by an unexpected pattern but we created \
+                             the Matcher object");
+    let location = error.location().expect("The location of the error is not known");
+    assert_eq!(location.uuid(), Some("6d2cba0c-e241-464a-89c3-8035cac8f73e"));
+    assert_eq!(location.line(), Some(4));
+}
+
+#[test]
+fn test_given_yaml_file_when_a_pattern_has_templated_values_then_they_are_evaluated() {
+    let pattern_file_path = "tests/matcher/templated_values_ok.yaml";
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                      .ok()
+                      .expect("Failed to create a Matcher when the values are valid templates");
+    let result = matcher.parse("login failure for alice from 10.0.0.2")
+                        .expect("Failed to parse a message");
+    let values = result.additional_values();
+    assert_eq!(values.get("summary").map(|value| value.as_ref()),
+               Some("login failure for alice from 10.0.0.2"));
+    assert_eq!(values.get("user_domain").map(|value| value.as_ref()),
+               Some("alice@corp"));
+}
+
+#[test]
+fn test_given_yaml_file_when_a_template_references_an_unknown_value_then_we_fail() {
+    let pattern_file_path = "tests/matcher/templated_values_unknown_reference.yaml";
+    let error = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                    .err()
+                    .expect("A template referenced an unknown value but we created the Matcher \
+                             object");
+    let location = error.location().expect("The location of the error is not known");
+    assert_eq!(location.line(), Some(6));
+}
+
+#[test]
+fn test_given_yaml_file_when_the_uuid_is_in_uppercase_then_the_error_points_to_its_location() {
+    let pattern_file_path = "tests/matcher/templated_values_uppercase_uuid.yaml";
+    let error = PatternLoader::from_file::<TrieMatcherFactory>(pattern_file_path)
+                    .err()
+                    .expect("A template referenced an unknown value but we created the Matcher \
+                             object");
+    let location = error.location().expect("The location of the error is not known");
+    assert_eq!(location.line(), Some(6));
+}
+
+#[test]
+fn test_given_yaml_file_when_a_pattern_has_metadata_then_it_is_available_in_the_match_results() {
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>("tests/matcher/metadata.yaml")
+                      .ok()
+                      .expect("Failed to create a Matcher when a pattern has metadata");
+    let result = matcher.parse("Failed password for bob from 10.0.0.1")
+                        .expect("Failed to parse a message");
+    let metadata = result.metadata().expect("The metadata of the pattern is not available");
+    assert_eq!(metadata.description(), Some("A password authentication failed"));
+    assert_eq!(metadata.severity(), Some(Severity::Warning));
+    assert_eq!(metadata.references().map(|references| references.len()), Some(2));
+    assert!(!metadata.is_deprecated());
+    assert_eq!(metadata.owner(), Some("secops"));
+}
+
+#[test]
+fn test_given_pattern_files_when_their_test_messages_are_run_then_every_result_is_reported() {
+    let pattern_file_paths = ["tests/matcher/ssh_ok.json",
+                              "tests/matcher/negative_test_message_fails.json"];
+    let report = TestRunner::run::<TrieMatcherFactory>(&pattern_file_paths);
+    assert!(report.errors().is_empty());
+    assert_eq!(report.patterns().len(), 4);
+    assert_eq!(report.passed(), 1);
+    assert_eq!(report.failed(), 1);
+    assert!(!report.is_success());
+
+    let failed = report.patterns().iter().find(|pattern| !pattern.is_success()).unwrap();
+    assert_eq!(failed.file(), "tests/matcher/negative_test_message_fails.json");
+    assert_eq!(failed.name(), Some("USER_LOGIN"));
+}
+
+#[test]
+fn test_given_pattern_files_when_one_of_them_cannot_be_loaded_then_the_others_are_still_run() {
+    let report = TestRunner::run::<TrieMatcherFactory>(&["tests/matcher/ssh_wrong.json",
+                                                         "tests/matcher/ssh_ok.json"]);
+    assert_eq!(report.errors().len(), 1);
+    assert_eq!(report.errors()[0].file(), Some("tests/matcher/ssh_wrong.json"));
+    assert_eq!(report.passed(), 1);
+    assert!(!report.is_success());
+}
+
+const IDENTICAL_PATTERNS_FILE: &'static str = "tests/matcher/identical_patterns_in_one_file.json";
+const IDENTICAL_PATTERNS_MESSAGE: &'static str = "sshd[26478]: Received disconnect from \
+                                                  10.0.0.1: 11: disconnected by user";
+
+// Identical patterns used to be accepted, so they are only reported.
+#[test]
+fn test_given_json_file_when_it_contains_identical_patterns_then_it_is_still_loaded_by_default() {
+    let matcher = PatternLoader::from_file::<TrieMatcherFactory>(IDENTICAL_PATTERNS_FILE)
+                      .ok()
+                      .expect("Failed to create a Matcher from a file with identical patterns");
+    let result = matcher.parse(IDENTICAL_PATTERNS_MESSAGE).expect("Failed to parse a message");
+    assert_eq!(result.pattern().uuid().hyphenated().to_string(),
+               "fa8bdbcb-e0fd-4da1-9fa4-15ecfec28ad2");
+}
+
+#[test]
+fn test_given_json_file_when_it_contains_identical_patterns_then_the_duplicate_policy_decides() {
+    let uuid_of_match = |policy| -> String {
+        let options = duplicate_policy(policy);
+        let matcher = PatternLoader::from_files::<TrieMatcherFactory>(&[IDENTICAL_PATTERNS_FILE],
+                                                                      &options)
+                          .ok()
+                          .expect("Failed to create a Matcher when duplicates are allowed");
+        let result = matcher.parse(IDENTICAL_PATTERNS_MESSAGE).expect("Failed to parse a message");
+        result.pattern().uuid().hyphenated().to_string()
+    };
+    assert_eq!(uuid_of_match(DuplicatePolicy::FirstWins),
+               "c11c806a-766d-4a09-9f24-7de1fe02e51e");
+    assert_eq!(uuid_of_match(DuplicatePolicy::LastWins),
+               "fa8bdbcb-e0fd-4da1-9fa4-15ecfec28ad2");
+}
@@ -1,17 +1,42 @@
 extern crate actiondb;
+#[macro_use]
 extern crate clap;
+extern crate serde_json;
+extern crate flate2;
+extern crate uuid;
 #[macro_use]
 extern crate log;
 
+mod convert;
+mod coverage;
+mod diff;
+mod export;
+mod format;
+mod gen_tests;
+mod graph;
+mod lint;
 mod logger;
 mod parse;
+mod repl;
+mod suggest;
+mod test;
 
 use clap::{Arg, App, SubCommand, ArgMatches};
 use actiondb::matcher::PatternLoader;
+use actiondb::lint::{Linter, Rule, Severity};
 use actiondb::matcher::suffix_array::SuffixArrayMatcherSuite;
 use actiondb::matcher::MatcherSuite;
+use actiondb::preprocess::Preprocessor;
 use log::LogLevelFilter;
-use self::logger::StdoutLogger;
+use self::convert::FileFormat;
+use self::coverage::{CoverageOptions, ReportFormat};
+use self::diff::{DiffFormat, DiffOptions};
+use self::format::OutputFormat;
+use self::gen_tests::GenTestsOptions;
+use self::graph::GraphFormat;
+use self::logger::StderrLogger;
+use self::parse::{ParseOptions, STANDARD_STREAM};
+use self::suggest::SuggestOptions;
 
 const AUTHOR: &'static str = "Tibor Benke <tibor.benke@balabit.com>";
 const APPNAME: &'static str = "adbtool";
@@ -23,6 +48,39 @@ const PARSE: &'static str = "parse";
 const IGNORE_ERRORS: &'static str = "ignore-errors";
 const INPUT_FILE: &'static str = "input file";
 const OUTPUT_FILE: &'static str = "output file";
+const EXPORT: &'static str = "export";
+const PROGRAM: &'static str = "program";
+const TEST: &'static str = "test";
+const JUNIT: &'static str = "junit";
+const JSON: &'static str = "json";
+const UNMATCHED_FILE: &'static str = "unmatched";
+const FORMAT: &'static str = "format";
+const SUGGEST: &'static str = "suggest";
+const PATTERNS: &'static str = "patterns";
+const SIMILARITY: &'static str = "similarity";
+const SAMPLES: &'static str = "samples";
+const MIN_COUNT: &'static str = "min-count";
+const COVERAGE: &'static str = "coverage";
+const SHAPES: &'static str = "shapes";
+const CONVERT: &'static str = "convert";
+const TO: &'static str = "to";
+const FMT: &'static str = "fmt";
+const CHECK: &'static str = "check";
+const LINT: &'static str = "lint";
+const ALLOW: &'static str = "allow";
+const WARN: &'static str = "warn";
+const DENY: &'static str = "deny";
+const DIFF: &'static str = "diff";
+const OLD_PATTERN_FILE: &'static str = "old pattern file";
+const NEW_PATTERN_FILE: &'static str = "new pattern file";
+const EXAMPLES: &'static str = "examples";
+const REPL: &'static str = "repl";
+const GRAPH: &'static str = "graph";
+const JOBS: &'static str = "jobs";
+const GEN_TESTS: &'static str = "gen-tests";
+const FORCE: &'static str = "force";
+const IN_PLACE: &'static str = "in-place";
+const PREPROCESS: &'static str = "preprocess";
 
 fn build_command_line_argument_parser<'a, 'b>() -> App<'a, 'b> {
     let version = env!("CARGO_PKG_VERSION");
@@ -55,11 +113,332 @@ fn build_command_line_argument_parser<'a, 'b>() -> App<'a, 'b> {
                         .arg(Arg::with_name(INPUT_FILE)
                                  .required(true)
                                  .index(2)
-                                 .help("The input file to be parsed"))
+                                 .help("The input file to be parsed, '-' means the standard \
+                                        input, .gz files are decompressed"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .index(3)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The output file where the results are written, '-' \
+                                        means the standard output"))
+                        .arg(Arg::with_name(UNMATCHED_FILE)
+                                 .short("u")
+                                 .long("unmatched")
+                                 .takes_value(true)
+                                 .help("The file where the lines without a match are copied"))
+                        .arg(Arg::with_name(FORMAT)
+                                 .short("f")
+                                 .long("format")
+                                 .takes_value(true)
+                                 .possible_values(&["json", "csv", "debug"])
+                                 .default_value("json")
+                                 .help("The format of the results"))
+                        .arg(Arg::with_name(JOBS)
+                                 .short("j")
+                                 .long("jobs")
+                                 .takes_value(true)
+                                 .default_value("1")
+                                 .validator(is_positive_integer)
+                                 .help("The number of threads parsing the input, the results \
+                                        are written in the order of the input"))
+                        .arg(preprocess_argument()))
+        .subcommand(SubCommand::with_name(EXPORT)
+                        .about("exports a pattern file as a syslog-ng patterndb XML file")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The pattern file to be exported"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .required(true)
+                                 .index(2)
+                                 .help("The patterndb XML file to be written"))
+                        .arg(Arg::with_name(PROGRAM)
+                                 .short("p")
+                                 .long("program")
+                                 .takes_value(true)
+                                 .help("The program name of the generated ruleset")))
+        .subcommand(SubCommand::with_name(TEST)
+                        .about("runs every test message of the pattern files")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .multiple(true)
+                                 .index(1)
+                                 .help("The pattern files, directories or globs to be tested"))
+                        .arg(Arg::with_name(JUNIT)
+                                 .long("junit")
+                                 .takes_value(true)
+                                 .help("The file where a JUnit XML report is written"))
+                        .arg(Arg::with_name(JSON)
+                                 .long("json")
+                                 .takes_value(true)
+                                 .help("The file where a JSON report is written"))
+                        .arg(preprocess_argument()))
+        .subcommand(SubCommand::with_name(SUGGEST)
+                        .about("clusters messages and suggests draft patterns for them")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(INPUT_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The messages to be clustered, '-' means the standard \
+                                        input"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .index(2)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The YAML pattern file where the draft patterns are \
+                                        written, '-' means the standard output"))
+                        .arg(Arg::with_name(PATTERNS)
+                                 .short("p")
+                                 .long("patterns")
+                                 .takes_value(true)
+                                 .help("Only the messages which aren't matched by this pattern \
+                                        file are clustered"))
+                        .arg(Arg::with_name(SIMILARITY)
+                                 .long("similarity")
+                                 .takes_value(true)
+                                 .default_value("0.5")
+                                 .help("The ratio of the equal tokens which is required to \
+                                        add a message to a cluster"))
+                        .arg(Arg::with_name(SAMPLES)
+                                 .long("samples")
+                                 .takes_value(true)
+                                 .default_value("3")
+                                 .help("The maximum number of test messages of a draft pattern"))
+                        .arg(Arg::with_name(MIN_COUNT)
+                                 .long("min-count")
+                                 .takes_value(true)
+                                 .default_value("1")
+                                 .help("The minimum number of messages of a draft pattern")))
+        .subcommand(SubCommand::with_name(COVERAGE)
+                        .about("reports how much of a corpus is matched by the patterns")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The pattern file which contains predefined patterns"))
+                        .arg(Arg::with_name(INPUT_FILE)
+                                 .required(true)
+                                 .index(2)
+                                 .help("The corpus to be parsed, '-' means the standard input"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .index(3)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The file where the report is written, '-' means the \
+                                        standard output"))
+                        .arg(Arg::with_name(FORMAT)
+                                 .short("f")
+                                 .long("format")
+                                 .takes_value(true)
+                                 .possible_values(&["table", "json"])
+                                 .default_value("table")
+                                 .help("The format of the report"))
+                        .arg(Arg::with_name(SHAPES)
+                                 .long("shapes")
+                                 .takes_value(true)
+                                 .default_value("10")
+                                 .help("The number of the most frequent unmatched message \
+                                        shapes to report"))
+                        .arg(preprocess_argument()))
+        .subcommand(SubCommand::with_name(CONVERT)
+                        .about("converts a pattern file between the JSON, YAML and patterndb \
+                                formats")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(INPUT_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The pattern file to be converted"))
                         .arg(Arg::with_name(OUTPUT_FILE)
+                                 .index(2)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The converted pattern file, '-' means the standard \
+                                        output"))
+                        .arg(Arg::with_name(TO)
+                                 .short("t")
+                                 .long("to")
+                                 .takes_value(true)
+                                 .possible_values(&["json", "yaml", "pdb"])
+                                 .help("The output format, by default it's chosen by the \
+                                        extension of the output file"))
+                        .arg(Arg::with_name(PROGRAM)
+                                 .short("p")
+                                 .long("program")
+                                 .takes_value(true)
+                                 .help("The program name of the generated patterndb ruleset")))
+        .subcommand(SubCommand::with_name(FMT)
+                        .about("rewrites pattern files in a canonical form")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .multiple(true)
+                                 .index(1)
+                                 .help("The pattern files, directories or globs to be formatted"))
+                        .arg(Arg::with_name(CHECK)
+                                 .long("check")
+                                 .help("Don't modify the files, exit with an error if one of \
+                                        them is not formatted")))
+        .subcommand(SubCommand::with_name(LINT)
+                        .about("checks pattern files for common mistakes")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .multiple(true)
+                                 .index(1)
+                                 .help("The pattern files, directories or globs to be checked"))
+                        .arg(lint_rule_argument(ALLOW, "A", "Suppresses a rule"))
+                        .arg(lint_rule_argument(WARN, "W", "Reports a rule as a warning"))
+                        .arg(lint_rule_argument(DENY, "D", "Reports a rule as an error")))
+        .subcommand(SubCommand::with_name(DIFF)
+                        .about("reports the messages whose classification is changed by a new \
+                                version of the patterns")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(OLD_PATTERN_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The old version of the pattern file"))
+                        .arg(Arg::with_name(NEW_PATTERN_FILE)
+                                 .required(true)
+                                 .index(2)
+                                 .help("The new version of the pattern file"))
+                        .arg(Arg::with_name(INPUT_FILE)
                                  .required(true)
                                  .index(3)
-                                 .help("The output file where the results are written")))
+                                 .help("The corpus to be parsed, '-' means the standard input"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .short("o")
+                                 .long("output")
+                                 .takes_value(true)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The file where the report is written, '-' means the \
+                                        standard output"))
+                        .arg(Arg::with_name(FORMAT)
+                                 .short("f")
+                                 .long("format")
+                                 .takes_value(true)
+                                 .possible_values(&["text", "json"])
+                                 .default_value("text")
+                                 .help("The format of the report"))
+                        .arg(Arg::with_name(EXAMPLES)
+                                 .long("examples")
+                                 .takes_value(true)
+                                 .default_value("3")
+                                 .help("The maximum number of example messages of a change")))
+        .subcommand(SubCommand::with_name(REPL)
+                        .about("starts an interactive shell to develop patterns")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .index(1)
+                                 .help("The JSON or YAML pattern file where the accepted \
+                                        patterns are saved, its patterns are loaded if it \
+                                        exists")))
+        .subcommand(SubCommand::with_name(GRAPH)
+                        .about("shows how the matcher is built from the patterns")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The pattern file"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .short("o")
+                                 .long("output")
+                                 .takes_value(true)
+                                 .default_value(STANDARD_STREAM)
+                                 .help("The output file, '-' means the standard output"))
+                        .arg(Arg::with_name(FORMAT)
+                                 .short("f")
+                                 .long("format")
+                                 .takes_value(true)
+                                 .possible_values(&["text", "dot"])
+                                 .default_value("text")
+                                 .help("The format of the output, 'dot' is a Graphviz graph")))
+        .subcommand(SubCommand::with_name(GEN_TESTS)
+                        .about("adds sample messages to the patterns as test messages and \
+                                writes the reformatted pattern file")
+                        .version(version)
+                        .author(AUTHOR)
+                        .arg(Arg::with_name(PATTERN_FILE)
+                                 .required(true)
+                                 .index(1)
+                                 .help("The JSON or YAML pattern file"))
+                        .arg(Arg::with_name(INPUT_FILE)
+                                 .required(true)
+                                 .index(2)
+                                 .help("The sample messages, '-' means the standard input"))
+                        .arg(Arg::with_name(OUTPUT_FILE)
+                                 .short("o")
+                                 .long("output")
+                                 .takes_value(true)
+                                 .help("The file where the reformatted pattern file is \
+                                        written, '-' means the standard output")
+                                 .default_value(STANDARD_STREAM))
+                        .arg(Arg::with_name(IN_PLACE)
+                                 .short("i")
+                                 .long("in-place")
+                                 .conflicts_with(OUTPUT_FILE)
+                                 .help("Overwrites the pattern file. It's refused unless the \
+                                        file is in the canonical form of fmt, so only its test \
+                                        messages are changed"))
+                        .arg(Arg::with_name(SAMPLES)
+                                 .long("samples")
+                                 .takes_value(true)
+                                 .default_value("3")
+                                 .help("The maximum number of test messages generated for a \
+                                        pattern"))
+                        .arg(Arg::with_name(FORCE)
+                                 .long("force")
+                                 .help("Adds test messages to the patterns which already have \
+                                        some")))
+}
+
+fn lint_rule_argument<'a, 'b>(name: &'static str,
+                              short: &str,
+                              help: &'static str)
+                              -> Arg<'a, 'b> {
+    let rules: Vec<&'static str> = Rule::all().iter().map(|rule| rule.as_str()).collect();
+    Arg::with_name(name)
+        .short(short)
+        .long(name)
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .possible_values(&rules)
+        .help(help)
+}
+
+fn preprocess_argument<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(PREPROCESS)
+        .long("preprocess")
+        .takes_value(true)
+        .help("A comma separated list of the preprocessing steps (trim, strip-control, \
+               normalize-whitespace and first-line) which are applied to the messages \
+               before they are parsed")
+}
+
+fn is_positive_integer(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("The value must be positive".to_owned()),
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("The value is not a positive integer: {}", value)),
+    }
+}
+
+fn preprocessor(matches: &ArgMatches) -> Preprocessor {
+    match Preprocessor::from_list(matches.value_of(PREPROCESS).unwrap_or("")) {
+        Ok(preprocessor) => preprocessor,
+        Err(step) => {
+            error!("Unknown preprocessing step: {}", step);
+            std::process::exit(1);
+        }
+    }
 }
 
 fn handle_validate<MS: MatcherSuite>(matches: &ArgMatches) {
@@ -72,26 +451,199 @@ fn handle_validate<MS: MatcherSuite>(matches: &ArgMatches) {
     };
 
     if let Err(e) = matcher {
-        error!("{}", e);
+        error!("{}", e.report());
         std::process::exit(1);
     }
 }
 
 fn handle_parse<MS: MatcherSuite>(matches: &ArgMatches) {
+    let options = ParseOptions {
+        pattern_file: matches.value_of(PATTERN_FILE).unwrap(),
+        input_file: matches.value_of(INPUT_FILE).unwrap(),
+        output_file: matches.value_of(OUTPUT_FILE).unwrap(),
+        unmatched_file: matches.value_of(UNMATCHED_FILE),
+        format: OutputFormat::from_str(matches.value_of(FORMAT).unwrap()).unwrap(),
+        jobs: value_t_or_exit!(matches, JOBS, usize),
+        preprocessor: preprocessor(matches),
+    };
+
+    if let Err(e) = parse::parse::<MS>(&options) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_export(matches: &ArgMatches) {
     let pattern_file = matches.value_of(PATTERN_FILE).unwrap();
+    let output_file = matches.value_of(OUTPUT_FILE).unwrap();
+    let program = matches.value_of(PROGRAM);
+
+    if let Err(e) = export::export(pattern_file, output_file, program) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_test<MS: MatcherSuite>(matches: &ArgMatches) {
+    let pattern_files: Vec<&str> = matches.values_of(PATTERN_FILE).unwrap().collect();
+    let junit_file = matches.value_of(JUNIT);
+    let json_file = matches.value_of(JSON);
+
+    match test::test::<MS>(&pattern_files, junit_file, json_file, &preprocessor(matches)) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_suggest<MS: MatcherSuite>(matches: &ArgMatches) {
+    let options = SuggestOptions {
+        input_file: matches.value_of(INPUT_FILE).unwrap(),
+        output_file: matches.value_of(OUTPUT_FILE).unwrap(),
+        pattern_file: matches.value_of(PATTERNS),
+        similarity_threshold: value_t_or_exit!(matches, SIMILARITY, f64),
+        max_samples: value_t_or_exit!(matches, SAMPLES, usize),
+        min_count: value_t_or_exit!(matches, MIN_COUNT, usize),
+    };
+
+    if let Err(e) = suggest::suggest::<MS>(&options) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_coverage<MS: MatcherSuite>(matches: &ArgMatches) {
+    let options = CoverageOptions {
+        pattern_file: matches.value_of(PATTERN_FILE).unwrap(),
+        input_file: matches.value_of(INPUT_FILE).unwrap(),
+        output_file: matches.value_of(OUTPUT_FILE).unwrap(),
+        format: ReportFormat::from_str(matches.value_of(FORMAT).unwrap()).unwrap(),
+        shapes: value_t_or_exit!(matches, SHAPES, usize),
+        preprocessor: preprocessor(matches),
+    };
+
+    if let Err(e) = coverage::coverage::<MS>(&options) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_convert(matches: &ArgMatches) {
     let input_file = matches.value_of(INPUT_FILE).unwrap();
     let output_file = matches.value_of(OUTPUT_FILE).unwrap();
+    let format = matches.value_of(TO).and_then(FileFormat::from_str);
+    let program = matches.value_of(PROGRAM);
+
+    if let Err(e) = convert::convert(input_file, output_file, format, program) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_fmt(matches: &ArgMatches) {
+    let pattern_files: Vec<&str> = matches.values_of(PATTERN_FILE).unwrap().collect();
+    let check = matches.is_present(CHECK);
+
+    match convert::fmt(&pattern_files, check) {
+        Ok(formatted) => {
+            if check && !formatted {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_lint(matches: &ArgMatches) {
+    let pattern_files: Vec<&str> = matches.values_of(PATTERN_FILE).unwrap().collect();
+    let mut linter = Linter::new();
+
+    for &(name, severity) in &[(WARN, Some(Severity::Warning)),
+                               (DENY, Some(Severity::Error)),
+                               (ALLOW, None)] {
+        for rule in matches.values_of(name).into_iter().flat_map(|rules| rules) {
+            let rule = Rule::from_str(rule).unwrap();
+            match severity {
+                Some(severity) => linter.set_severity(rule, severity),
+                None => linter.allow(rule),
+            }
+        }
+    }
+
+    match lint::lint(&pattern_files, &linter) {
+        Ok(false) => {}
+        Ok(true) => std::process::exit(1),
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn handle_diff<MS: MatcherSuite>(matches: &ArgMatches) {
+    let options = DiffOptions {
+        old_pattern_file: matches.value_of(OLD_PATTERN_FILE).unwrap(),
+        new_pattern_file: matches.value_of(NEW_PATTERN_FILE).unwrap(),
+        input_file: matches.value_of(INPUT_FILE).unwrap(),
+        output_file: matches.value_of(OUTPUT_FILE).unwrap(),
+        format: DiffFormat::from_str(matches.value_of(FORMAT).unwrap()).unwrap(),
+        max_examples: value_t_or_exit!(matches, EXAMPLES, usize),
+    };
+
+    if let Err(e) = diff::diff::<MS>(&options) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_repl<MS: MatcherSuite>(matches: &ArgMatches) {
+    if let Err(e) = repl::repl::<MS>(matches.value_of(PATTERN_FILE)) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_graph<MS: MatcherSuite>(matches: &ArgMatches) {
+    let pattern_file = matches.value_of(PATTERN_FILE).unwrap();
+    let output_file = matches.value_of(OUTPUT_FILE).unwrap();
+    let format = GraphFormat::from_str(matches.value_of(FORMAT).unwrap()).unwrap();
+
+    if let Err(e) = graph::graph::<MS>(pattern_file, output_file, format) {
+        error!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn handle_gen_tests<MS: MatcherSuite>(matches: &ArgMatches) {
+    let pattern_file = matches.value_of(PATTERN_FILE).unwrap();
+    let options = GenTestsOptions {
+        pattern_file: pattern_file,
+        input_file: matches.value_of(INPUT_FILE).unwrap(),
+        output_file: if matches.is_present(IN_PLACE) {
+            pattern_file
+        } else {
+            matches.value_of(OUTPUT_FILE).unwrap()
+        },
+        max_samples: value_t_or_exit!(matches, SAMPLES, usize),
+        force: matches.is_present(FORCE),
+    };
 
-    if let Err(e) = parse::parse::<MS>(pattern_file, input_file, output_file) {
+    if let Err(e) = gen_tests::gen_tests::<MS>(&options) {
         error!("{}", e);
         std::process::exit(1);
     }
 }
 
-fn setup_stdout_logger(log_level: LogLevelFilter) {
+fn setup_stderr_logger(log_level: LogLevelFilter) {
     let _ = log::set_logger(|max_log_level| {
         max_log_level.set(log_level);
-        Box::new(StdoutLogger)
+        Box::new(StderrLogger)
     });
 }
 
@@ -108,6 +660,28 @@ fn process_command_line_args<'a, MS: MatcherSuite>(matches: ArgMatches<'a>) {
         handle_validate::<MS>(&matches);
     } else if let Some(matches) = matches.subcommand_matches(PARSE) {
         handle_parse::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(EXPORT) {
+        handle_export(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(TEST) {
+        handle_test::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(SUGGEST) {
+        handle_suggest::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(COVERAGE) {
+        handle_coverage::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(CONVERT) {
+        handle_convert(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(FMT) {
+        handle_fmt(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(LINT) {
+        handle_lint(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(DIFF) {
+        handle_diff::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(REPL) {
+        handle_repl::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(GRAPH) {
+        handle_graph::<MS>(&matches);
+    } else if let Some(matches) = matches.subcommand_matches(GEN_TESTS) {
+        handle_gen_tests::<MS>(&matches);
     } else {
         error!("{}", matches.usage.as_ref().unwrap());
     }
@@ -116,6 +690,6 @@ fn process_command_line_args<'a, MS: MatcherSuite>(matches: ArgMatches<'a>) {
 fn main() {
     let matches = build_command_line_argument_parser().get_matches();
     let log_level = choose_log_level(&matches);
-    setup_stdout_logger(log_level);
+    setup_stderr_logger(log_level);
     process_command_line_args::<SuffixArrayMatcherSuite>(matches);
 }
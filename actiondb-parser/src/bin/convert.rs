@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::path::Path;
+use actiondb::matcher::PatternLoader;
+use actiondb::matcher::pattern::file::PatternFile;
+use actiondb::pdb::Exporter;
+use parse::{open_output, STANDARD_STREAM};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Yaml,
+    Pdb,
+}
+
+impl FileFormat {
+    // The formats and the extensions are matched case-insensitively.
+    pub fn from_str(format: &str) -> Option<FileFormat> {
+        match &format.to_lowercase()[..] {
+            "json" => Some(FileFormat::Json),
+            "yaml" | "yml" => Some(FileFormat::Yaml),
+            "xml" | "pdb" => Some(FileFormat::Pdb),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<FileFormat> {
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(FileFormat::from_str)
+    }
+}
+
+// Converts a pattern file into an other format. The format is chosen by the
+// extension of the output file unless it's given explicitly.
+pub fn convert(input_file: &str,
+               output_file: &str,
+               format: Option<FileFormat>,
+               program: Option<&str>)
+               -> Result<(), Error> {
+    let format = try!(format.or_else(|| FileFormat::from_path(output_file)).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidInput,
+                   format!("Failed to determine the output format of {}, use the --to option",
+                           output_file))
+    }));
+    let file = try!(load(input_file));
+    let content = try!(render(&file, format, program));
+    let mut output = try!(open_output(output_file));
+    output.write_all(content.as_bytes())
+}
+
+// Rewrites the pattern files in a canonical form. If `check` is set, the
+// files are not modified, only the ones which are not in the canonical form
+// are reported. Returns whether every file was in the canonical form.
+pub fn fmt(pattern_files: &[&str], check: bool) -> Result<bool, Error> {
+    let mut formatted = true;
+
+    for pattern_file in pattern_files {
+        let paths = try!(PatternLoader::resolve(pattern_file).map_err(|err| {
+            Error::new(ErrorKind::Other, err.report())
+        }));
+        for path in paths {
+            let format = match FileFormat::from_path(&path) {
+                Some(FileFormat::Pdb) | None => {
+                    warn!("Only JSON and YAML pattern files can be formatted: {}", path);
+                    continue;
+                }
+                Some(format) => format,
+            };
+            let file = try!(load(&path));
+            let content = try!(render(&file, format, None));
+            if try!(read(&path)) == content {
+                continue;
+            }
+            formatted = false;
+            if check {
+                warn!("The pattern file is not formatted: {}", path);
+            } else {
+                info!("Formatting pattern file: {}", path);
+                let mut output = try!(File::create(&path));
+                try!(output.write_all(content.as_bytes()));
+            }
+        }
+    }
+    Ok(formatted)
+}
+
+pub fn load(path: &str) -> Result<PatternFile, Error> {
+    if path == STANDARD_STREAM {
+        return Err(Error::new(ErrorKind::InvalidInput,
+                              "The pattern file cannot be read from the standard input"));
+    }
+    PatternLoader::load_file_with_location(path).map_err(|err| {
+        Error::new(ErrorKind::Other,
+                   format!("Failed to load the pattern file: {}",
+                           err.report()))
+    })
+}
+
+pub fn read(path: &str) -> Result<String, Error> {
+    let mut content = String::new();
+    let mut file = try!(File::open(path));
+    try!(file.read_to_string(&mut content));
+    Ok(content)
+}
+
+pub fn render(file: &PatternFile,
+              format: FileFormat,
+              program: Option<&str>)
+              -> Result<String, Error> {
+    let content = match format {
+        FileFormat::Json => file.to_json().map_err(|err| err.to_string()),
+        FileFormat::Yaml => file.to_yaml().map_err(|err| err.to_string()),
+        FileFormat::Pdb => {
+            let mut exporter = Exporter::new();
+            exporter.set_program(program.map(|program| program.to_owned()));
+            exporter.export(file)
+                    .map(|export| {
+                        for untranslatable in export.untranslatable() {
+                            warn!("{}", untranslatable);
+                        }
+                        export.into_document()
+                    })
+                    .map_err(|err| err.to_string())
+        }
+    };
+    content.map(|content| format!("{}\n", content)).map_err(|err| {
+        Error::new(ErrorKind::Other,
+                   format!("Failed to serialize the pattern file: {}", err))
+    })
+}
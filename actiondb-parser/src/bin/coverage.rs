@@ -0,0 +1,67 @@
+use std::io::{Error, ErrorKind, Write};
+use actiondb::coverage::Coverage;
+use actiondb::matcher::{LoadOptions, PatternLoader, MatcherSuite};
+use actiondb::preprocess::Preprocessor;
+use parse::{open_input, open_output};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+impl ReportFormat {
+    pub fn from_str(format: &str) -> Option<ReportFormat> {
+        match format {
+            "table" => Some(ReportFormat::Table),
+            "json" => Some(ReportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct CoverageOptions<'a> {
+    pub pattern_file: &'a str,
+    pub input_file: &'a str,
+    pub output_file: &'a str,
+    pub format: ReportFormat,
+    // the number of the most frequent unmatched message shapes to report
+    pub shapes: usize,
+    pub preprocessor: Preprocessor,
+}
+
+pub fn coverage<MS: MatcherSuite>(options: &CoverageOptions) -> Result<(), Error> {
+    let mut coverage = Coverage::new();
+    let paths = [options.pattern_file];
+    let (matcher, loaded) =
+        try!(PatternLoader::from_files_with_sources::<MS::MatcherFactory>(&paths,
+                                                                          &LoadOptions::new())
+                 .map_err(|err| {
+                     Error::new(ErrorKind::Other,
+                                format!("Failed to load the pattern file: {}",
+                                        err.report()))
+                 }));
+    // every pattern is registered before the corpus is parsed, so the ones
+    // without hits are reported, too
+    for pattern in loaded {
+        coverage.add_pattern(pattern.uuid().clone(),
+                             pattern.name().map(|name| name.to_owned()));
+    }
+
+    for line in try!(open_input(options.input_file)).lines() {
+        let line = try!(line);
+        coverage.parse(&matcher, &options.preprocessor.process(&line));
+    }
+
+    let report = match options.format {
+        ReportFormat::Table => coverage.to_table(options.shapes),
+        ReportFormat::Json => {
+            try!(coverage.to_json(options.shapes).map_err(|err| {
+                Error::new(ErrorKind::Other,
+                           format!("Failed to create the JSON report: {}", err))
+            }))
+        }
+    };
+    let mut output = try!(open_output(options.output_file));
+    output.write_all(report.as_bytes())
+}
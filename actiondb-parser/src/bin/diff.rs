@@ -0,0 +1,62 @@
+use std::io::{Error, ErrorKind, Write};
+use actiondb::diff::Diff;
+use actiondb::matcher::{PatternLoader, MatcherSuite};
+use parse::{open_input, open_output};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffFormat {
+    Text,
+    Json,
+}
+
+impl DiffFormat {
+    pub fn from_str(format: &str) -> Option<DiffFormat> {
+        match format {
+            "text" => Some(DiffFormat::Text),
+            "json" => Some(DiffFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct DiffOptions<'a> {
+    pub old_pattern_file: &'a str,
+    pub new_pattern_file: &'a str,
+    pub input_file: &'a str,
+    pub output_file: &'a str,
+    pub format: DiffFormat,
+    pub max_examples: usize,
+}
+
+pub fn diff<MS: MatcherSuite>(options: &DiffOptions) -> Result<(), Error> {
+    let old = try!(load::<MS>(options.old_pattern_file));
+    let new = try!(load::<MS>(options.new_pattern_file));
+    let mut diff = Diff::new();
+    diff.set_max_examples(options.max_examples);
+
+    for line in try!(open_input(options.input_file)).lines() {
+        let line = try!(line);
+        diff.compare(&old, &new, &line);
+    }
+
+    let report = match options.format {
+        DiffFormat::Text => diff.to_text(),
+        DiffFormat::Json => {
+            try!(diff.to_json().map_err(|err| {
+                Error::new(ErrorKind::Other,
+                           format!("Failed to create the JSON report: {}", err))
+            }))
+        }
+    };
+    let mut output = try!(open_output(options.output_file));
+    output.write_all(report.as_bytes())
+}
+
+fn load<MS: MatcherSuite>(pattern_file: &str) -> Result<MS::Matcher, Error> {
+    PatternLoader::from_file::<MS::MatcherFactory>(pattern_file).map_err(|err| {
+        Error::new(ErrorKind::Other,
+                   format!("Failed to load the pattern file {}: {}",
+                           pattern_file,
+                           err.report()))
+    })
+}
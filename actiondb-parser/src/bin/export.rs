@@ -0,0 +1,34 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+use actiondb::matcher::PatternLoader;
+use actiondb::pdb::Exporter;
+
+pub fn export(pattern_file_path: &str,
+              output_file_path: &str,
+              program: Option<&str>)
+              -> Result<(), Error> {
+    let file = match PatternLoader::load_file_with_location(pattern_file_path) {
+        Ok(file) => file,
+        Err(err) => {
+            return Err(Error::new(ErrorKind::Other,
+                                  format!("Failed to load the pattern file: {}", err.report())))
+        }
+    };
+
+    let mut exporter = Exporter::new();
+    exporter.set_program(program.map(|program| program.to_owned()));
+
+    match exporter.export(&file) {
+        Ok(export) => {
+            for untranslatable in export.untranslatable() {
+                warn!("{}", untranslatable);
+            }
+            let mut output_file = try!(File::create(output_file_path));
+            output_file.write_all(export.document().as_bytes())
+        }
+        Err(err) => {
+            Err(Error::new(ErrorKind::Other,
+                           format!("Failed to export the pattern file: {}", err)))
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Write};
+use actiondb::matcher::result::MatchResult;
+use serde_json::{self, Value};
+use serde_json::builder::{ArrayBuilder, ObjectBuilder};
+
+const CSV_HEADER: &'static str = "message,uuid,name,tags,values";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Debug,
+}
+
+impl OutputFormat {
+    pub fn from_str(format: &str) -> Option<OutputFormat> {
+        match format {
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            "debug" => Some(OutputFormat::Debug),
+            _ => None,
+        }
+    }
+
+    pub fn write_header<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        match *self {
+            OutputFormat::Csv => writeln!(writer, "{}", CSV_HEADER),
+            OutputFormat::Json | OutputFormat::Debug => Ok(()),
+        }
+    }
+
+    // Writes the result of parsing one line. Every result takes exactly one
+    // line in the output.
+    pub fn write_result<W: Write>(&self,
+                                  writer: &mut W,
+                                  line: &str,
+                                  result: Option<&MatchResult>)
+                                  -> Result<(), Error> {
+        match *self {
+            OutputFormat::Json => {
+                let json = try!(serde_json::to_string(&to_json(line, result)).map_err(|err| {
+                    Error::new(ErrorKind::Other, err.to_string())
+                }));
+                writeln!(writer, "{}", json)
+            }
+            OutputFormat::Csv => writeln!(writer, "{}", to_csv(line, result)),
+            OutputFormat::Debug => writeln!(writer, "{:?}", result),
+        }
+    }
+}
+
+fn to_json(line: &str, result: Option<&MatchResult>) -> Value {
+    let result = match result {
+        Some(result) => {
+            let pattern = result.pattern();
+            let tags = pattern.tags().map(|tags| {
+                tags.iter().fold(ArrayBuilder::new(), |builder, tag| builder.push(tag)).build()
+            });
+            let values = merged_values(result)
+                             .into_iter()
+                             .fold(ObjectBuilder::new(),
+                                   |builder, (key, value)| builder.insert(key, value))
+                             .build();
+            ObjectBuilder::new()
+                .insert("uuid", pattern.uuid().hyphenated().to_string())
+                .insert("name", pattern.name())
+                .insert("tags", tags)
+                .insert("values", values)
+                .build()
+        }
+        None => Value::Null,
+    };
+    ObjectBuilder::new()
+        .insert("message", line)
+        .insert("match", result)
+        .build()
+}
+
+// The tags are separated by semicolons, the values are `key=value` pairs
+// separated by semicolons. The semicolons, equal signs and backslashes in the
+// tags, keys and values are escaped with a backslash.
+fn to_csv(line: &str, result: Option<&MatchResult>) -> String {
+    let mut fields = vec![escape_csv(line)];
+    if let Some(result) = result {
+        let pattern = result.pattern();
+        let tags = pattern.tags()
+                          .map(|tags| {
+                              tags.iter()
+                                  .map(|tag| escape_list_item(tag))
+                                  .collect::<Vec<String>>()
+                                  .join(";")
+                          })
+                          .unwrap_or_default();
+        let values: Vec<String> = merged_values(result)
+                                      .into_iter()
+                                      .map(|(key, value)| {
+                                          format!("{}={}",
+                                                  escape_list_item(&key),
+                                                  escape_list_item(&value))
+                                      })
+                                      .collect();
+        fields.push(pattern.uuid().hyphenated().to_string());
+        fields.push(escape_csv(pattern.name().unwrap_or("")));
+        fields.push(escape_csv(&tags));
+        fields.push(escape_csv(&values.join(";")));
+    } else {
+        fields.extend(vec![String::new(); 4]);
+    }
+    fields.join(",")
+}
+
+fn escape_list_item(item: &str) -> String {
+    item.replace('\\', "\\\\").replace(';', "\\;").replace('=', "\\=")
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace("\"", "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+// The parsed values take precedence over the additional values of the
+// pattern.
+fn merged_values(result: &MatchResult) -> Vec<(String, String)> {
+    let mut values: BTreeMap<String, String> = result.additional_values()
+                                                     .into_iter()
+                                                     .map(|(key, value)| {
+                                                         (key.to_owned(), value.into_owned())
+                                                     })
+                                                     .collect();
+    for (key, value) in result.values() {
+        values.insert((*key).to_owned(), (*value).to_owned());
+    }
+    for (key, value) in result.indexed_values() {
+        values.insert(key, value.into_owned());
+    }
+    values.into_iter().collect()
+}
@@ -0,0 +1,70 @@
+use std::io::{Error, ErrorKind, Write};
+use actiondb::Matcher;
+use actiondb::matcher::{MatcherFactory, MatcherSuite};
+use actiondb::matcher::pattern::file::PatternFile;
+use actiondb::matcher::testing::TestGenerator;
+use convert::{self, FileFormat};
+use parse::{open_input, open_output, STANDARD_STREAM};
+
+pub struct GenTestsOptions<'a> {
+    pub pattern_file: &'a str,
+    pub input_file: &'a str,
+    pub output_file: &'a str,
+    pub max_samples: usize,
+    pub force: bool,
+}
+
+// Adds sample messages matched by the patterns as their test messages. The
+// whole pattern file is written out again in its canonical form, see `fmt`,
+// so a pattern file is overwritten only if it's already in that form.
+pub fn gen_tests<MS: MatcherSuite>(options: &GenTestsOptions) -> Result<(), Error> {
+    let format_path = if options.output_file == STANDARD_STREAM {
+        options.pattern_file
+    } else {
+        options.output_file
+    };
+    let format = match FileFormat::from_path(format_path) {
+        Some(FileFormat::Pdb) | None => {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "The test messages can be written only into JSON or YAML \
+                                   pattern files"))
+        }
+        Some(format) => format,
+    };
+    let mut file = try!(convert::load(options.pattern_file));
+    if options.output_file == options.pattern_file {
+        try!(check_canonical_form(options.pattern_file, &file, format));
+    }
+
+    let mut matcher = MS::MatcherFactory::new_matcher();
+    for pattern in &file.patterns {
+        matcher.add_pattern(pattern.clone());
+    }
+    let mut generator = TestGenerator::new();
+    generator.set_max_samples(options.max_samples);
+    for line in try!(open_input(options.input_file)).lines() {
+        let line = try!(line);
+        generator.add_message(&matcher, &line);
+    }
+
+    let updated = generator.apply(&mut file, options.force);
+    // the log goes to the standard output, where the pattern file may be written
+    debug!("Test messages were generated for {} pattern(s)", updated);
+    let content = try!(convert::render(&file, format, None));
+    let mut output = try!(open_output(options.output_file));
+    output.write_all(content.as_bytes())
+}
+
+// Overwriting a pattern file which is not in the canonical form would change
+// more than its test messages, like its comments or the order of its keys.
+fn check_canonical_form(path: &str, file: &PatternFile, format: FileFormat) -> Result<(), Error> {
+    if try!(convert::read(path)) == try!(convert::render(file, format, None)) {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::InvalidInput,
+                       format!("The pattern file is not in the canonical form, overwriting it \
+                                would change more than its test messages. Format it with \
+                                'adbtool fmt' first or write the result into an other file: {}",
+                               path)))
+    }
+}
@@ -0,0 +1,40 @@
+use std::io::{Error, ErrorKind, Write};
+use actiondb::matcher::{PatternLoader, MatcherSuite};
+use actiondb::matcher::dump::{self, Dump};
+use parse::open_output;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    Text,
+    Dot,
+}
+
+impl GraphFormat {
+    pub fn from_str(format: &str) -> Option<GraphFormat> {
+        match format {
+            "text" => Some(GraphFormat::Text),
+            "dot" => Some(GraphFormat::Dot),
+            _ => None,
+        }
+    }
+}
+
+// Writes the structure of the matcher built from the pattern file.
+pub fn graph<MS: MatcherSuite>(pattern_file: &str,
+                               output_file: &str,
+                               format: GraphFormat)
+                               -> Result<(), Error> {
+    let matcher = try!(PatternLoader::from_file::<MS::MatcherFactory>(pattern_file)
+                           .map_err(|err| {
+                               Error::new(ErrorKind::Other,
+                                          format!("Failed to load the pattern file: {}",
+                                                  err.report()))
+                           }));
+    let root = matcher.dump();
+    let content = match format {
+        GraphFormat::Text => dump::to_text(&root),
+        GraphFormat::Dot => dump::to_dot(&root),
+    };
+    let mut output = try!(open_output(output_file));
+    output.write_all(content.as_bytes())
+}
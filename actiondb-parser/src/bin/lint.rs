@@ -0,0 +1,41 @@
+use std::io::{Error, ErrorKind};
+use actiondb::lint::{Linter, Severity};
+use actiondb::matcher::PatternLoader;
+
+// Lints every pattern of the pattern files and logs the problems. Returns
+// whether there was a problem with `error` severity.
+pub fn lint(pattern_files: &[&str], linter: &Linter) -> Result<bool, Error> {
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for pattern_file in pattern_files {
+        let paths = try!(PatternLoader::resolve(pattern_file).map_err(|err| {
+            Error::new(ErrorKind::Other, err.to_string())
+        }));
+        for path in paths {
+            let file = try!(PatternLoader::load_file_with_location(&path).map_err(|err| {
+                Error::new(ErrorKind::Other,
+                           format!("Failed to load the pattern file: {}",
+                                   err.report()))
+            }));
+            for pattern in file.patterns() {
+                for lint in linter.lint(pattern) {
+                    match lint.severity() {
+                        Severity::Error => {
+                            errors += 1;
+                            error!("{}: {}", path, lint);
+                        }
+                        Severity::Warning => {
+                            warnings += 1;
+                            warn!("{}: {}", path, lint);
+                        }
+                        Severity::Info => info!("{}: {}", path, lint),
+                    }
+                }
+            }
+        }
+    }
+
+    info!("Total number of errors: {} warnings: {}", errors, warnings);
+    Ok(errors > 0)
+}
@@ -1,17 +1,20 @@
 extern crate log;
 
+use std::io::{self, Write};
 use log::{LogRecord, LogLevel, LogMetadata};
 
-pub struct StdoutLogger;
+// The log messages are written to the standard error, so they are never
+// mixed into the results written to the standard output.
+pub struct StderrLogger;
 
-impl log::Log for StdoutLogger {
+impl log::Log for StderrLogger {
     fn enabled(&self, metadata: &LogMetadata) -> bool {
         metadata.level() <= LogLevel::Trace
     }
 
     fn log(&self, record: &LogRecord) {
         if self.enabled(record.metadata()) {
-            println!("{} - {}", record.level(), record.args());
+            let _ = writeln!(io::stderr(), "{} - {}", record.level(), record.args());
         }
     }
 }
@@ -1,40 +1,263 @@
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::{BufReader, BufRead, Error, ErrorKind, BufWriter, Write};
+use std::io::{self, BufReader, BufRead, Error, ErrorKind, BufWriter, Write};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 use actiondb::Matcher;
 use actiondb::matcher::{PatternLoader, MatcherSuite};
+use actiondb::preprocess::Preprocessor;
+use flate2::read::GzDecoder;
+use format::OutputFormat;
 
-pub fn parse<MS: MatcherSuite>(pattern_file_path: &str,
-             input_file_path: &str,
-             output_file_path: &str)
-             -> Result<(), Error> {
-    match PatternLoader::from_file::<MS::MatcherFactory>(pattern_file_path) {
+// The input or output file name which denotes the standard input or output.
+pub const STANDARD_STREAM: &'static str = "-";
+// The input files with this extension are decompressed while they are read.
+const GZIP_EXTENSION: &'static str = ".gz";
+// The number of lines a worker thread parses at once.
+const CHUNK_SIZE: usize = 1024;
+
+pub struct ParseOptions<'a> {
+    pub pattern_file: &'a str,
+    pub input_file: &'a str,
+    pub output_file: &'a str,
+    pub unmatched_file: Option<&'a str>,
+    pub format: OutputFormat,
+    pub jobs: usize,
+    // the lines are parsed after preprocessing, but the original lines are
+    // written into the output
+    pub preprocessor: Preprocessor,
+}
+
+pub fn parse<MS: MatcherSuite>(options: &ParseOptions) -> Result<(), Error> {
+    match PatternLoader::from_file::<MS::MatcherFactory>(options.pattern_file) {
         Ok(matcher) => {
-            let input_file = try!(File::open(input_file_path));
-            let mut output_file = try!(File::create(output_file_path));
-            parse_file::<MS>(&input_file, &mut output_file, &matcher);
+            let input = try!(open_input(options.input_file));
+            let mut output = try!(open_output(options.output_file));
+            let mut unmatched = match options.unmatched_file {
+                Some(path) => Some(try!(open_output(path))),
+                None => None,
+            };
+            let count = if options.jobs > 1 {
+                try!(parse_input_in_parallel(input,
+                                             &mut output,
+                                             unmatched.as_mut(),
+                                             options.format,
+                                             matcher,
+                                             &options.preprocessor,
+                                             options.jobs))
+            } else {
+                try!(parse_input::<MS>(input,
+                                       &mut output,
+                                       unmatched.as_mut(),
+                                       options.format,
+                                       &matcher,
+                                       &options.preprocessor))
+            };
+            // the buffered writers would drop their write errors
+            try!(output.flush());
+            if let Some(ref mut unmatched) = unmatched {
+                try!(unmatched.flush());
+            }
+            info!("Total number of lines: {}", count);
             Ok(())
         }
         Err(err) => {
             Err(Error::new(ErrorKind::Other,
-                           format!("Failed to parse a pattern in the input file: {:?}", err)))
+                           format!("Failed to parse a pattern in the input file: {}",
+                                   err.report())))
         }
     }
 }
 
-fn parse_file<MS: MatcherSuite>(input_file: &File, output_file: &mut File, matcher: &MS::Matcher) {
-    let reader = BufReader::new(input_file);
-    let mut writer = BufWriter::new(output_file);
+pub fn open_input(path: &str) -> Result<Box<BufRead>, Error> {
+    if path == STANDARD_STREAM {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else if path.ends_with(GZIP_EXTENSION) {
+        let file = try!(File::open(path));
+        Ok(Box::new(BufReader::new(try!(GzDecoder::new(file)))))
+    } else {
+        Ok(Box::new(BufReader::new(try!(File::open(path)))))
+    }
+}
+
+pub fn open_output(path: &str) -> Result<Box<Write>, Error> {
+    if path == STANDARD_STREAM {
+        Ok(Box::new(BufWriter::new(io::stdout())))
+    } else {
+        Ok(Box::new(BufWriter::new(try!(File::create(path)))))
+    }
+}
+
+fn parse_input<MS: MatcherSuite>(input: Box<BufRead>,
+                                 output: &mut Box<Write>,
+                                 mut unmatched: Option<&mut Box<Write>>,
+                                 format: OutputFormat,
+                                 matcher: &MS::Matcher,
+                                 preprocessor: &Preprocessor)
+                                 -> Result<usize, Error> {
     let mut count: usize = 0;
 
-    for line in reader.lines() {
-        if let Ok(l) = line {
-            let parse_result = matcher.parse(&l);
-            let _ = write!(&mut writer, "{:?}\n", parse_result);
-            count += 1;
-        } else {
+    try!(format.write_header(output));
+    for line in input.lines() {
+        let line = try!(line);
+        let input = preprocessor.process(&line);
+        let parse_result = matcher.parse(&input);
+        try!(format.write_result(output, &line, parse_result.as_ref()));
+        if let (None, Some(unmatched)) = (parse_result, unmatched.as_mut()) {
+            try!(writeln!(unmatched, "{}", line));
+        }
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+struct Chunk {
+    index: usize,
+    lines: Vec<String>,
+}
+
+// The formatted results of a chunk and the lines without a match.
+struct ParsedChunk {
+    index: usize,
+    output: Vec<u8>,
+    unmatched: Vec<u8>,
+}
+
+// The lines are parsed in chunks by `jobs` worker threads which share the
+// matcher. The parsed chunks are written in the order of the input.
+fn parse_input_in_parallel<M>(input: Box<BufRead>,
+                              output: &mut Box<Write>,
+                              mut unmatched: Option<&mut Box<Write>>,
+                              format: OutputFormat,
+                              matcher: M,
+                              preprocessor: &Preprocessor,
+                              jobs: usize)
+                              -> Result<usize, Error>
+    where M: Matcher + Send + Sync + 'static
+{
+    let matcher = Arc::new(matcher);
+    let collect_unmatched = unmatched.is_some();
+    let (chunk_sender, chunk_receiver) = mpsc::channel::<Chunk>();
+    let chunk_receiver = Arc::new(Mutex::new(chunk_receiver));
+    let (result_sender, result_receiver) = mpsc::channel::<Result<ParsedChunk, Error>>();
+
+    let mut workers = Vec::new();
+    for _ in 0..jobs {
+        let matcher = matcher.clone();
+        let preprocessor = preprocessor.clone();
+        let chunks = chunk_receiver.clone();
+        let results = result_sender.clone();
+        workers.push(thread::spawn(move || {
+            loop {
+                let chunk = match chunks.lock().unwrap().recv() {
+                    Ok(chunk) => chunk,
+                    Err(_) => break,
+                };
+                let result = parse_chunk(chunk,
+                                         format,
+                                         &*matcher,
+                                         &preprocessor,
+                                         collect_unmatched);
+                if results.send(result).is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+    drop(result_sender);
+
+    try!(format.write_header(output));
+    let mut pending = BTreeMap::new();
+    let mut next = 0;
+    let mut sent = 0;
+    let mut count = 0;
+    let mut lines = input.lines();
+    loop {
+        let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+        for line in lines.by_ref().take(CHUNK_SIZE) {
+            chunk.push(try!(line));
+        }
+        if chunk.is_empty() {
             break;
         }
+        count += chunk.len();
+        let chunk = Chunk {
+            index: sent,
+            lines: chunk,
+        };
+        try!(chunk_sender.send(chunk).map_err(|_| worker_error()));
+        sent += 1;
+        // limits the number of chunks in memory
+        while sent - next >= 2 * jobs {
+            try!(write_parsed_chunks(&result_receiver,
+                                     &mut pending,
+                                     &mut next,
+                                     output,
+                                     &mut unmatched));
+        }
+    }
+    drop(chunk_sender);
+
+    while next < sent {
+        try!(write_parsed_chunks(&result_receiver,
+                                 &mut pending,
+                                 &mut next,
+                                 output,
+                                 &mut unmatched));
+    }
+    for worker in workers {
+        try!(worker.join().map_err(|_| worker_error()));
     }
+    Ok(count)
+}
+
+fn parse_chunk<M: Matcher + ?Sized>(chunk: Chunk,
+                                    format: OutputFormat,
+                                    matcher: &M,
+                                    preprocessor: &Preprocessor,
+                                    collect_unmatched: bool)
+                                    -> Result<ParsedChunk, Error> {
+    let mut output = Vec::new();
+    let mut unmatched = Vec::new();
+
+    for line in &chunk.lines {
+        let input = preprocessor.process(line);
+        let parse_result = matcher.parse(&input);
+        try!(format.write_result(&mut output, line, parse_result.as_ref()));
+        if parse_result.is_none() && collect_unmatched {
+            try!(writeln!(unmatched, "{}", line));
+        }
+    }
+
+    Ok(ParsedChunk {
+        index: chunk.index,
+        output: output,
+        unmatched: unmatched,
+    })
+}
+
+// Waits for a parsed chunk, then writes the chunks which are next in the
+// order of the input.
+fn write_parsed_chunks(results: &mpsc::Receiver<Result<ParsedChunk, Error>>,
+                       pending: &mut BTreeMap<usize, ParsedChunk>,
+                       next: &mut usize,
+                       output: &mut Box<Write>,
+                       unmatched: &mut Option<&mut Box<Write>>)
+                       -> Result<(), Error> {
+    let parsed = try!(try!(results.recv().map_err(|_| worker_error())));
+    pending.insert(parsed.index, parsed);
+
+    while let Some(parsed) = pending.remove(&*next) {
+        try!(output.write_all(&parsed.output));
+        if let Some(ref mut unmatched) = *unmatched {
+            try!(unmatched.write_all(&parsed.unmatched));
+        }
+        *next += 1;
+    }
+    Ok(())
+}
 
-    info!("Total number of lines: {}", count);
+fn worker_error() -> Error {
+    Error::new(ErrorKind::Other, "A worker thread stopped unexpectedly")
 }
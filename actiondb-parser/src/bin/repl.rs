@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, Error, ErrorKind, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use uuid::Uuid;
+use actiondb::Matcher;
+use actiondb::grammar;
+use actiondb::matcher::{BuildError, MatcherFactory, MatcherSuite, PatternLoader, Pattern};
+use actiondb::matcher::compiled_pattern::TokenType;
+use actiondb::matcher::explain::explain;
+use actiondb::matcher::pattern::RawPattern;
+use actiondb::matcher::pattern::file::PatternFile;
+use actiondb::matcher::pattern::testmessage::TestMessage;
+use actiondb::matcher::result::MatchResult;
+use convert::{self, FileFormat};
+
+const PROMPT: &'static str = "adb> ";
+const HELP: &'static str = "\
+Lines which don't start with ':' are parsed as messages.
+
+:pattern <pattern>  compiles a pattern and makes it the current one
+:name <name>        names the current pattern
+:test               adds the last message to the current pattern as a test message
+:negative           adds the last message as a negative test message
+:accept             adds the current pattern to the accepted ones
+:list               lists the accepted patterns
+:save [file]        saves the accepted patterns into a pattern file
+:help               prints this help
+:quit               exits";
+
+// The pattern which is being developed.
+struct Draft {
+    name: Option<String>,
+    uuid: Uuid,
+    pattern: String,
+    test_messages: Vec<TestMessage>,
+}
+
+impl Draft {
+    fn new(pattern: String) -> Draft {
+        Draft {
+            name: None,
+            uuid: Uuid::new_v4(),
+            pattern: pattern,
+            test_messages: Vec::new(),
+        }
+    }
+
+    fn compile<MS: MatcherSuite>(&self) -> Result<Pattern, BuildError> {
+        let test_messages = if self.test_messages.is_empty() {
+            None
+        } else {
+            Some(self.test_messages.clone())
+        };
+        RawPattern::new(self.name.clone(),
+                        self.uuid.clone(),
+                        self.pattern.clone(),
+                        test_messages,
+                        None,
+                        None)
+            .compile::<MS::ParserFactory>()
+    }
+}
+
+pub struct Session<MS: MatcherSuite> {
+    pattern_file: Option<String>,
+    patterns: Vec<Pattern>,
+    draft: Option<Draft>,
+    last_message: Option<String>,
+    _suite: PhantomData<MS>,
+}
+
+impl<MS: MatcherSuite> Session<MS> {
+    // The patterns of `pattern_file` are accepted at the start of the
+    // session if it exists.
+    pub fn new(pattern_file: Option<&str>) -> Result<Session<MS>, Error> {
+        let patterns = match pattern_file {
+            Some(path) if Path::new(path).exists() => {
+                let file = try!(PatternLoader::load_file_with_location(path).map_err(|err| {
+                    Error::new(ErrorKind::Other,
+                               format!("Failed to load the pattern file: {}",
+                                       err.report()))
+                }));
+                file.patterns
+            }
+            _ => Vec::new(),
+        };
+
+        Ok(Session {
+            pattern_file: pattern_file.map(|path| path.to_owned()),
+            patterns: patterns,
+            draft: None,
+            last_message: None,
+            _suite: PhantomData,
+        })
+    }
+
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> Result<(), Error> {
+        try!(writeln!(output, "Type :help for the available commands."));
+        let mut lines = input.lines();
+        loop {
+            try!(write!(output, "{}", PROMPT));
+            try!(output.flush());
+            let line = match lines.next() {
+                Some(line) => try!(line),
+                None => return writeln!(output, ""),
+            };
+            if !try!(self.execute(line.trim_right_matches('\r'), output)) {
+                return Ok(());
+            }
+        }
+    }
+
+    // Returns false if the session is over.
+    fn execute<W: Write>(&mut self, line: &str, output: &mut W) -> Result<bool, Error> {
+        if !line.starts_with(':') {
+            if !line.is_empty() {
+                try!(self.parse(line, output));
+            }
+            return Ok(true);
+        }
+
+        let (command, argument) = match line.find(' ') {
+            Some(pos) => (&line[1..pos], line[pos + 1..].trim()),
+            None => (&line[1..], ""),
+        };
+        let result = match command {
+            "pattern" | "p" => self.set_pattern(argument, output),
+            "name" | "n" => self.set_name(argument, output),
+            "test" | "t" => self.add_test_message(false, output),
+            "negative" => self.add_test_message(true, output),
+            "accept" | "a" => self.accept(output),
+            "list" | "l" => self.list(output),
+            "save" | "s" => self.save(argument, output),
+            "help" | "h" => writeln!(output, "{}", HELP),
+            "quit" | "q" => return Ok(false),
+            _ => writeln!(output, "Unknown command: {}, type :help for the commands", command),
+        };
+        try!(result);
+        Ok(true)
+    }
+
+    fn set_pattern<W: Write>(&mut self, pattern: &str, output: &mut W) -> Result<(), Error> {
+        let draft = Draft::new(pattern.to_owned());
+        match draft.compile::<MS>() {
+            Ok(compiled) => {
+                for token in compiled.pattern() {
+                    try!(write_token(token, output));
+                }
+                self.draft = Some(draft);
+                Ok(())
+            }
+            Err(err) => writeln!(output, "{}", err.report()),
+        }
+    }
+
+    fn set_name<W: Write>(&mut self, name: &str, output: &mut W) -> Result<(), Error> {
+        match self.draft.as_mut() {
+            Some(draft) => {
+                draft.name = if name.is_empty() {
+                    None
+                } else {
+                    Some(name.to_owned())
+                };
+                Ok(())
+            }
+            None => writeln!(output, "There is no current pattern, use :pattern first"),
+        }
+    }
+
+    fn parse<W: Write>(&mut self, message: &str, output: &mut W) -> Result<(), Error> {
+        self.last_message = Some(message.to_owned());
+        let draft = match self.draft.as_ref() {
+            Some(draft) => {
+                match draft.compile::<MS>() {
+                    Ok(pattern) => Some(pattern),
+                    Err(err) => return writeln!(output, "{}", err.report()),
+                }
+            }
+            None => None,
+        };
+
+        let mut matcher = MS::MatcherFactory::new_matcher();
+        for pattern in self.patterns.iter().chain(draft.iter()) {
+            matcher.add_pattern(pattern.clone());
+        }
+        let draft_matched = match matcher.parse(message) {
+            Some(result) => {
+                try!(write_result(&result, output));
+                draft.as_ref().map_or(false, |draft| result.pattern().uuid() == draft.uuid())
+            }
+            None => {
+                try!(writeln!(output, "No match"));
+                false
+            }
+        };
+
+        if let (Some(draft), false) = (draft, draft_matched) {
+            try!(writeln!(output, "The current pattern doesn't match:"));
+            let explanation = explain(draft.pattern(), message);
+            for step in explanation.steps() {
+                match step.value() {
+                    Some(value) => {
+                        try!(writeln!(output, "  ok      {} = {:?}", step.token(), value))
+                    }
+                    None => try!(writeln!(output, "  failed  {}", step.token())),
+                }
+            }
+            for token in explanation.unreached() {
+                try!(writeln!(output, "  skipped {}", token));
+            }
+            if !explanation.rest().is_empty() {
+                try!(writeln!(output, "  unmatched input: {:?}", explanation.rest()));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_test_message<W: Write>(&mut self, negative: bool, output: &mut W) -> Result<(), Error> {
+        let message = match self.last_message.as_ref() {
+            Some(message) => message.clone(),
+            None => return writeln!(output, "There is no message yet"),
+        };
+        let pattern = match self.draft.as_ref().map(|draft| draft.compile::<MS>()) {
+            Some(Ok(pattern)) => pattern,
+            Some(Err(err)) => return writeln!(output, "{}", err.report()),
+            None => return writeln!(output, "There is no current pattern, use :pattern first"),
+        };
+
+        let mut matcher = MS::MatcherFactory::new_matcher();
+        matcher.add_pattern(pattern);
+        let values = matcher.parse(&message).map(|result| {
+            let mut values = result.values()
+                                   .iter()
+                                   .map(|(key, value)| (key.to_string(), value.to_string()))
+                                   .collect::<BTreeMap<String, String>>();
+            for (key, value) in result.indexed_values() {
+                values.insert(key, value.into_owned());
+            }
+            values
+        });
+        let mut test_message = match (values, negative) {
+            (Some(values), false) => TestMessage::new(message, values, None),
+            (None, true) => TestMessage::new(message, BTreeMap::new(), None),
+            (None, false) => {
+                return writeln!(output,
+                                "The current pattern doesn't match the message, use :negative \
+                                 to add it as a negative test message")
+            }
+            (Some(_), true) => {
+                return writeln!(output,
+                                "The current pattern matches the message, it cannot be a \
+                                 negative test message")
+            }
+        };
+        test_message.set_negative(negative);
+        if let Some(draft) = self.draft.as_mut() {
+            draft.test_messages.push(test_message);
+        }
+        writeln!(output, "Test message added")
+    }
+
+    fn accept<W: Write>(&mut self, output: &mut W) -> Result<(), Error> {
+        let pattern = match self.draft.as_ref().map(|draft| draft.compile::<MS>()) {
+            Some(Ok(pattern)) => pattern,
+            Some(Err(err)) => return writeln!(output, "{}", err.report()),
+            None => return writeln!(output, "There is no current pattern, use :pattern first"),
+        };
+        try!(writeln!(output, "Accepted {}", describe(&pattern)));
+        self.patterns.push(pattern);
+        self.draft = None;
+        Ok(())
+    }
+
+    fn list<W: Write>(&self, output: &mut W) -> Result<(), Error> {
+        for pattern in &self.patterns {
+            try!(writeln!(output,
+                          "{}: {}",
+                          describe(pattern),
+                          grammar::format_pattern(pattern.pattern())));
+        }
+        Ok(())
+    }
+
+    fn save<W: Write>(&mut self, path: &str, output: &mut W) -> Result<(), Error> {
+        let path = match (path.is_empty(), self.pattern_file.as_ref()) {
+            (false, _) => path.to_owned(),
+            (true, Some(pattern_file)) => pattern_file.clone(),
+            (true, None) => return writeln!(output, "The file name is missing: :save <file>"),
+        };
+        let format = match FileFormat::from_path(&path) {
+            Some(FileFormat::Pdb) | None => {
+                return writeln!(output, "The patterns can be saved only into JSON or YAML files")
+            }
+            Some(format) => format,
+        };
+
+        let file = PatternFile { patterns: self.patterns.clone() };
+        let content = try!(convert::render(&file, format, None));
+        let mut output_file = try!(File::create(&path));
+        try!(output_file.write_all(content.as_bytes()));
+        try!(writeln!(output, "Saved {} pattern(s) into {}", self.patterns.len(), path));
+        self.pattern_file = Some(path);
+        Ok(())
+    }
+}
+
+fn describe(pattern: &Pattern) -> String {
+    format!("{} ({})", pattern.name().unwrap_or("<unnamed>"), pattern.uuid())
+}
+
+fn write_token<W: Write>(token: &TokenType, output: &mut W) -> Result<(), Error> {
+    match *token {
+        TokenType::Literal(ref literal) => writeln!(output, "  literal {:?}", literal),
+        TokenType::Parser(ref parser) => {
+            writeln!(output,
+                     "  parser  {}",
+                     grammar::format_pattern(&[TokenType::Parser(parser.boxed_clone())]))
+        }
+    }
+}
+
+fn write_result<W: Write>(result: &MatchResult, output: &mut W) -> Result<(), Error> {
+    try!(writeln!(output, "Matched by {}", describe(result.pattern())));
+    for (key, value) in result.values() {
+        try!(writeln!(output, "  {} = {:?}", key, value));
+    }
+    for (key, value) in result.indexed_values() {
+        try!(writeln!(output, "  {} = {:?}", key, value));
+    }
+    Ok(())
+}
+
+pub fn repl<MS: MatcherSuite>(pattern_file: Option<&str>) -> Result<(), Error> {
+    let mut session = try!(Session::<MS>::new(pattern_file));
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    session.run(stdin.lock(), &mut stdout)
+}
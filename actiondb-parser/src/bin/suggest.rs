@@ -0,0 +1,47 @@
+use std::io::{Error, ErrorKind, Write};
+use actiondb::Matcher;
+use actiondb::matcher::{PatternLoader, MatcherSuite};
+use actiondb::suggest::Suggester;
+use parse::{open_input, open_output};
+
+pub struct SuggestOptions<'a> {
+    pub input_file: &'a str,
+    pub output_file: &'a str,
+    // only the messages which aren't matched by these patterns are clustered
+    pub pattern_file: Option<&'a str>,
+    pub similarity_threshold: f64,
+    pub max_samples: usize,
+    pub min_count: usize,
+}
+
+pub fn suggest<MS: MatcherSuite>(options: &SuggestOptions) -> Result<(), Error> {
+    let matcher = match options.pattern_file {
+        Some(pattern_file) => {
+            match PatternLoader::from_file::<MS::MatcherFactory>(pattern_file) {
+                Ok(matcher) => Some(matcher),
+                Err(err) => {
+                    return Err(Error::new(ErrorKind::Other,
+                                          format!("Failed to load the pattern file: {}",
+                                                  err.report())))
+                }
+            }
+        }
+        None => None,
+    };
+
+    let mut suggester = Suggester::new();
+    suggester.set_similarity_threshold(options.similarity_threshold);
+    suggester.set_max_samples(options.max_samples);
+    suggester.set_min_count(options.min_count);
+
+    for line in try!(open_input(options.input_file)).lines() {
+        let line = try!(line);
+        let matched = matcher.as_ref().map_or(false, |matcher| matcher.parse(&line).is_some());
+        if !matched && !line.is_empty() {
+            suggester.add_message(&line);
+        }
+    }
+
+    let mut output = try!(open_output(options.output_file));
+    output.write_all(suggester.to_yaml().as_bytes())
+}
@@ -0,0 +1,77 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+use actiondb::matcher::MatcherSuite;
+use actiondb::matcher::testing::{PatternReport, TestReport, TestRunner};
+use actiondb::preprocess::Preprocessor;
+
+// Runs every test message of the pattern files and writes the requested
+// reports. Returns whether every test message passed.
+pub fn test<MS: MatcherSuite>(pattern_file_paths: &[&str],
+                              junit_file_path: Option<&str>,
+                              json_file_path: Option<&str>,
+                              preprocessor: &Preprocessor)
+                              -> Result<bool, Error> {
+    let report = TestRunner::run_with_preprocessor::<MS::MatcherFactory>(pattern_file_paths,
+                                                                         preprocessor);
+    print_summary(&report);
+
+    if let Some(path) = junit_file_path {
+        let junit = try!(report.to_junit().map_err(|err| {
+            Error::new(ErrorKind::Other,
+                       format!("Failed to create the JUnit report: {}", err))
+        }));
+        try!(write_file(path, &junit));
+    }
+
+    if let Some(path) = json_file_path {
+        let json = try!(report.to_json().map_err(|err| {
+            Error::new(ErrorKind::Other,
+                       format!("Failed to create the JSON report: {}", err))
+        }));
+        try!(write_file(path, &json));
+    }
+
+    Ok(report.is_success())
+}
+
+fn print_summary(report: &TestReport) {
+    for err in report.errors() {
+        error!("{}", err.report());
+    }
+
+    for pattern in report.patterns() {
+        print_pattern_summary(pattern);
+    }
+
+    info!("Total number of test messages: {} passed: {} failed: {} load errors: {}",
+          report.passed() + report.failed(),
+          report.passed(),
+          report.failed(),
+          report.errors().len());
+}
+
+fn print_pattern_summary(pattern: &PatternReport) {
+    let status = if pattern.is_success() {
+        "PASS"
+    } else {
+        "FAIL"
+    };
+    info!("{} {} uuid={} name={} passed={} failed={}",
+          status,
+          pattern.file(),
+          pattern.uuid().hyphenated(),
+          pattern.name().unwrap_or(""),
+          pattern.passed(),
+          pattern.failed());
+
+    for test_case in pattern.test_cases() {
+        if let Some(err) = test_case.error() {
+            error!("{}", err);
+        }
+    }
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), Error> {
+    let mut file = try!(File::create(path));
+    file.write_all(content.as_bytes())
+}
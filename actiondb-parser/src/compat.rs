@@ -0,0 +1,170 @@
+// Copyright (c) 2016 Tibor Benke <ihrwein@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use actiondb::matcher::LoadedPattern;
+use actiondb::matcher::result::MatchResult;
+use syslog_ng_common::MessageFormatter;
+use syslog_ng_common::LogMessage;
+use uuid::Uuid;
+
+use keys;
+
+// patterndb uses this class if a rule doesn't have one.
+const DEFAULT_CLASS: &'static str = "system";
+const PROGRAM_MACRO: &'static str = "PROGRAM";
+
+// Where the `.classifier.class` of a matching message comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassSource {
+    Name,
+    Metadata,
+}
+
+impl ClassSource {
+    pub fn from_str(source: &str) -> Option<ClassSource> {
+        match source {
+            "name" => Some(ClassSource::Name),
+            "metadata" => Some(ClassSource::Metadata),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ClassSource {
+    fn default() -> ClassSource {
+        ClassSource::Name
+    }
+}
+
+// Fills the fields of the matching messages in the same way as syslog-ng's
+// db-parser does: `.classifier.class`, `.classifier.rule_id`, the ruleset and
+// the program of the message. `.classifier.context_id` is left out, because
+// it's the id of a correlation context, and actiondb doesn't correlate.
+#[derive(Clone, Debug, Default)]
+pub struct PatterndbCompat {
+    enabled: bool,
+    class_source: ClassSource,
+    ruleset: Option<String>,
+    // the ruleset of the patterns, by default it's the name of their file
+    rulesets: HashMap<Uuid, String>,
+}
+
+impl PatterndbCompat {
+    pub fn new() -> PatterndbCompat {
+        PatterndbCompat::default()
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_class_source(&mut self, class_source: ClassSource) {
+        self.class_source = class_source;
+    }
+
+    // Every pattern belongs to this ruleset instead of the one named after
+    // its file. An empty ruleset (the default of the `actiondb()` block)
+    // keeps the file names.
+    pub fn set_ruleset(&mut self, ruleset: String) {
+        self.ruleset = if ruleset.is_empty() {
+            None
+        } else {
+            Some(ruleset)
+        };
+    }
+
+    // Assigns the loaded patterns to rulesets named after their pattern
+    // files (`sshd.yaml` becomes `sshd`).
+    pub fn set_loaded_patterns(&mut self, patterns: &[LoadedPattern]) {
+        self.rulesets = patterns.iter()
+                                .map(|pattern| {
+                                    (pattern.uuid().clone(),
+                                     PatterndbCompat::ruleset_of(pattern.path()))
+                                })
+                                .collect();
+    }
+
+    fn ruleset_of(path: &str) -> String {
+        Path::new(path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(path)
+            .to_owned()
+    }
+
+    pub fn fill(&self,
+                formatter: &mut MessageFormatter,
+                msg: &mut LogMessage,
+                result: &MatchResult) {
+        self.fill_class(formatter, msg, result);
+        self.fill_rule_id(formatter, msg, result);
+        self.fill_ruleset(formatter, msg, result);
+        self.fill_program(formatter, msg);
+    }
+
+    fn fill_class(&self,
+                  formatter: &mut MessageFormatter,
+                  msg: &mut LogMessage,
+                  result: &MatchResult) {
+        let metadata_class = match self.class_source {
+            ClassSource::Name => None,
+            ClassSource::Metadata => result.metadata().and_then(|metadata| metadata.class()),
+        };
+        let class = metadata_class.or(result.pattern().name()).unwrap_or(DEFAULT_CLASS);
+        let (key, value) = formatter.format(keys::PATTERN_NAME, class);
+        msg.insert(key, value.as_bytes());
+    }
+
+    fn fill_rule_id(&self,
+                    formatter: &mut MessageFormatter,
+                    msg: &mut LogMessage,
+                    result: &MatchResult) {
+        let uuid = result.pattern().uuid().hyphenated().to_string();
+        let (key, value) = formatter.format(keys::CLASSIFIER_RULE_ID, &uuid);
+        msg.insert(key, value.as_bytes());
+    }
+
+    fn fill_ruleset(&self,
+                    formatter: &mut MessageFormatter,
+                    msg: &mut LogMessage,
+                    result: &MatchResult) {
+        let ruleset = match self.ruleset {
+            Some(ref ruleset) => Some(ruleset),
+            None => self.rulesets.get(result.pattern().uuid()),
+        };
+        if let Some(ruleset) = ruleset {
+            let (key, value) = formatter.format(keys::CLASSIFIER_RULESET, ruleset);
+            msg.insert(key, value.as_bytes());
+        }
+    }
+
+    fn fill_program(&self, formatter: &mut MessageFormatter, msg: &mut LogMessage) {
+        let program = match msg.get(PROGRAM_MACRO) {
+            Some(program) => String::from_utf8_lossy(program).into_owned(),
+            None => return,
+        };
+        let (key, value) = formatter.format(keys::CLASSIFIER_PROGRAM, &program);
+        msg.insert(key, value.as_bytes());
+    }
+}
+
+// Parses the yes/no values of syslog-ng.
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "yes" | "true" | "1" => Some(true),
+        "no" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
@@ -8,3 +8,11 @@
 
 pub const PATTERN_UUID: &'static str = ".classifier.uuid";
 pub const PATTERN_NAME: &'static str = ".classifier.class";
+pub const CLASSIFIER_DESCRIPTION: &'static str = ".classifier.description";
+pub const CLASSIFIER_SEVERITY: &'static str = ".classifier.severity";
+pub const CLASSIFIER_REFERENCES: &'static str = ".classifier.references";
+pub const CLASSIFIER_DEPRECATED: &'static str = ".classifier.deprecated";
+pub const CLASSIFIER_OWNER: &'static str = ".classifier.owner";
+pub const CLASSIFIER_RULE_ID: &'static str = ".classifier.rule_id";
+pub const CLASSIFIER_RULESET: &'static str = ".classifier.ruleset";
+pub const CLASSIFIER_PROGRAM: &'static str = ".classifier.program";
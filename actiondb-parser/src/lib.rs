@@ -11,45 +11,161 @@ extern crate log;
 #[macro_use]
 extern crate syslog_ng_common;
 extern crate actiondb;
+extern crate uuid;
 
 use std::borrow::Borrow;
+use std::marker::PhantomData;
 
-use actiondb::matcher::{Matcher, PatternLoader, MatcherSuite};
+use actiondb::matcher::{DuplicatePolicy, LoadOptions, LoadedPattern, Matcher, PatternLoader,
+                        MatcherSuite, TestMessagePolicy};
+use actiondb::preprocess::Preprocessor;
 use syslog_ng_common::{Parser, ParserBuilder, Error, LogMessage, MessageFormatter, Pipe, GlobalConfig};
 
+mod compat;
 mod msgfilller;
 mod keys;
+mod metadata;
 mod options;
+mod unmatched;
 
+#[cfg(test)]
+mod tests;
+
+use self::compat::{ClassSource, PatterndbCompat};
+use self::metadata::MetadataField;
 use self::msgfilller::MessageFiller;
+use self::unmatched::UnmatchedMarker;
 
 #[derive(Clone)]
 pub struct ActiondbParserBuilder<MS> where MS: MatcherSuite, MS::Matcher: Clone {
-    matcher: Option<MS::Matcher>,
-    formatter: MessageFormatter
+    pattern_files: Vec<String>,
+    load_options: LoadOptions,
+    formatter: MessageFormatter,
+    metadata_fields: Vec<MetadataField>,
+    unmatched: UnmatchedMarker,
+    compat: PatterndbCompat,
+    preprocessor: Preprocessor,
+    _suite: PhantomData<MS>,
 }
 
 impl<MS> ActiondbParserBuilder<MS> where MS: MatcherSuite, MS::Matcher: Clone {
-    pub fn set_pattern_file(&mut self, path: &str) {
-        match PatternLoader::from_file::<MS::MatcherFactory>(path) {
-            Ok(matcher) => self.matcher = Some(matcher),
-            Err(err) => {
-                error!("ActiondbParser: failed to set 'pattern_file': {}", err);
+    // Every pattern file, directory or glob pattern is merged into one
+    // matcher when the parser is built. The `actiondb()` block forwards an
+    // empty path if its `pattern_file()` is not set, it's ignored.
+    pub fn add_pattern_file(&mut self, path: String) {
+        if !path.is_empty() {
+            self.pattern_files.push(path);
+        }
+    }
+
+    pub fn set_duplicate_policy(&mut self, policy: &str) -> Result<(), Error> {
+        match DuplicatePolicy::from_str(policy) {
+            Some(policy) => {
+                self.load_options.set_duplicate_policy(policy);
+                Ok(())
+            }
+            None => {
+                Err(Error::invalid_value(options::DUPLICATE_UUIDS,
+                                         policy,
+                                         "error, first-wins or last-wins"))
+            }
+        }
+    }
+
+    pub fn set_test_message_policy(&mut self, policy: &str) -> Result<(), Error> {
+        match TestMessagePolicy::from_str(policy) {
+            Some(policy) => {
+                self.load_options.set_test_message_policy(policy);
+                Ok(())
+            }
+            None => {
+                Err(Error::invalid_value(options::TEST_MESSAGES,
+                                         policy,
+                                         "strict, warn or skip"))
+            }
+        }
+    }
+
+    pub fn set_patterndb_compat(&mut self, enabled: &str) -> Result<(), Error> {
+        match compat::parse_bool(enabled) {
+            Some(enabled) => {
+                self.compat.set_enabled(enabled);
+                Ok(())
+            }
+            None => Err(Error::invalid_value(options::PATTERNDB_COMPAT, enabled, "yes or no")),
+        }
+    }
+
+    pub fn set_class_source(&mut self, source: &str) -> Result<(), Error> {
+        match ClassSource::from_str(source) {
+            Some(source) => {
+                self.compat.set_class_source(source);
+                Ok(())
             }
+            None => Err(Error::invalid_value(options::CLASS_SOURCE, source, "name or metadata")),
         }
     }
 
+    pub fn set_preprocess(&mut self, steps: &str) -> Result<(), Error> {
+        match Preprocessor::from_list(steps) {
+            Ok(preprocessor) => {
+                self.preprocessor = preprocessor;
+                Ok(())
+            }
+            Err(_) => {
+                Err(Error::invalid_value(options::PREPROCESS,
+                                         steps,
+                                         "a comma separated list of trim, strip-control, \
+                                          normalize-whitespace and first-line"))
+            }
+        }
+    }
+
+    fn build_matcher(&self) -> Result<(MS::Matcher, Vec<LoadedPattern>), Error> {
+        if self.pattern_files.is_empty() {
+            return Err(Error::missing_required_option(options::PATTERN_FILE));
+        }
+        let paths: Vec<&str> = self.pattern_files.iter().map(|path| path.as_str()).collect();
+        // the error is returned with every detail, so syslog-ng can refuse
+        // the configuration with an actionable message
+        PatternLoader::from_files_with_sources::<MS::MatcherFactory>(&paths, &self.load_options)
+            .map_err(|err| {
+                Error::verbatim_error(format!("ActiondbParser: failed to load 'pattern_file': {}",
+                                              err.report()))
+            })
+    }
+
     pub fn set_prefix(&mut self, prefix: String) {
         self.formatter.set_prefix(prefix);
     }
+
+    pub fn set_classifier_metadata(&mut self, fields: &str) -> Result<(), Error> {
+        match MetadataField::parse_list(fields) {
+            Ok(fields) => {
+                self.metadata_fields = fields;
+                Ok(())
+            }
+            Err(_) => {
+                Err(Error::invalid_value(options::CLASSIFIER_METADATA,
+                                         fields,
+                                         metadata::EXPECTED_VALUE))
+            }
+        }
+    }
 }
 
 impl<MS, P> ParserBuilder<P> for ActiondbParserBuilder<MS> where P: Pipe, MS: MatcherSuite + Clone, MS::Matcher: Clone {
     type Parser = ActiondbParser<MS::Matcher>;
     fn new(_: GlobalConfig) -> Self {
         ActiondbParserBuilder {
-            matcher: None,
+            pattern_files: Vec::new(),
+            load_options: LoadOptions::new(),
             formatter: MessageFormatter::new(),
+            metadata_fields: Vec::new(),
+            unmatched: UnmatchedMarker::new(),
+            compat: PatterndbCompat::new(),
+            preprocessor: Preprocessor::new(),
+            _suite: PhantomData,
         }
     }
     fn option(&mut self, name: String, value: String) -> Result<(), Error> {
@@ -59,26 +175,50 @@ impl<MS, P> ParserBuilder<P> for ActiondbParserBuilder<MS> where P: Pipe, MS: Ma
 
         match name.borrow() {
             options::PATTERN_FILE => {
-                self.set_pattern_file(&value);
+                self.add_pattern_file(value);
                 Ok(())
             }
+            options::DUPLICATE_UUIDS => self.set_duplicate_policy(&value),
+            options::TEST_MESSAGES => self.set_test_message_policy(&value),
             options::PREFIX => {
                 self.set_prefix(value);
                 Ok(())
             }
+            options::CLASSIFIER_METADATA => self.set_classifier_metadata(&value),
+            options::UNMATCHED_TAG => {
+                self.unmatched.set_tag(value);
+                Ok(())
+            }
+            options::UNMATCHED_CLASS => {
+                self.unmatched.set_class(value);
+                Ok(())
+            }
+            options::PATTERNDB_COMPAT => self.set_patterndb_compat(&value),
+            options::CLASS_SOURCE => self.set_class_source(&value),
+            options::PREPROCESS => self.set_preprocess(&value),
+            options::RULESET => {
+                self.compat.set_ruleset(value);
+                Ok(())
+            }
             _ => {
                 Err(Error::unknown_option(name))
             }
         }
     }
-    fn build(self) -> Result<Self::Parser, Error> {
-        let ActiondbParserBuilder {matcher, formatter} = self;
+    fn build(mut self) -> Result<Self::Parser, Error> {
         debug!("ActiondbParser: building");
-        let matcher =
-            try!(matcher.ok_or(Error::missing_required_option(options::PATTERN_FILE)));
+        let (matcher, loaded) = try!(self.build_matcher());
+        self.compat.set_loaded_patterns(&loaded);
+        let ActiondbParserBuilder {
+            formatter, metadata_fields, unmatched, compat, preprocessor, ..
+        } = self;
         Ok(ActiondbParser {
             matcher: matcher,
             formatter: formatter,
+            metadata_fields: metadata_fields,
+            unmatched: unmatched,
+            compat: compat,
+            preprocessor: preprocessor,
         })
     }
 }
@@ -86,12 +226,24 @@ impl<MS, P> ParserBuilder<P> for ActiondbParserBuilder<MS> where P: Pipe, MS: Ma
 pub struct ActiondbParser<M> where M: Matcher + Clone {
     pub matcher: M,
     pub formatter: MessageFormatter,
+    pub metadata_fields: Vec<MetadataField>,
+    pub unmatched: UnmatchedMarker,
+    pub compat: PatterndbCompat,
+    pub preprocessor: Preprocessor,
 }
 
 impl<M, P> Parser<P> for ActiondbParser<M> where P: Pipe, M: Matcher + Clone {
     fn parse(&mut self, _: &mut P, msg: &mut LogMessage, input: &str) -> bool {
-        if let Some(result) = self.matcher.parse(input) {
-            MessageFiller::fill_logmsg(&mut self.formatter, msg, &result);
+        let input = self.preprocessor.process(input);
+        if let Some(result) = self.matcher.parse(&input) {
+            MessageFiller::fill_logmsg(&mut self.formatter,
+                                       msg,
+                                       &result,
+                                       &self.metadata_fields,
+                                       &self.compat);
+            true
+        } else if self.unmatched.is_enabled() {
+            self.unmatched.mark(&mut self.formatter, msg);
             true
         } else {
             false
@@ -0,0 +1,77 @@
+// Copyright (c) 2016 Tibor Benke <ihrwein@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+use actiondb::matcher::pattern::metadata::Metadata;
+
+use keys;
+
+// The metadata fields which can be written into the `.classifier.*` fields
+// of the messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataField {
+    Description,
+    Severity,
+    References,
+    Deprecated,
+    Owner,
+}
+
+impl MetadataField {
+    // Parses a comma separated list, like `description,severity`.
+    pub fn parse_list(fields: &str) -> Result<Vec<MetadataField>, String> {
+        let mut result = Vec::new();
+        let fields = fields.split(',').map(|field| field.trim()).filter(|field| !field.is_empty());
+        for field in fields {
+            let field = try!(MetadataField::from_str(field).ok_or(field.to_owned()));
+            result.push(field);
+        }
+        Ok(result)
+    }
+
+    pub fn from_str(field: &str) -> Option<MetadataField> {
+        match field {
+            "description" => Some(MetadataField::Description),
+            "severity" => Some(MetadataField::Severity),
+            "references" => Some(MetadataField::References),
+            "deprecated" => Some(MetadataField::Deprecated),
+            "owner" => Some(MetadataField::Owner),
+            _ => None,
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match *self {
+            MetadataField::Description => keys::CLASSIFIER_DESCRIPTION,
+            MetadataField::Severity => keys::CLASSIFIER_SEVERITY,
+            MetadataField::References => keys::CLASSIFIER_REFERENCES,
+            MetadataField::Deprecated => keys::CLASSIFIER_DEPRECATED,
+            MetadataField::Owner => keys::CLASSIFIER_OWNER,
+        }
+    }
+
+    // The value of the field in `metadata`. References are joined with
+    // commas, like `mitre:T1110,url:https://wiki.example.com/sshd`.
+    pub fn value(&self, metadata: &Metadata) -> Option<String> {
+        match *self {
+            MetadataField::Description => metadata.description().map(|value| value.to_owned()),
+            MetadataField::Severity => metadata.severity().map(|value| value.to_string()),
+            MetadataField::References => {
+                metadata.references().map(|references| {
+                    references.iter()
+                              .map(|reference| reference.to_string())
+                              .collect::<Vec<String>>()
+                              .join(",")
+                })
+            }
+            MetadataField::Deprecated => Some(metadata.is_deprecated().to_string()),
+            MetadataField::Owner => metadata.owner().map(|value| value.to_owned()),
+        }
+    }
+}
+
+pub const EXPECTED_VALUE: &'static str = "a comma separated list of description, severity, \
+                                          references, deprecated and owner";
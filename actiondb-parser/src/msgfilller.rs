@@ -10,17 +10,26 @@ use syslog_ng_common::MessageFormatter;
 use syslog_ng_common::LogMessage;
 use actiondb::matcher::result::MatchResult;
 
+use compat::PatterndbCompat;
 use keys;
+use metadata::MetadataField;
 
 pub struct MessageFiller;
 
 impl MessageFiller {
     pub fn fill_logmsg(formatter: &mut MessageFormatter,
                        msg: &mut LogMessage,
-                       result: &MatchResult) {
+                       result: &MatchResult,
+                       metadata_fields: &[MetadataField],
+                       compat: &PatterndbCompat) {
         MessageFiller::fill_values(formatter, msg, result);
-        MessageFiller::fill_name(formatter, msg, result);
+        if compat.is_enabled() {
+            compat.fill(formatter, msg, result);
+        } else {
+            MessageFiller::fill_name(formatter, msg, result);
+        }
         MessageFiller::fill_uuid(formatter, msg, result);
+        MessageFiller::fill_metadata(formatter, msg, result, metadata_fields);
         MessageFiller::fill_tags(msg, result);
     }
 
@@ -36,16 +45,18 @@ impl MessageFiller {
             let (key, value) = formatter.format(key, value);
             msg.insert(key, value.as_bytes());
         }
+        for (key, value) in result.indexed_values() {
+            let (key, value) = formatter.format(&key, &value);
+            msg.insert(key, value.as_bytes());
+        }
     }
 
     fn fill_additional_values(formatter: &mut MessageFormatter,
                               msg: &mut LogMessage,
                               result: &MatchResult) {
-        if let Some(values) = result.pattern().values() {
-            for (key, value) in values {
-                let (key, value) = formatter.format(key, value);
-                msg.insert(key, value.as_bytes());
-            }
+        for (key, value) in result.additional_values() {
+            let (key, value) = formatter.format(key, &value);
+            msg.insert(key, value.as_bytes());
         }
     }
 
@@ -62,6 +73,20 @@ impl MessageFiller {
         msg.insert(key, value.as_bytes());
     }
 
+    fn fill_metadata(formatter: &mut MessageFormatter,
+                     msg: &mut LogMessage,
+                     result: &MatchResult,
+                     metadata_fields: &[MetadataField]) {
+        if let Some(metadata) = result.metadata() {
+            for field in metadata_fields {
+                if let Some(value) = field.value(metadata) {
+                    let (key, value) = formatter.format(field.key(), &value);
+                    msg.insert(key, value.as_bytes());
+                }
+            }
+        }
+    }
+
     fn fill_tags(msg: &mut LogMessage, result: &MatchResult) {
         if let Some(tags) = result.pattern().tags() {
             for i in tags {
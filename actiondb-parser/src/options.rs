@@ -8,3 +8,12 @@
 
 pub const PATTERN_FILE: &'static str = "pattern_file";
 pub const PREFIX: &'static str = "prefix";
+pub const CLASSIFIER_METADATA: &'static str = "classifier_metadata";
+pub const DUPLICATE_UUIDS: &'static str = "duplicate_uuids";
+pub const TEST_MESSAGES: &'static str = "test_messages";
+pub const UNMATCHED_TAG: &'static str = "unmatched_tag";
+pub const UNMATCHED_CLASS: &'static str = "unmatched_class";
+pub const PATTERNDB_COMPAT: &'static str = "patterndb_compat";
+pub const CLASS_SOURCE: &'static str = "class_source";
+pub const RULESET: &'static str = "ruleset";
+pub const PREPROCESS: &'static str = "preprocess";
@@ -0,0 +1,322 @@
+use super::*;
+use {keys, options};
+
+use syslog_ng_common::{LogMessage, Parser, ParserBuilder, mock, GlobalConfig,
+                       SYSLOG_NG_INITIALIZED, syslog_ng_global_init, Error};
+
+type Builder = ActiondbParserBuilder<SuffixArrayMatcherSuite>;
+type BuiltParser = <Builder as ParserBuilder<mock::MockPipe>>::Parser;
+
+const SSHD_PATTERNS: &'static str = "tests/patterns/sshd.yaml";
+const CRON_PATTERNS: &'static str = "tests/patterns/cron.yaml";
+const SSHD_OVERRIDE_PATTERNS: &'static str = "tests/patterns/sshd_override.yaml";
+const FAILING_TEST_MESSAGE_PATTERNS: &'static str = "tests/patterns/failing_test_message.yaml";
+
+const SSHD_MESSAGE: &'static str = "Received disconnect from 10.0.0.1: 11: disconnected by user";
+const CRON_MESSAGE: &'static str = "pam_unix(cron:session): session opened for user root";
+const UNKNOWN_MESSAGE: &'static str = "kernel: eth0 link is down";
+
+fn builder_with_options(options: &[(&str, &str)]) -> Builder {
+    SYSLOG_NG_INITIALIZED.call_once(|| {
+        unsafe {
+            syslog_ng_global_init();
+        }
+    });
+    let mut builder = <Builder as ParserBuilder<mock::MockPipe>>::new(GlobalConfig::new(0x0308));
+    for &(name, value) in options {
+        ParserBuilder::<mock::MockPipe>::option(&mut builder, name.to_owned(), value.to_owned())
+            .ok()
+            .unwrap();
+    }
+    builder
+}
+
+fn build(options: &[(&str, &str)]) -> Result<BuiltParser, Error> {
+    ParserBuilder::<mock::MockPipe>::build(builder_with_options(options))
+}
+
+fn parse(options: &[(&str, &str)], input: &str) -> (bool, LogMessage) {
+    let mut parser = build(options).ok().unwrap();
+    let mut logmsg = LogMessage::new();
+    let mut pipe = mock::MockPipe::new();
+    let matched = parser.parse(&mut pipe, &mut logmsg, input);
+    (matched, logmsg)
+}
+
+fn set_option(name: &str, value: &str) -> Result<(), Error> {
+    let mut builder = builder_with_options(&[]);
+    ParserBuilder::<mock::MockPipe>::option(&mut builder, name.to_owned(), value.to_owned())
+}
+
+#[test]
+fn test_given_parser_when_a_message_matches_then_its_values_tags_and_uuid_are_set() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS)], SSHD_MESSAGE);
+    assert!(matched);
+    assert_eq!(b"10.0.0.1", logmsg.get("ipaddr").unwrap());
+    assert_eq!(b"11", logmsg.get("code").unwrap());
+    assert_eq!(b"9a49c47d-29e9-4072-be84-3b76c6814743",
+               logmsg.get(keys::PATTERN_UUID).unwrap());
+    assert_eq!(b"SSH_DISCONNECT", logmsg.get(keys::PATTERN_NAME).unwrap());
+    assert!(logmsg.tags().contains(&b"ssh".to_vec()));
+}
+
+#[test]
+fn test_given_parser_when_the_prefix_is_set_then_the_keys_are_prefixed() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS), (options::PREFIX, "adb.")],
+                            SSHD_MESSAGE);
+    assert_eq!(b"10.0.0.1", logmsg.get("adb.ipaddr").unwrap());
+    assert_eq!(None, logmsg.get("ipaddr"));
+}
+
+#[test]
+fn test_given_parser_when_a_message_does_not_match_then_parse_fails() {
+    let (matched, _) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS)], UNKNOWN_MESSAGE);
+    assert!(!matched);
+}
+
+#[test]
+fn test_given_parser_when_no_pattern_file_is_set_then_it_cannot_be_built() {
+    assert!(build(&[]).is_err());
+    assert!(build(&[(options::PATTERN_FILE, "")]).is_err());
+}
+
+#[test]
+fn test_given_parser_when_a_pattern_file_does_not_exist_then_it_cannot_be_built() {
+    assert!(build(&[(options::PATTERN_FILE, "tests/patterns/not_found.yaml")]).is_err());
+}
+
+#[test]
+fn test_given_parser_when_pattern_file_is_set_multiple_times_then_every_file_is_loaded() {
+    let parser_options = [(options::PATTERN_FILE, SSHD_PATTERNS),
+                          (options::PATTERN_FILE, CRON_PATTERNS)];
+    let (matched, logmsg) = parse(&parser_options, SSHD_MESSAGE);
+    assert!(matched);
+    assert_eq!(b"SSH_DISCONNECT", logmsg.get(keys::PATTERN_NAME).unwrap());
+    let (matched, logmsg) = parse(&parser_options, CRON_MESSAGE);
+    assert!(matched);
+    assert_eq!(b"CRON_SESSION_OPENED", logmsg.get(keys::PATTERN_NAME).unwrap());
+    assert_eq!(b"root", logmsg.get("user").unwrap());
+}
+
+#[test]
+fn test_given_parser_when_a_uuid_is_duplicated_then_by_default_it_cannot_be_built() {
+    assert!(build(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                    (options::PATTERN_FILE, SSHD_OVERRIDE_PATTERNS)])
+                .is_err());
+    assert!(build(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                    (options::PATTERN_FILE, SSHD_OVERRIDE_PATTERNS),
+                    (options::DUPLICATE_UUIDS, "error")])
+                .is_err());
+}
+
+#[test]
+fn test_given_parser_when_the_first_duplicate_wins_then_the_first_pattern_is_used() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::PATTERN_FILE, SSHD_OVERRIDE_PATTERNS),
+                                    (options::DUPLICATE_UUIDS, "first-wins"),
+                                    (options::TEST_MESSAGES, "skip")],
+                                  SSHD_MESSAGE);
+    assert!(matched);
+    assert_eq!(b"SSH_DISCONNECT", logmsg.get(keys::PATTERN_NAME).unwrap());
+    assert_eq!(None, logmsg.get("reason"));
+}
+
+#[test]
+fn test_given_parser_when_the_last_duplicate_wins_then_the_last_pattern_is_used() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::PATTERN_FILE, SSHD_OVERRIDE_PATTERNS),
+                                    (options::DUPLICATE_UUIDS, "last-wins"),
+                                    (options::TEST_MESSAGES, "skip")],
+                                  SSHD_MESSAGE);
+    assert!(matched);
+    assert_eq!(b"SSH_DISCONNECT_OVERRIDE",
+               logmsg.get(keys::PATTERN_NAME).unwrap());
+    assert_eq!(b"logout", logmsg.get("reason").unwrap());
+}
+
+#[test]
+fn test_given_parser_builder_when_duplicate_uuids_is_set_then_the_policy_is_checked() {
+    assert!(set_option(options::DUPLICATE_UUIDS, "error").is_ok());
+    assert!(set_option(options::DUPLICATE_UUIDS, "first-wins").is_ok());
+    assert!(set_option(options::DUPLICATE_UUIDS, "last-wins").is_ok());
+    assert!(set_option(options::DUPLICATE_UUIDS, "newest-wins").is_err());
+}
+
+#[test]
+fn test_given_parser_builder_when_an_option_is_unknown_then_it_is_rejected() {
+    assert!(set_option("pattern_files", SSHD_PATTERNS).is_err());
+}
+
+#[test]
+fn test_given_parser_when_a_test_message_fails_then_only_the_strict_policy_fails_the_build() {
+    let pattern_file = (options::PATTERN_FILE, FAILING_TEST_MESSAGE_PATTERNS);
+    assert!(build(&[pattern_file]).is_err());
+    assert!(build(&[pattern_file, (options::TEST_MESSAGES, "strict")]).is_err());
+    assert!(build(&[pattern_file, (options::TEST_MESSAGES, "warn")]).is_ok());
+    assert!(build(&[pattern_file, (options::TEST_MESSAGES, "skip")]).is_ok());
+}
+
+#[test]
+fn test_given_parser_when_the_test_messages_are_skipped_then_the_patterns_still_match() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, FAILING_TEST_MESSAGE_PATTERNS),
+                                    (options::TEST_MESSAGES, "skip")],
+                                  "disk /dev/sda1 is full");
+    assert!(matched);
+    assert_eq!(b"/dev/sda1", logmsg.get("device").unwrap());
+}
+
+#[test]
+fn test_given_parser_builder_when_test_messages_is_set_then_the_policy_is_checked() {
+    assert!(set_option(options::TEST_MESSAGES, "strict").is_ok());
+    assert!(set_option(options::TEST_MESSAGES, "warn").is_ok());
+    assert!(set_option(options::TEST_MESSAGES, "skip").is_ok());
+    assert!(set_option(options::TEST_MESSAGES, "never").is_err());
+}
+
+#[test]
+fn test_given_parser_when_unmatched_tag_and_class_are_set_then_unmatched_messages_are_marked() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::UNMATCHED_TAG, ".classifier.unknown"),
+                                    (options::UNMATCHED_CLASS, "unknown")],
+                                  UNKNOWN_MESSAGE);
+    assert!(matched);
+    assert!(logmsg.tags().contains(&b".classifier.unknown".to_vec()));
+    assert_eq!(b"unknown", logmsg.get(keys::PATTERN_NAME).unwrap());
+}
+
+#[test]
+fn test_given_parser_when_only_the_unmatched_tag_is_set_then_the_class_is_not_set() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::UNMATCHED_TAG, ".classifier.unknown"),
+                                    (options::UNMATCHED_CLASS, "")],
+                                  UNKNOWN_MESSAGE);
+    assert!(matched);
+    assert!(logmsg.tags().contains(&b".classifier.unknown".to_vec()));
+    assert_eq!(None, logmsg.get(keys::PATTERN_NAME));
+}
+
+#[test]
+fn test_given_parser_when_unmatched_marking_is_enabled_then_matching_messages_are_not_marked() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::UNMATCHED_TAG, ".classifier.unknown"),
+                                    (options::UNMATCHED_CLASS, "unknown")],
+                                  SSHD_MESSAGE);
+    assert!(matched);
+    assert!(!logmsg.tags().contains(&b".classifier.unknown".to_vec()));
+    assert_eq!(b"SSH_DISCONNECT", logmsg.get(keys::PATTERN_NAME).unwrap());
+}
+
+#[test]
+fn test_given_parser_when_the_unmatched_options_are_empty_then_unmatched_messages_are_rejected() {
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::UNMATCHED_TAG, ""),
+                                    (options::UNMATCHED_CLASS, "")],
+                                  UNKNOWN_MESSAGE);
+    assert!(!matched);
+    assert_eq!(None, logmsg.get(keys::PATTERN_NAME));
+}
+
+#[test]
+fn test_given_parser_when_classifier_metadata_is_set_then_the_metadata_fields_are_set() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                              (options::CLASSIFIER_METADATA, "severity, owner")],
+                            SSHD_MESSAGE);
+    assert_eq!(b"notice", logmsg.get(keys::CLASSIFIER_SEVERITY).unwrap());
+    assert_eq!(b"secops", logmsg.get(keys::CLASSIFIER_OWNER).unwrap());
+    assert_eq!(None, logmsg.get(keys::CLASSIFIER_DESCRIPTION));
+}
+
+#[test]
+fn test_given_parser_builder_when_classifier_metadata_is_set_then_the_fields_are_checked() {
+    assert!(set_option(options::CLASSIFIER_METADATA, "description,references").is_ok());
+    assert!(set_option(options::CLASSIFIER_METADATA, "deprecated").is_ok());
+    assert!(set_option(options::CLASSIFIER_METADATA, "severity,color").is_err());
+}
+
+#[test]
+fn test_given_parser_when_the_compat_mode_is_disabled_then_the_compat_fields_are_not_set() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                              (options::PATTERNDB_COMPAT, "no")],
+                            SSHD_MESSAGE);
+    assert_eq!(None, logmsg.get(keys::CLASSIFIER_RULE_ID));
+    assert_eq!(None, logmsg.get(keys::CLASSIFIER_RULESET));
+}
+
+#[test]
+fn test_given_parser_when_the_compat_mode_is_enabled_then_the_rule_id_and_ruleset_are_set() {
+    let parser_options = [(options::PATTERN_FILE, SSHD_PATTERNS),
+                          (options::PATTERN_FILE, CRON_PATTERNS),
+                          (options::PATTERNDB_COMPAT, "yes")];
+    let (_, logmsg) = parse(&parser_options, SSHD_MESSAGE);
+    assert_eq!(b"9a49c47d-29e9-4072-be84-3b76c6814743",
+               logmsg.get(keys::CLASSIFIER_RULE_ID).unwrap());
+    assert_eq!(b"sshd", logmsg.get(keys::CLASSIFIER_RULESET).unwrap());
+    assert_eq!(b"SSH_DISCONNECT", logmsg.get(keys::PATTERN_NAME).unwrap());
+    let (_, logmsg) = parse(&parser_options, CRON_MESSAGE);
+    assert_eq!(b"cron", logmsg.get(keys::CLASSIFIER_RULESET).unwrap());
+}
+
+#[test]
+fn test_given_parser_when_the_ruleset_is_set_then_it_overrides_the_name_of_the_file() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                              (options::PATTERNDB_COMPAT, "true"),
+                              (options::RULESET, "linux")],
+                            SSHD_MESSAGE);
+    assert_eq!(b"linux", logmsg.get(keys::CLASSIFIER_RULESET).unwrap());
+}
+
+#[test]
+fn test_given_parser_when_the_class_comes_from_the_metadata_then_it_is_set_as_the_class() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                              (options::PATTERNDB_COMPAT, "yes"),
+                              (options::CLASS_SOURCE, "metadata")],
+                            SSHD_MESSAGE);
+    assert_eq!(b"session", logmsg.get(keys::PATTERN_NAME).unwrap());
+}
+
+#[test]
+fn test_given_parser_when_the_metadata_has_no_class_then_the_name_is_used_as_the_class() {
+    let (_, logmsg) = parse(&[(options::PATTERN_FILE, CRON_PATTERNS),
+                              (options::PATTERNDB_COMPAT, "yes"),
+                              (options::CLASS_SOURCE, "metadata")],
+                            CRON_MESSAGE);
+    assert_eq!(b"CRON_SESSION_OPENED", logmsg.get(keys::PATTERN_NAME).unwrap());
+}
+
+#[test]
+fn test_given_parser_builder_when_patterndb_compat_is_set_then_the_value_is_checked() {
+    assert!(set_option(options::PATTERNDB_COMPAT, "yes").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "no").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "true").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "false").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "1").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "0").is_ok());
+    assert!(set_option(options::PATTERNDB_COMPAT, "maybe").is_err());
+}
+
+#[test]
+fn test_given_parser_builder_when_class_source_is_set_then_the_value_is_checked() {
+    assert!(set_option(options::CLASS_SOURCE, "name").is_ok());
+    assert!(set_option(options::CLASS_SOURCE, "metadata").is_ok());
+    assert!(set_option(options::CLASS_SOURCE, "uuid").is_err());
+}
+
+#[test]
+fn test_given_parser_when_preprocessing_is_enabled_then_the_processed_message_is_matched() {
+    let input = "\u{1b}[1;31mReceived disconnect from 10.0.0.1: 11: disconnected by user\
+                 \u{1b}[0m\r\n";
+    let (matched, _) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS)], input);
+    assert!(!matched);
+    let (matched, logmsg) = parse(&[(options::PATTERN_FILE, SSHD_PATTERNS),
+                                    (options::PREPROCESS, "strip-control,trim")],
+                                  input);
+    assert!(matched);
+    assert_eq!(b"11", logmsg.get("code").unwrap());
+}
+
+#[test]
+fn test_given_parser_builder_when_preprocess_is_set_then_the_steps_are_checked() {
+    assert!(set_option(options::PREPROCESS, "normalize-whitespace, first-line").is_ok());
+    assert!(set_option(options::PREPROCESS, "strip-control,trim").is_ok());
+    assert!(set_option(options::PREPROCESS, "trim,lowercase").is_err());
+}
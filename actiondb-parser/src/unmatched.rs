@@ -0,0 +1,61 @@
+// Copyright (c) 2016 Tibor Benke <ihrwein@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+use syslog_ng_common::MessageFormatter;
+use syslog_ng_common::LogMessage;
+
+use keys;
+
+// Marks the messages without a matching pattern with a tag and a class, so
+// they can be routed instead of being rejected by the parser. Marking is
+// enabled if either of them is set.
+#[derive(Clone, Debug, Default)]
+pub struct UnmatchedMarker {
+    tag: Option<String>,
+    class: Option<String>,
+}
+
+impl UnmatchedMarker {
+    pub fn new() -> UnmatchedMarker {
+        UnmatchedMarker::default()
+    }
+
+    // An empty tag or class (the default of the `actiondb()` block) is
+    // not set.
+    pub fn set_tag(&mut self, tag: String) {
+        self.tag = non_empty(tag);
+    }
+
+    // The class is stored in the `.classifier.class` field, just like the
+    // name of a matching pattern.
+    pub fn set_class(&mut self, class: String) {
+        self.class = non_empty(class);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.tag.is_some() || self.class.is_some()
+    }
+
+    pub fn mark(&self, formatter: &mut MessageFormatter, msg: &mut LogMessage) {
+        if let Some(ref class) = self.class {
+            let (key, value) = formatter.format(keys::PATTERN_NAME, class);
+            msg.insert(key, value.as_bytes());
+        }
+        if let Some(ref tag) = self.tag {
+            msg.set_tag(tag.as_bytes());
+        }
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}